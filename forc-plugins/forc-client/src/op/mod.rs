@@ -2,6 +2,6 @@ mod deploy;
 mod run;
 mod submit;
 
-pub use deploy::deploy;
+pub use deploy::{deploy, deploy_pkg};
 pub use run::run;
 pub use submit::submit;
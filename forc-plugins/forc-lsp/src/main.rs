@@ -16,5 +16,8 @@ struct App {}
 async fn main() {
     App::parse();
 
-    sway_lsp::start().await
+    let exit_code = sway_lsp::start().await;
+    // Exit straight away rather than waiting for the runtime to join any compilations that are
+    // still running, so that the server never outlives the client.
+    std::process::exit(exit_code)
 }
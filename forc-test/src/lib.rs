@@ -66,6 +66,9 @@ pub struct TestResult {
     pub gas_used: u64,
 }
 
+/// A callback invoked with the result of each test as soon as it completes.
+pub type OnTestResult<'a> = dyn Fn(&TestResult) + Sync + 'a;
+
 const TEST_METADATA_SEED: u64 = 0x7E57u64;
 /// A mapping from each member package of a build plan to its compiled contract dependencies.
 type ContractDependencyMap = HashMap<pkg::Pinned, Vec<Arc<pkg::BuiltPackage>>>;
@@ -397,10 +400,13 @@ impl<'a> PackageTests {
     }
 
     /// Run all tests after applying the provided filter and collect their results.
+    ///
+    /// `on_test` is called with the result of each test as soon as it completes.
     pub(crate) fn run_tests(
         &self,
         test_runners: &rayon::ThreadPool,
         test_filter: Option<&TestFilter>,
+        on_test: &OnTestResult<'_>,
     ) -> anyhow::Result<TestedPackage> {
         let pkg_with_tests = self.built_pkg_with_tests();
         let tests = test_runners.install(|| {
@@ -447,7 +453,7 @@ impl<'a> PackageTests {
                     let span = test_entry.span.clone();
                     let file_path = test_entry.file_path.clone();
                     let condition = test_entry.pass_condition.clone();
-                    Ok(TestResult {
+                    let test_result = TestResult {
                         name,
                         file_path,
                         duration,
@@ -456,7 +462,9 @@ impl<'a> PackageTests {
                         condition,
                         logs,
                         gas_used,
-                    })
+                    };
+                    on_test(&test_result);
+                    Ok(test_result)
                 })
                 .collect::<anyhow::Result<_>>()
         })?;
@@ -616,6 +624,20 @@ impl BuiltTests {
         self,
         test_runner_count: TestRunnerCount,
         test_filter: Option<TestFilter>,
+    ) -> anyhow::Result<Tested> {
+        self.run_with_progress(test_runner_count, test_filter, &|_| {})
+    }
+
+    /// Run all built tests, calling `on_test` with the result of each test as soon as it
+    /// completes, return the result.
+    ///
+    /// Tests are executed in parallel, so `on_test` may be called from multiple threads and the
+    /// results are not reported in any particular order.
+    pub fn run_with_progress(
+        self,
+        test_runner_count: TestRunnerCount,
+        test_filter: Option<TestFilter>,
+        on_test: &OnTestResult<'_>,
     ) -> anyhow::Result<Tested> {
         let test_runners = match test_runner_count {
             TestRunnerCount::Manual(runner_count) => rayon::ThreadPoolBuilder::new()
@@ -623,7 +645,7 @@ impl BuiltTests {
                 .build(),
             TestRunnerCount::Auto => rayon::ThreadPoolBuilder::new().build(),
         }?;
-        run_tests(self, &test_runners, test_filter)
+        run_tests(self, &test_runners, test_filter, on_test)
     }
 }
 
@@ -702,16 +724,17 @@ fn run_tests(
     built: BuiltTests,
     test_runners: &rayon::ThreadPool,
     test_filter: Option<TestFilter>,
+    on_test: &OnTestResult<'_>,
 ) -> anyhow::Result<Tested> {
     match built {
         BuiltTests::Package(pkg) => {
-            let tested_pkg = pkg.run_tests(test_runners, test_filter.as_ref())?;
+            let tested_pkg = pkg.run_tests(test_runners, test_filter.as_ref(), on_test)?;
             Ok(Tested::Package(Box::new(tested_pkg)))
         }
         BuiltTests::Workspace(workspace) => {
             let tested_pkgs = workspace
                 .into_iter()
-                .map(|pkg| pkg.run_tests(test_runners, test_filter.as_ref()))
+                .map(|pkg| pkg.run_tests(test_runners, test_filter.as_ref(), on_test))
                 .collect::<anyhow::Result<Vec<TestedPackage>>>()?;
             Ok(Tested::Workspace(tested_pkgs))
         }
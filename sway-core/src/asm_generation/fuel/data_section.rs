@@ -2,7 +2,9 @@ use crate::{
     asm_generation::from_ir::ir_type_size_in_bytes, size_bytes_round_up_to_word_alignment,
 };
 
+use sway_error::error::CompileError;
 use sway_ir::{Constant, ConstantValue, Context};
+use sway_types::Span;
 
 use std::{
     collections::BTreeMap,
@@ -94,7 +96,7 @@ impl Entry {
         constant: &Constant,
         name: Option<String>,
         padding: Option<Padding>,
-    ) -> Entry {
+    ) -> Result<Entry, CompileError> {
         // We have to do some painful special handling here for enums, which are tagged unions.
         // This really should be handled by the IR more explicitly and is something that will
         // hopefully be addressed by https://github.com/FuelLabs/sway/issues/2819#issuecomment-1256930392
@@ -110,7 +112,7 @@ impl Entry {
                 // we use unions (otherwise we should be generalising this a bit more).
                 if let ConstantValue::Struct(els) = &constant.value {
                     if els.len() == 2 {
-                        let tag_entry = Entry::from_constant(context, &els[0], None, None);
+                        let tag_entry = Entry::from_constant(context, &els[0], None, None)?;
 
                         // Here's the special case.  We need to get the size of the union and
                         // attach it to this constant entry which will be one of the variants.
@@ -123,18 +125,22 @@ impl Entry {
                                 &els[1],
                                 None,
                                 Some(Padding::Left { target_size }),
-                            )
+                            )?
                         };
 
                         // Return here from our special case.
-                        return Entry::new_collection(vec![tag_entry, val_entry], name, padding);
+                        return Ok(Entry::new_collection(
+                            vec![tag_entry, val_entry],
+                            name,
+                            padding,
+                        ));
                     }
                 }
             }
         };
 
         // Not a tagged union, no trickiness required.
-        match &constant.value {
+        Ok(match &constant.value {
             ConstantValue::Undef | ConstantValue::Unit => Entry::new_byte(0, name, padding),
             ConstantValue::Bool(b) => Entry::new_byte(u8::from(*b), name, padding),
             ConstantValue::Uint(u) => {
@@ -147,6 +153,14 @@ impl Entry {
             ConstantValue::U256(u) => {
                 Entry::new_byte_array(u.to_be_bytes().to_vec(), name, padding)
             }
+            // The `u256` type of a fixed-point constant doesn't carry its scale, so writing out
+            // only the scaled value would silently lose it.
+            ConstantValue::Fixed { .. } => {
+                return Err(CompileError::Unimplemented(
+                    "Fixed-point constants are not supported in the data section yet.",
+                    Span::dummy(),
+                ))
+            }
             ConstantValue::B256(bs) => {
                 Entry::new_byte_array(bs.to_be_bytes().to_vec(), name, padding)
             }
@@ -155,7 +169,7 @@ impl Entry {
             ConstantValue::Array(els) => Entry::new_collection(
                 els.iter()
                     .map(|el| Entry::from_constant(context, el, None, None))
-                    .collect(),
+                    .collect::<Result<_, _>>()?,
                 name,
                 padding,
             ),
@@ -172,11 +186,11 @@ impl Entry {
                             Some(Padding::Right { target_size }),
                         )
                     })
-                    .collect(),
+                    .collect::<Result<_, _>>()?,
                 name,
                 padding,
             ),
-        }
+        })
     }
 
     /// Converts a literal to a big-endian representation. This is padded to words.
@@ -1724,8 +1724,8 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         constant: &Constant,
         config_name: Option<String>,
         span: Option<Span>,
-    ) -> (VirtualRegister, Option<DataId>) {
-        match &constant.value {
+    ) -> Result<(VirtualRegister, Option<DataId>), CompileError> {
+        Ok(match &constant.value {
             // Use cheaper $zero or $one registers if possible.
             ConstantValue::Unit | ConstantValue::Bool(false) | ConstantValue::Uint(0)
                 if config_name.is_none() =>
@@ -1739,7 +1739,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
 
             _otherwise => {
                 // Get the constant into the namespace.
-                let entry = Entry::from_constant(self.context, constant, config_name, None);
+                let entry = Entry::from_constant(self.context, constant, config_name, None)?;
                 let data_id = self.data_section.insert_data_value(entry);
 
                 // Allocate a register for it, and a load instruction.
@@ -1754,7 +1754,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 });
                 (reg, Some(data_id))
             }
-        }
+        })
 
         // Insert the value into the map.
         //self.reg_map.insert(*value, reg.clone());
@@ -1782,10 +1782,12 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         self.reg_map
             .get(value)
             .cloned()
+            .map(Ok)
             .or_else(|| {
                 value.get_constant(self.context).map(|constant| {
                     let span = self.md_mgr.val_to_span(self.context, *value);
-                    self.initialise_constant(constant, None, span).0
+                    self.initialise_constant(constant, None, span)
+                        .map(|(reg, _)| reg)
                 })
             })
             .or_else(|| {
@@ -1797,19 +1799,19 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                         .unwrap()
                         .to_string();
 
-                    let initialized =
-                        self.initialise_constant(constant, Some(config_name.clone()), span);
-                    if let Some(data_id) = initialized.1 {
+                    let (reg, data_id) =
+                        self.initialise_constant(constant, Some(config_name.clone()), span)?;
+                    if let Some(data_id) = data_id {
                         self.data_section.config_map.insert(config_name, data_id.0);
                     }
-                    initialized.0
+                    Ok(reg)
                 })
             })
-            .ok_or_else(|| {
-                CompileError::Internal(
+            .unwrap_or_else(|| {
+                Err(CompileError::Internal(
                     "An attempt to get register for unknown Value.",
                     Span::dummy(),
-                )
+                ))
             })
     }
 
@@ -256,7 +256,9 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             });
         }
 
-        let locals_alloc_result = self.alloc_locals(function);
+        let locals_alloc_result = self
+            .alloc_locals(function)
+            .map_err(|e| handler.emit_err(e))?;
 
         if func_is_entry {
             self.compile_external_args(function)
@@ -730,12 +732,15 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
     fn alloc_locals(
         &mut self,
         function: Function,
-    ) -> (
-        u64,
-        virtual_register::VirtualRegister,
-        Vec<InitMutVars>,
-        u64,
-    ) {
+    ) -> Result<
+        (
+            u64,
+            virtual_register::VirtualRegister,
+            Vec<InitMutVars>,
+            u64,
+        ),
+        CompileError,
+    > {
         // Scan the function to see if there are any calls to functions with more than
         // NUM_ARG_REGISTERS. The extra args will need stack allocation too.
         let mut max_num_extra_args = 0u64;
@@ -767,7 +772,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         // Otherwise they go in runtime allocated space, either a register or on the stack.
         //
         // Stack offsets are in words to both enforce alignment and simplify use with LW/SW.
-        let (stack_base_words, init_mut_vars) = function.locals_iter(self.context).fold(
+        let (stack_base_words, init_mut_vars) = function.locals_iter(self.context).try_fold(
             (0, Vec::new()),
             |(stack_base_words, mut init_mut_vars), (_name, ptr)| {
                 if let (false, Some(constant)) = (
@@ -779,9 +784,9 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                         constant,
                         None,
                         None,
-                    ));
+                    )?);
                     self.ptr_map.insert(*ptr, Storage::Data(data_id));
-                    (stack_base_words, init_mut_vars)
+                    Ok::<_, CompileError>((stack_base_words, init_mut_vars))
                 } else {
                     self.ptr_map.insert(*ptr, Storage::Stack(stack_base_words));
 
@@ -808,7 +813,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                             constant,
                             None,
                             None,
-                        ));
+                        )?);
 
                         init_mut_vars.push(InitMutVars {
                             stack_base_words,
@@ -818,10 +823,10 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                         });
                     }
 
-                    (stack_base_words + var_word_size, init_mut_vars)
+                    Ok((stack_base_words + var_word_size, init_mut_vars))
                 }
             },
-        );
+        )?;
 
         // Reserve space on the stack (in bytes) for all our locals which require it.  Firstly save
         // the current $sp.
@@ -844,12 +849,12 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             comment: format!("allocate {locals_size_bytes} bytes for locals and {max_num_extra_args} slots for call arguments."),
             owning_span: None,
         });
-        (
+        Ok((
             locals_size_bytes,
             locals_base_reg,
             init_mut_vars,
             max_num_extra_args,
-        )
+        ))
     }
 
     fn init_locals(
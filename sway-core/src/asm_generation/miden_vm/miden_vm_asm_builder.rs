@@ -332,7 +332,7 @@ impl<'ir, 'eng> MidenVMAsmBuilder<'ir, 'eng> {
                 } => todo!(),
                 InstOp::Nop => (),
                 InstOp::PtrToInt(ptr_val, int_ty) => todo!(),
-                InstOp::Ret(ret_val, ty) => self.compile_return(ret_val, ty),
+                InstOp::Ret(ret_val, ty) => self.compile_return(handler, ret_val, ty),
                 InstOp::Store {
                     dst_val_ptr,
                     stored_val,
@@ -583,11 +583,26 @@ impl<'ir, 'eng> MidenVMAsmBuilder<'ir, 'eng> {
 
     /// all we have to do here is make sure the returned value is on top of the stack
     /// this works in both internal procedure returns and external main returns
-    pub fn compile_return(&mut self, ret_val: &Value, ty: &Type) {
-        self.compile_value_access(ret_val)
-    }
-
-    fn compile_value_access(&mut self, ret_val: &Value) {
+    pub fn compile_return(&mut self, handler: &Handler, ret_val: &Value, ty: &Type) {
+        self.compile_value_access(handler, ret_val)
+    }
+
+    fn compile_value_access(&mut self, handler: &Handler, ret_val: &Value) {
+        // Fixed-point constants are typed as plain `u256`, so there's no way to lower them
+        // without losing their scale.
+        if let Some(Constant {
+            value: ConstantValue::Fixed { .. },
+            ..
+        }) = ret_val.get_constant(self.context)
+        {
+            handler.emit_err(CompileError::Unimplemented(
+                "Fixed-point constants are not supported by the Miden VM backend yet.",
+                self.md_mgr
+                    .val_to_span(self.context, *ret_val)
+                    .unwrap_or_else(Self::empty_span),
+            ));
+            return;
+        }
         self.push_op(MidenAsmOp::access_value(*ret_val));
     }
 
@@ -655,6 +670,7 @@ impl<'ir, 'eng> MidenVMAsmBuilder<'ir, 'eng> {
             Uint(x) => vec![DirectOp::push(*x)],
             U256(x) => todo!(),
             B256(_) => todo!(),
+            Fixed { .. } => unreachable!("fixed-point constants are rejected when accessed"),
             String(_) => todo!(),
             Array(_) => todo!(),
             Struct(_) => todo!(),
@@ -2,6 +2,7 @@ use std::{path::PathBuf, sync::Arc};
 
 use serde::{Deserialize, Serialize};
 use strum::EnumString;
+use sway_ir::OverflowMode;
 
 #[derive(
     Clone,
@@ -32,6 +33,19 @@ pub enum BuildTarget {
     MidenVM,
 }
 
+impl BuildTarget {
+    /// How constant arithmetic that overflows is folded, matching the semantics of the target VM.
+    ///
+    /// The EVM wraps around on overflow, while the Fuel VM and Miden VM fail at run time, so
+    /// overflowing expressions are left unfolded for them.
+    pub fn overflow_mode(&self) -> OverflowMode {
+        match self {
+            BuildTarget::Fuel | BuildTarget::MidenVM => OverflowMode::Checked,
+            BuildTarget::EVM => OverflowMode::Wrapping,
+        }
+    }
+}
+
 /// Configuration for the overall build and compilation process.
 #[derive(Clone)]
 pub struct BuildConfig {
@@ -13,11 +13,12 @@ use sway_types::span::Span;
 
 pub(crate) use purity::{check_function_purity, PurityEnv};
 
-use crate::{language::ty, Engines};
+use crate::{language::ty, BuildTarget, Engines};
 
 pub fn compile_program<'eng>(
     program: &ty::TyProgram,
     include_tests: bool,
+    build_target: BuildTarget,
     engines: &'eng Engines,
 ) -> Result<Context<'eng>, Vec<CompileError>> {
     let declaration_engine = engines.de();
@@ -53,6 +54,7 @@ pub fn compile_program<'eng>(
         ty::TyProgramKind::Contract { .. } => Kind::Contract,
         ty::TyProgramKind::Library { .. } => Kind::Library,
     };
+    ctx.overflow_mode = build_target.overflow_mode();
 
     match kind {
         // predicates and scripts have the same codegen, their only difference is static
@@ -107,3 +109,41 @@ pub fn compile_program<'eng>(
         )]
     })
 }
+
+/// The in-memory size of an enum and of each of its variants' payloads, as laid out in the IR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumLayout {
+    /// The size of the enum in bytes, including its tag.
+    pub size_in_bytes: u64,
+    /// The size in bytes of the payload of each variant, in declaration order.
+    pub variant_sizes_in_bytes: Vec<u64>,
+}
+
+/// Computes the [EnumLayout] of an enum with the given variants.
+///
+/// Fails if the type of a variant can't be laid out in memory, e.g. because it's generic.
+pub fn enum_layout(
+    engines: &Engines,
+    variants: &[ty::TyEnumVariant],
+) -> Result<EnumLayout, CompileError> {
+    let type_engine = engines.te();
+    let decl_engine = engines.de();
+    let mut ctx = Context::new(engines.se());
+    let enum_type = types::create_tagged_union_type(type_engine, decl_engine, &mut ctx, variants)?;
+    let variant_sizes_in_bytes = variants
+        .iter()
+        .map(|variant| {
+            convert::convert_resolved_typeid_no_span(
+                type_engine,
+                decl_engine,
+                &mut ctx,
+                &variant.type_argument.type_id,
+            )
+            .map(|variant_type| variant_type.size_in_bytes(&ctx))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(EnumLayout {
+        size_in_bytes: enum_type.size_in_bytes(&ctx),
+        variant_sizes_in_bytes,
+    })
+}
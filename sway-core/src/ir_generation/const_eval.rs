@@ -1,4 +1,4 @@
-use std::ops::{BitAnd, BitOr, BitXor, Not, Rem};
+use std::ops::{BitAnd, BitOr, BitXor, Not};
 
 use crate::{
     asm_generation::from_ir::{ir_type_size_in_bytes, ir_type_str_size_in_bytes},
@@ -26,7 +26,7 @@ use sway_ir::{
     metadata::combine as md_combine,
     module::Module,
     value::Value,
-    InstOp, Instruction, Type, TypeContent,
+    BinaryOpKind, InstOp, Instruction, Type, TypeContent,
 };
 use sway_types::{ident::Ident, integer_bits::IntegerBits, span::Spanned, Span};
 use sway_utils::mapped_stack::MappedStack;
@@ -747,44 +747,25 @@ fn const_eval_intrinsic(
             let ty = args[0].ty;
             assert!(args.len() == 2 && ty.eq(lookup.context, &args[1].ty));
 
+            let op = match intrinsic.kind {
+                Intrinsic::Add => BinaryOpKind::Add,
+                Intrinsic::Sub => BinaryOpKind::Sub,
+                Intrinsic::Mul => BinaryOpKind::Mul,
+                Intrinsic::Div => BinaryOpKind::Div,
+                Intrinsic::Mod => BinaryOpKind::Mod,
+                _ => unreachable!(),
+            };
+
             use ConstantValue::*;
             match (&args[0].value, &args[1].value) {
-                (Uint(arg1), Uint(ref arg2)) => {
-                    // All arithmetic is done as if it were u64
-                    let result = match intrinsic.kind {
-                        Intrinsic::Add => arg1.checked_add(*arg2),
-                        Intrinsic::Sub => arg1.checked_sub(*arg2),
-                        Intrinsic::Mul => arg1.checked_mul(*arg2),
-                        Intrinsic::Div => arg1.checked_div(*arg2),
-                        Intrinsic::Mod => arg1.checked_rem(*arg2),
-                        _ => unreachable!(),
-                    };
-
-                    match result {
-                        Some(result) => Ok(Some(Constant {
-                            ty,
-                            value: ConstantValue::Uint(result),
-                        })),
-                        None => Err(ConstEvalError::CannotBeEvaluatedToConst {
-                            span: intrinsic.span.clone(),
-                        }),
-                    }
-                }
-                (U256(arg1), U256(arg2)) => {
-                    let result = match intrinsic.kind {
-                        Intrinsic::Add => arg1.checked_add(arg2),
-                        Intrinsic::Sub => arg1.checked_sub(arg2),
-                        Intrinsic::Mul => arg1.checked_mul(arg2),
-                        Intrinsic::Div => arg1.checked_div(arg2),
-                        Intrinsic::Mod => Some(arg1.rem(arg2)),
-                        _ => unreachable!(),
-                    };
-
-                    match result {
-                        Some(result) => Ok(Some(Constant {
-                            ty,
-                            value: ConstantValue::U256(result),
-                        })),
+                (Uint(_), Uint(_)) | (U256(_), U256(_)) => {
+                    match args[0].try_fold_binary(
+                        lookup.context,
+                        op,
+                        &args[1],
+                        lookup.context.overflow_mode,
+                    ) {
+                        Some(result) => Ok(Some(result)),
                         None => Err(ConstEvalError::CannotBeEvaluatedToConst {
                             span: intrinsic.span.clone(),
                         }),
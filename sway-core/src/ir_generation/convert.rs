@@ -112,18 +112,27 @@ fn convert_resolved_type(
         TypeInfo::B256 => Type::get_b256(context),
         TypeInfo::StringSlice => Type::get_slice(context),
         TypeInfo::StringArray(n) => Type::new_string_array(context, n.val() as u64),
-        TypeInfo::Struct(decl_ref) => super::types::get_struct_for_types(
-            type_engine,
-            decl_engine,
-            context,
-            decl_engine
-                .get_struct(decl_ref)
-                .fields
-                .iter()
-                .map(|field| field.type_argument.type_id)
-                .collect::<Vec<_>>()
-                .as_slice(),
-        )?,
+        TypeInfo::Struct(decl_ref) => {
+            let decl = decl_engine.get_struct(decl_ref);
+            let struct_type = super::types::get_struct_for_types(
+                type_engine,
+                decl_engine,
+                context,
+                decl.fields
+                    .iter()
+                    .map(|field| field.type_argument.type_id)
+                    .collect::<Vec<_>>()
+                    .as_slice(),
+            )?;
+            struct_type.set_struct_field_names(
+                context,
+                decl.fields
+                    .iter()
+                    .map(|field| field.name.as_str().to_string())
+                    .collect(),
+            );
+            struct_type
+        }
         TypeInfo::Enum(decl_ref) => create_tagged_union_type(
             type_engine,
             decl_engine,
@@ -7,12 +7,13 @@ use crate::{
     },
     size_bytes_round_up_to_word_alignment,
 };
+use sway_error::error::CompileError;
 use sway_ir::{
     constant::{Constant, ConstantValue},
     context::Context,
     irtype::Type,
 };
-use sway_types::state::StateIndex;
+use sway_types::{state::StateIndex, Span};
 
 /// Determines how values that are less then a word in length
 /// has to be padded to word boundary when in structs or enums.
@@ -70,8 +71,8 @@ pub fn serialize_to_storage_slots(
     ix: &StateIndex,
     ty: &Type,
     indices: &[usize],
-) -> Vec<StorageSlot> {
-    match &constant.value {
+) -> Result<Vec<StorageSlot>, CompileError> {
+    Ok(match &constant.value {
         ConstantValue::Undef => vec![],
         // If not being a part of an aggregate, single byte values like `bool`, `u8`, and unit
         // are stored as a byte at the beginning of the storage slot.
@@ -148,6 +149,7 @@ pub fn serialize_to_storage_slots(
                 Bytes32::new(b.to_be_bytes()),
             )]
         }
+        ConstantValue::Fixed { .. } => return Err(fixed_point_in_storage_error()),
         ConstantValue::B256(b) if ty.is_b256(context) => {
             vec![StorageSlot::new(
                 get_storage_key(ix, indices),
@@ -162,7 +164,7 @@ pub fn serialize_to_storage_slots(
             // is a multiple of 4. This is useful because each storage slot is 4 words.
             // Regarding padding, the top level type in the call is either a string array, struct, or
             // a union. They will properly set the initial padding for the further recursive calls.
-            let mut packed = serialize_to_words(constant, context, ty, InByte8Padding::default());
+            let mut packed = serialize_to_words(constant, context, ty, InByte8Padding::default())?;
             packed.extend(vec![
                 Bytes8::new([0; 8]);
                 ((packed.len() + 3) / 4) * 4 - packed.len()
@@ -185,7 +187,7 @@ pub fn serialize_to_storage_slots(
                 .collect()
         }
         _ => vec![],
-    }
+    })
 }
 
 /// Given a constant value `constant` and a type `ty`, serialize the constant into a vector of
@@ -195,8 +197,8 @@ fn serialize_to_words(
     context: &Context,
     ty: &Type,
     padding: InByte8Padding,
-) -> Vec<Bytes8> {
-    match &constant.value {
+) -> Result<Vec<Bytes8>, CompileError> {
+    Ok(match &constant.value {
         ConstantValue::Undef => vec![],
         ConstantValue::Unit if ty.is_unit(context) => vec![Bytes8::new([0; 8])],
         ConstantValue::Bool(b) if ty.is_bool(context) => match padding {
@@ -218,6 +220,7 @@ fn serialize_to_words(
             let b = b.to_be_bytes();
             Vec::from_iter((0..4).map(|i| Bytes8::new(b[8 * i..8 * i + 8].try_into().unwrap())))
         }
+        ConstantValue::Fixed { .. } => return Err(fixed_point_in_storage_error()),
         ConstantValue::B256(b) if ty.is_b256(context) => {
             let b = b.to_be_bytes();
             Vec::from_iter((0..4).map(|i| Bytes8::new(b[8 * i..8 * i + 8].try_into().unwrap())))
@@ -245,8 +248,9 @@ fn serialize_to_words(
             let field_tys = ty.get_field_types(context);
             vec.iter()
                 .zip(field_tys.iter())
-                .flat_map(|(f, ty)| serialize_to_words(f, context, ty, InByte8Padding::Right))
-                .collect()
+                .map(|(f, ty)| serialize_to_words(f, context, ty, InByte8Padding::Right))
+                .collect::<Result<Vec<_>, _>>()?
+                .concat()
         }
         _ if ty.is_union(context) => {
             let value_size_in_words =
@@ -263,12 +267,21 @@ fn serialize_to_words(
                 .iter()
                 .cloned()
                 .chain(
-                    serialize_to_words(constant, context, &constant.ty, InByte8Padding::Left)
+                    serialize_to_words(constant, context, &constant.ty, InByte8Padding::Left)?
                         .iter()
                         .cloned(),
                 )
                 .collect()
         }
         _ => vec![],
-    }
+    })
+}
+
+/// Fixed-point constants are typed as plain `u256`, which doesn't carry their scale, so they are
+/// rejected rather than stored as a bare `u256` that would read back with the scale lost.
+fn fixed_point_in_storage_error() -> CompileError {
+    CompileError::Unimplemented(
+        "Fixed-point constants are not supported in storage yet.",
+        Span::dummy(),
+    )
 }
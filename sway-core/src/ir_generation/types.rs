@@ -54,7 +54,12 @@ pub(super) fn create_tuple_aggregate(
         .map(|ty_id| convert_resolved_typeid_no_span(type_engine, decl_engine, context, &ty_id))
         .collect::<Result<Vec<_>, CompileError>>()?;
 
-    Ok(Type::new_struct(context, field_types))
+    // Tuple fields are named by their positions, which also stops a struct with the same field
+    // types from lending its field names to the tuple.
+    let field_names = (0..field_types.len()).map(|idx| idx.to_string()).collect();
+    let tuple_type = Type::new_struct(context, field_types);
+    tuple_type.set_struct_field_names(context, field_names);
+    Ok(tuple_type)
 }
 
 pub(super) fn create_array_aggregate(
@@ -119,6 +119,16 @@ pub fn parse(
     }
 }
 
+/// Lexes the input provided, without parsing it.
+///
+/// Comments are stripped from the returned token stream.
+pub fn lex(
+    input: Arc<str>,
+    handler: &Handler,
+) -> Result<sway_ast::token::TokenStream, ErrorEmitted> {
+    sway_parse::lex(handler, &input, 0, input.len(), None)
+}
+
 /// Parses the tree kind in the input provided.
 ///
 /// This will lex the entire input, but parses only the module kind.
@@ -734,8 +744,12 @@ pub(crate) fn compile_ast_to_ir_to_asm(
     // errors and then hold as a runtime invariant that none of the types will be unresolved in the
     // IR phase.
 
-    let mut ir = match ir_generation::compile_program(program, build_config.include_tests, engines)
-    {
+    let mut ir = match ir_generation::compile_program(
+        program,
+        build_config.include_tests,
+        build_config.build_target,
+        engines,
+    ) {
         Ok(ir) => ir,
         Err(errors) => {
             let mut last = None;
@@ -68,7 +68,7 @@ impl ty::TyStorageField {
             None,
             &self.initializer,
         )
-        .map(|constant| serialize_to_storage_slots(&constant, context, ix, &constant.ty, &[]))
+        .and_then(|constant| serialize_to_storage_slots(&constant, context, ix, &constant.ty, &[]))
     }
 }
 
@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use sway_ir::{Constant, Context, Type};
+use sway_types::SourceEngine;
+
+fn benchmarks(c: &mut Criterion) {
+    let source_engine = SourceEngine::default();
+    let mut context = Context::new(&source_engine);
+    let u64_ty = Type::get_uint64(&context);
+    let big_array = |context: &mut Context| {
+        let elems = (0..10_000)
+            .map(|n| Constant::new_uint(context, 64, n))
+            .collect();
+        Constant::new_array(context, u64_ty, elems)
+    };
+    let a = big_array(&mut context);
+    let b = big_array(&mut context);
+
+    c.bench_function("eq_big_array", |bencher| {
+        bencher.iter(|| black_box(&a).eq(&context, black_box(&b)))
+    });
+
+    c.bench_function("structural_eq_big_array", |bencher| {
+        bencher.iter(|| black_box(&a).structural_eq(&context, black_box(&b)))
+    });
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
@@ -1,19 +1,170 @@
 //! [`Constant`] is a typed constant value.
 
-use crate::{context::Context, irtype::Type, pretty::DebugWithContext, value::Value};
+use crate::{
+    context::Context,
+    error::IrError,
+    instruction::{BinaryOpKind, Predicate, UnaryOpKind},
+    irtype::Type,
+    pretty::DebugWithContext,
+    value::Value,
+};
+use rustc_hash::FxHasher;
+use std::{
+    fmt::{self, Write},
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
 use sway_types::u256::U256;
 
 /// A [`Type`] and constant value, including [`ConstantValue::Undef`] for uninitialized constants.
-#[derive(Debug, Clone, DebugWithContext, Hash)]
+#[derive(Debug, Clone)]
 pub struct Constant {
     pub ty: Type,
     pub value: ConstantValue,
 }
 
+/// Only the value of a constant is hashed, so that constants which are [`Constant::eq`] always have
+/// the same hash.
+///
+/// Hashing the type would be stable within a context, as types are uniqued by their content, but
+/// a union type is equal to each of its variant types without being the same type.
+impl Hash for Constant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
 pub type B256 = U256;
 
+/// The elements of an array or struct constant, along with the hash of their values.
+///
+/// The hash is computed once, when the elements are created, so that hashing an aggregate doesn't
+/// walk its elements and [`Constant::eq`] can tell aggregates with different hashes apart without
+/// comparing their elements.
+#[derive(Clone)]
+pub struct ConstantElements {
+    elems: Vec<Constant>,
+    hash: u64,
+}
+
+impl ConstantElements {
+    pub fn new(elems: Vec<Constant>) -> Self {
+        let mut hasher = FxHasher::default();
+        elems.hash(&mut hasher);
+        ConstantElements {
+            elems,
+            hash: hasher.finish(),
+        }
+    }
+
+    pub fn as_slice(&self) -> &[Constant] {
+        &self.elems
+    }
+
+    pub fn into_vec(self) -> Vec<Constant> {
+        self.elems
+    }
+}
+
+impl From<Vec<Constant>> for ConstantElements {
+    fn from(elems: Vec<Constant>) -> Self {
+        ConstantElements::new(elems)
+    }
+}
+
+impl Deref for ConstantElements {
+    type Target = [Constant];
+
+    fn deref(&self) -> &[Constant] {
+        &self.elems
+    }
+}
+
+impl<'a> IntoIterator for &'a ConstantElements {
+    type Item = &'a Constant;
+    type IntoIter = std::slice::Iter<'a, Constant>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elems.iter()
+    }
+}
+
+impl Hash for ConstantElements {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+impl fmt::Debug for ConstantElements {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.elems.fmt(f)
+    }
+}
+
+/// How constant folding treats arithmetic whose result doesn't fit in the type of its operands.
+///
+/// The bounds of integers narrower than 64 bits are those of their own width, e.g. `255` for `u8`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OverflowMode {
+    /// The result wraps around at the bounds of the type.
+    Wrapping,
+    /// The result is clamped to the bounds of the type.
+    Saturating,
+    /// Overflowing expressions are not folded, so they are left to fail at run time.
+    #[default]
+    Checked,
+}
+
+/// A binary operation that [`Constant::try_eval_binop`] can evaluate.
+#[derive(Debug, Clone, Copy, Hash)]
+pub enum BinaryOp {
+    /// An arithmetic, bitwise or shift operation, resulting in a constant of the operands' type.
+    Arith(BinaryOpKind),
+    /// A comparison, resulting in a `bool` constant.
+    Cmp(Predicate),
+}
+
+impl From<BinaryOpKind> for BinaryOp {
+    fn from(op: BinaryOpKind) -> Self {
+        BinaryOp::Arith(op)
+    }
+}
+
+impl From<Predicate> for BinaryOp {
+    fn from(pred: Predicate) -> Self {
+        BinaryOp::Cmp(pred)
+    }
+}
+
+/// A comparison that [`Constant::try_fold_cmp`] can evaluate.
+///
+/// This is a superset of the [`Predicate`]s of the `cmp` instruction, so that passes can also fold
+/// the negated and non-strict comparisons they build from them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComparisonOp {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl From<Predicate> for ComparisonOp {
+    fn from(pred: Predicate) -> Self {
+        match pred {
+            Predicate::Equal => ComparisonOp::Equal,
+            Predicate::LessThan => ComparisonOp::LessThan,
+            Predicate::GreaterThan => ComparisonOp::GreaterThan,
+        }
+    }
+}
+
 /// A constant representation of each of the supported [`Type`]s.
-#[derive(Debug, Clone, DebugWithContext, Hash)]
+///
+/// `Debug` is implemented manually so that [`ConstantValue::String`] is rendered as escaped text
+/// rather than as a list of bytes.
+#[derive(Clone, Hash)]
 pub enum ConstantValue {
     Undef,
     Unit,
@@ -22,8 +173,154 @@ pub enum ConstantValue {
     U256(U256),
     B256(B256),
     String(Vec<u8>),
-    Array(Vec<Constant>),
-    Struct(Vec<Constant>),
+    Array(ConstantElements),
+    Struct(ConstantElements),
+    /// A fixed-point number, stored as the integer `value` scaled by `10^scale`, so that e.g.
+    /// `1.25` with a `scale` of 2 has a `value` of 125. Its type is `u256`, the type of `value`,
+    /// which doesn't carry the scale, so the backends reject fixed-point constants rather than
+    /// lowering them as plain `u256`s.
+    ///
+    /// The same number has a different representation for each scale, so fixed-point constants
+    /// are only equal if their scales are, and are only folded with constants of the same scale.
+    Fixed {
+        value: U256,
+        scale: u8,
+    },
+}
+
+impl ConstantValue {
+    fn fmt_with(
+        &self,
+        f: &mut fmt::Formatter,
+        prefix: &str,
+        fmt_elems: impl Fn(&[Constant], &mut fmt::DebugTuple),
+    ) -> fmt::Result {
+        let name = |variant: &str| format!("{prefix}{variant}");
+        match self {
+            ConstantValue::Undef => f.write_str(&name("Undef")),
+            ConstantValue::Unit => f.write_str(&name("Unit")),
+            ConstantValue::Bool(b) => f.debug_tuple(&name("Bool")).field(b).finish(),
+            ConstantValue::Uint(n) => f.debug_tuple(&name("Uint")).field(n).finish(),
+            ConstantValue::U256(n) => f.debug_tuple(&name("U256")).field(n).finish(),
+            ConstantValue::B256(n) => f.debug_tuple(&name("B256")).field(n).finish(),
+            ConstantValue::Fixed { value, scale } => f
+                .debug_struct(&name("Fixed"))
+                .field("value", value)
+                .field("scale", scale)
+                .finish(),
+            ConstantValue::String(bytes) => f
+                .debug_tuple(&name("String"))
+                .field(&format_args!("\"{}\"", escape_string_bytes(bytes)))
+                .finish(),
+            ConstantValue::Array(elems) => {
+                let mut debug_tuple = f.debug_tuple(&name("Array"));
+                fmt_elems(elems, &mut debug_tuple);
+                debug_tuple.finish()
+            }
+            ConstantValue::Struct(elems) => {
+                let mut debug_tuple = f.debug_tuple(&name("Struct"));
+                fmt_elems(elems, &mut debug_tuple);
+                debug_tuple.finish()
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ConstantValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with(f, "", |elems, debug_tuple| {
+            debug_tuple.field(&elems);
+        })
+    }
+}
+
+impl DebugWithContext for ConstantValue {
+    fn fmt_with_context(&self, f: &mut fmt::Formatter, context: &Context) -> fmt::Result {
+        self.fmt_with(f, "ConstantValue::", |elems, debug_tuple| {
+            debug_tuple.field(&elems.with_context(context));
+        })
+    }
+}
+
+impl DebugWithContext for Constant {
+    fn fmt_with_context(&self, f: &mut fmt::Formatter, context: &Context) -> fmt::Result {
+        f.debug_struct("Constant")
+            .field("ty", &self.ty.with_context(context))
+            .field("value", &TypedConstantValue(self).with_context(context))
+            .finish()
+    }
+}
+
+/// The value of a [Constant], debug printed using its type.
+///
+/// Struct fields are printed with the field names recorded for the type, falling back to their
+/// positional indices for tuples and structs without names.
+struct TypedConstantValue<'a>(&'a Constant);
+
+impl DebugWithContext for TypedConstantValue<'_> {
+    fn fmt_with_context(&self, f: &mut fmt::Formatter, context: &Context) -> fmt::Result {
+        let Constant { ty, value } = self.0;
+        match value {
+            ConstantValue::Struct(fields) => {
+                let names = ty.get_struct_field_names(context);
+                let mut debug_struct = f.debug_struct("ConstantValue::Struct");
+                for (idx, field) in fields.iter().enumerate() {
+                    let name = names
+                        .and_then(|names| names.get(idx))
+                        .cloned()
+                        .unwrap_or_else(|| idx.to_string());
+                    debug_struct.field(&name, &field.with_context(context));
+                }
+                debug_struct.finish()
+            }
+            _ => value.fmt_with_context(f, context),
+        }
+    }
+}
+
+/// Renders string bytes as readable text.
+///
+/// Valid UTF-8 is rendered as is, except for quotes, backslashes and control characters which
+/// are escaped. Bytes which aren't valid UTF-8 are rendered as `\xNN`.
+pub(crate) fn escape_string_bytes(bytes: &[u8]) -> String {
+    fn escape_str(s: &str, escaped: &mut String) {
+        for c in s.chars() {
+            match c {
+                '\n' => escaped.push_str("\\n"),
+                '\t' => escaped.push_str("\\t"),
+                '\r' => escaped.push_str("\\r"),
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                c if c.is_ascii_control() => write!(escaped, "\\x{:02x}", c as u8).unwrap(),
+                c if c.is_control() => write!(escaped, "{}", c.escape_unicode()).unwrap(),
+                c => escaped.push(c),
+            }
+        }
+    }
+
+    let mut escaped = String::with_capacity(bytes.len());
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                escape_str(valid, &mut escaped);
+                break;
+            }
+            Err(err) => {
+                let (valid, invalid) = rest.split_at(err.valid_up_to());
+                escape_str(
+                    std::str::from_utf8(valid).expect("prefix is valid UTF-8"),
+                    &mut escaped,
+                );
+                let invalid_len = err.error_len().unwrap_or(invalid.len());
+                for b in &invalid[..invalid_len] {
+                    write!(escaped, "\\x{b:02x}").unwrap();
+                }
+                rest = &invalid[invalid_len..];
+            }
+        }
+    }
+    escaped
 }
 
 impl Constant {
@@ -59,6 +356,40 @@ impl Constant {
         }
     }
 
+    /// Parses a `u256` constant from a decimal number, or a hex number prefixed with `0x`.
+    ///
+    /// Values which don't fit in 256 bits are an error, rather than being truncated.
+    pub fn new_u256_from_str(context: &mut Context, s: &str) -> Result<Self, IrError> {
+        let invalid = |why_str: String| IrError::InvalidIntegerLiteral(s.to_string(), why_str);
+        let (digits, radix, radix_name) = match s.strip_prefix("0x") {
+            Some(digits) => (digits, 16, "hex"),
+            None => (s, 10, "decimal"),
+        };
+        if digits.is_empty() {
+            return Err(invalid(format!("expected {radix_name} digits")));
+        }
+        if let Some(c) = digits.chars().find(|c| !c.is_digit(radix)) {
+            return Err(invalid(format!("'{c}' is not a {radix_name} digit")));
+        }
+        let value = digits
+            .chars()
+            .try_fold(U256::from(0), |value, c| {
+                value
+                    .checked_mul(&U256::from(radix as u64))?
+                    .checked_add(&U256::from(c.to_digit(radix).unwrap() as u64))
+            })
+            .ok_or_else(|| invalid("the value doesn't fit in 256 bits".to_string()))?;
+        Ok(Self::new_uint256(context, value))
+    }
+
+    /// Creates a fixed-point constant of `value / 10^scale`, see [`ConstantValue::Fixed`].
+    pub fn new_fixed(context: &mut Context, value: U256, scale: u8) -> Self {
+        Constant {
+            ty: Type::new_uint(context, 256),
+            value: ConstantValue::Fixed { value, scale },
+        }
+    }
+
     pub fn new_b256(context: &Context, bytes: [u8; 32]) -> Self {
         Constant {
             ty: Type::get_b256(context),
@@ -66,6 +397,30 @@ impl Constant {
         }
     }
 
+    /// Parses a `b256` constant from 64 hex digits, optionally prefixed with `0x`, in big-endian
+    /// order.
+    pub fn new_b256_from_hex(context: &Context, hex: &str) -> Result<Self, IrError> {
+        let digits = hex.strip_prefix("0x").unwrap_or(hex);
+        if digits.len() != 64 {
+            return Err(IrError::InvalidHexLiteral(
+                hex.to_string(),
+                format!("expected 64 hex digits, found {}", digits.len()),
+            ));
+        }
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(IrError::InvalidHexLiteral(
+                hex.to_string(),
+                format!("'{c}' is not a hex digit"),
+            ));
+        }
+        let mut bytes = [0u8; 32];
+        for (byte, pair) in bytes.iter_mut().zip(digits.as_bytes().chunks(2)) {
+            let nybble = |c: u8| (c as char).to_digit(16).unwrap() as u8;
+            *byte = (nybble(pair[0]) << 4) | nybble(pair[1]);
+        }
+        Ok(Self::new_b256(context, bytes))
+    }
+
     pub fn new_string(context: &mut Context, string: Vec<u8>) -> Self {
         Constant {
             ty: Type::new_string_array(context, string.len() as u64),
@@ -73,17 +428,45 @@ impl Constant {
         }
     }
 
+    /// Creates a string array constant holding the UTF-8 bytes of `s`.
+    ///
+    /// Use [`Constant::new_string()`] for strings which aren't valid UTF-8.
+    pub fn new_string_from_str(context: &mut Context, s: &str) -> Self {
+        Self::new_string(context, s.as_bytes().to_vec())
+    }
+
+    /// Creates an array constant of `elems`, which must all be of type `elm_ty`.
+    ///
+    /// The length of the array is that of `elems`, so for an empty array `elm_ty` is the only
+    /// source of its element type. See also [`Constant::new_empty_array()`].
     pub fn new_array(context: &mut Context, elm_ty: Type, elems: Vec<Constant>) -> Self {
         Constant {
             ty: Type::new_array(context, elm_ty, elems.len() as u64),
-            value: ConstantValue::Array(elems),
+            value: ConstantValue::Array(elems.into()),
         }
     }
 
+    /// Creates a zero-length array constant of `elm_ty` elements, i.e. of type `[elm_ty; 0]`.
+    ///
+    /// Empty arrays are equal exactly when their element types are.
+    pub fn new_empty_array(context: &mut Context, elm_ty: Type) -> Self {
+        Self::new_array(context, elm_ty, Vec::new())
+    }
+
+    /// Creates a `[u8; N]` constant of the given bytes.
+    pub fn new_byte_array(context: &mut Context, bytes: &[u8]) -> Self {
+        let elems = bytes
+            .iter()
+            .map(|b| Constant::new_uint(context, 8, *b as u64))
+            .collect();
+        let u8_ty = Type::get_uint8(context);
+        Self::new_array(context, u8_ty, elems)
+    }
+
     pub fn new_struct(context: &mut Context, field_tys: Vec<Type>, fields: Vec<Constant>) -> Self {
         Constant {
             ty: Type::new_struct(context, field_tys),
-            value: ConstantValue::Struct(fields),
+            value: ConstantValue::Struct(fields.into()),
         }
     }
 
@@ -114,6 +497,11 @@ impl Constant {
         Value::new_constant(context, new_const)
     }
 
+    pub fn get_fixed(context: &mut Context, value: U256, scale: u8) -> Value {
+        let new_const = Constant::new_fixed(context, value, scale);
+        Value::new_constant(context, new_const)
+    }
+
     pub fn get_b256(context: &mut Context, value: [u8; 32]) -> Value {
         let new_const = Constant::new_b256(context, value);
         Value::new_constant(context, new_const)
@@ -124,6 +512,14 @@ impl Constant {
         Value::new_constant(context, new_const)
     }
 
+    /// Creates a string array constant value holding the UTF-8 bytes of `value`.
+    ///
+    /// Use [`Constant::get_string()`] for strings which aren't valid UTF-8.
+    pub fn get_string_from_str(context: &mut Context, value: &str) -> Value {
+        let new_const = Constant::new_string_from_str(context, value);
+        Value::new_constant(context, new_const)
+    }
+
     /// `value` must be created as an array constant first, using [`Constant::new_array()`].
     pub fn get_array(context: &mut Context, value: Constant) -> Value {
         assert!(value.ty.is_array(context));
@@ -136,8 +532,480 @@ impl Constant {
         Value::new_constant(context, value)
     }
 
+    /// Returns a constant [`Value`] of this constant, like [`Value::new_constant()`], but reuses
+    /// the value returned by an earlier call for an equal constant instead of creating a new one.
+    ///
+    /// Only constants of the very same type are reused, as [`Constant::eq`] also considers a union
+    /// type equal to its variant types.  The returned value may be shared, so metadata shouldn't
+    /// be added to it.
+    pub fn into_cached_value(self, context: &mut Context) -> Value {
+        let mut hasher = FxHasher::default();
+        self.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let cached = context.constant_values.get(&hash).and_then(|values| {
+            values.iter().copied().find(|value| {
+                value
+                    .get_constant(context)
+                    .is_some_and(|constant| constant.ty == self.ty && constant.eq(context, &self))
+            })
+        });
+        if let Some(value) = cached {
+            return value;
+        }
+
+        let value = Value::new_constant(context, self);
+        context.constant_values.entry(hash).or_default().push(value);
+        value
+    }
+
+    /// Returns the value of a bool constant, or `None` for any other constant.
+    ///
+    /// ```
+    /// # use sway_ir::{Constant, Context};
+    /// # let source_engine = sway_types::SourceEngine::default();
+    /// # let mut context = Context::new(&source_engine);
+    /// assert_eq!(Constant::new_bool(&context, true).as_bool(), Some(true));
+    /// assert_eq!(Constant::new_uint(&mut context, 64, 1).as_bool(), None);
+    /// ```
+    pub fn as_bool(&self) -> Option<bool> {
+        match &self.value {
+            ConstantValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an integer constant of up to 64 bits, or `None` for any other constant.
+    ///
+    /// ```
+    /// # use sway_ir::{Constant, Context};
+    /// # let source_engine = sway_types::SourceEngine::default();
+    /// # let mut context = Context::new(&source_engine);
+    /// assert_eq!(Constant::new_uint(&mut context, 8, 42).as_uint(), Some(42));
+    /// assert_eq!(Constant::new_bool(&context, true).as_uint(), None);
+    /// ```
+    pub fn as_uint(&self) -> Option<u64> {
+        match &self.value {
+            ConstantValue::Uint(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a `u256` constant, or `None` for any other constant, including `b256`
+    /// constants.
+    ///
+    /// ```
+    /// # use sway_ir::{Constant, Context};
+    /// # use sway_types::u256::U256;
+    /// # let source_engine = sway_types::SourceEngine::default();
+    /// # let mut context = Context::new(&source_engine);
+    /// let n = Constant::new_uint256(&mut context, U256::from(42));
+    /// assert_eq!(n.as_u256(), Some(U256::from(42)));
+    /// assert_eq!(Constant::new_b256(&context, [0; 32]).as_u256(), None);
+    /// ```
+    pub fn as_u256(&self) -> Option<U256> {
+        match &self.value {
+            ConstantValue::U256(n) => Some(n.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a `b256` constant, or `None` for any other constant, including `u256`
+    /// constants.
+    ///
+    /// ```
+    /// # use sway_ir::{Constant, Context, B256};
+    /// # let source_engine = sway_types::SourceEngine::default();
+    /// # let mut context = Context::new(&source_engine);
+    /// let mut bytes = [0; 32];
+    /// bytes[31] = 1;
+    /// let b = Constant::new_b256(&context, bytes);
+    /// assert_eq!(b.as_b256(), Some(B256::from_be_bytes(&bytes)));
+    /// assert_eq!(Constant::new_uint(&mut context, 64, 1).as_b256(), None);
+    /// ```
+    pub fn as_b256(&self) -> Option<B256> {
+        match &self.value {
+            ConstantValue::B256(n) => Some(n.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the fields of a struct constant, each paired with its type as declared by the
+    /// struct's type, or `None` if this isn't a struct constant.
+    ///
+    /// Also returns `None` if the number of fields doesn't match the struct's type, rather than
+    /// pairing fields with the wrong types.
+    ///
+    /// ```
+    /// # use sway_ir::{Constant, Context, Type};
+    /// # let source_engine = sway_types::SourceEngine::default();
+    /// # let mut context = Context::new(&source_engine);
+    /// let u64_ty = Type::get_uint64(&context);
+    /// let bool_ty = Type::get_bool(&context);
+    /// let n = Constant::new_uint(&mut context, 64, 42);
+    /// let b = Constant::new_bool(&context, true);
+    /// let s = Constant::new_struct(&mut context, vec![u64_ty, bool_ty], vec![n.clone(), b.clone()]);
+    /// let fields = s.struct_fields(&context).unwrap();
+    /// assert_eq!(fields.len(), 2);
+    /// assert!(fields[0].0.eq(&context, &u64_ty) && fields[0].1.eq(&context, &n));
+    /// assert!(fields[1].0.eq(&context, &bool_ty) && fields[1].1.eq(&context, &b));
+    /// assert!(n.struct_fields(&context).is_none());
+    /// ```
+    pub fn struct_fields(&self, context: &Context) -> Option<Vec<(Type, &Constant)>> {
+        let ConstantValue::Struct(fields) = &self.value else {
+            return None;
+        };
+        let field_tys = self.ty.get_field_types(context);
+        (self.ty.is_struct(context) && field_tys.len() == fields.len())
+            .then(|| field_tys.into_iter().zip(fields).collect())
+    }
+
+    /// Returns the contents of a string constant as readable text, or `None` if this isn't a
+    /// string constant. See [`ConstantValue`]'s `Debug` output for how bytes are escaped.
+    pub fn string_lossy(&self) -> Option<String> {
+        match &self.value {
+            ConstantValue::String(bytes) => Some(escape_string_bytes(bytes)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this constant is zero: a `0` integer or `b256`, `false`, or an array or
+    /// struct whose elements are all zero.
+    pub fn is_zero(&self) -> bool {
+        match &self.value {
+            ConstantValue::Bool(b) => !b,
+            ConstantValue::Uint(n) => *n == 0,
+            ConstantValue::U256(n)
+            | ConstantValue::B256(n)
+            | ConstantValue::Fixed { value: n, .. } => *n == U256::from(0),
+            ConstantValue::Array(elems) | ConstantValue::Struct(elems) => {
+                elems.iter().all(Constant::is_zero)
+            }
+            ConstantValue::Undef | ConstantValue::Unit | ConstantValue::String(_) => false,
+        }
+    }
+
+    /// Returns `true` if every bit of this constant is set: an integer equal to the maximum
+    /// value of its width, an all ones `b256`, `true`, or an array or struct whose elements are
+    /// all ones.
+    pub fn is_all_ones(&self, context: &Context) -> bool {
+        match &self.value {
+            ConstantValue::Bool(b) => *b,
+            ConstantValue::Uint(n) => match self.ty.get_uint_width(context) {
+                Some(width @ 1..=64) => *n == u64::MAX >> (64 - width),
+                _ => false,
+            },
+            ConstantValue::U256(n)
+            | ConstantValue::B256(n)
+            | ConstantValue::Fixed { value: n, .. } => {
+                n.to_be_bytes().iter().all(|b| *b == u8::MAX)
+            }
+            ConstantValue::Array(elems) | ConstantValue::Struct(elems) => {
+                elems.iter().all(|elem| elem.is_all_ones(context))
+            }
+            ConstantValue::Undef | ConstantValue::Unit | ConstantValue::String(_) => false,
+        }
+    }
+
+    /// Returns the width in bits of an integer or bool constant, or `None` for any other type.
+    ///
+    /// The width is read from the constant's type rather than its value, so `u8`, `u64` and
+    /// `u256` constants report `8`, `64` and `256` respectively, and bools report `1`.
+    pub fn bit_width(&self, context: &Context) -> Option<u32> {
+        if self.ty.is_bool(context) {
+            Some(1)
+        } else {
+            self.ty.get_uint_width(context).map(u32::from)
+        }
+    }
+
+    /// Returns the number of bytes this constant occupies in memory on the VM.
+    ///
+    /// The size is that of the constant's type, so struct fields are padded to words and array
+    /// elements are packed, as described in [Type::size_in_bytes].
+    pub fn size_in_bytes(&self, context: &Context) -> u64 {
+        self.ty.size_in_bytes(context)
+    }
+
+    /// Returns the index of the variant of an enum constant, or `None` if this isn't an enum.
+    ///
+    /// Enums are lowered to a struct of a `u64` tag and a union of the variant types, or of just
+    /// the tag if no variant has a payload. See [Constant::enum_payload] for the payload.
+    pub fn enum_variant_index(&self, context: &Context) -> Option<u64> {
+        self.enum_tag_and_payload(context).map(|(tag, _)| tag)
+    }
+
+    /// Returns the payload of an enum constant, which is a unit constant for variants without
+    /// one, or `None` if this isn't an enum.
+    pub fn enum_payload(&self, context: &Context) -> Option<&Constant> {
+        self.enum_tag_and_payload(context)
+            .map(|(_, payload)| payload)
+    }
+
+    fn enum_tag_and_payload(&self, context: &Context) -> Option<(u64, &Constant)> {
+        let fields = match &self.value {
+            ConstantValue::Struct(fields) => fields,
+            _ => return None,
+        };
+        // Enums whose variants have no payload only have a tag in their type, but their constants
+        // still hold a unit payload.
+        let is_enum = match self.ty.get_field_types(context).as_slice() {
+            [tag_ty, union_ty] => tag_ty.is_uint64(context) && union_ty.is_union(context),
+            [tag_ty] => tag_ty.is_uint64(context) && fields.len() == 2,
+            _ => false,
+        };
+        match fields.as_slice() {
+            [Constant {
+                value: ConstantValue::Uint(tag),
+                ..
+            }, payload]
+                if is_enum =>
+            {
+                Some((*tag, payload))
+            }
+            _ => None,
+        }
+    }
+
+    /// Evaluates `op` on this constant and `rhs`, treating overflows according to `mode`.
+    ///
+    /// Integers overflow at the bounds of the width of their type. Returns `None` if the operands
+    /// aren't integers of matching types, on division by zero, or on overflow in
+    /// [`OverflowMode::Checked`]. Only the shift amount of `lsh` and `rsh` may be of a different
+    /// type, e.g. a `u64` amount for a `u256` value.
+    ///
+    /// Fixed-point constants are folded with [`Constant::try_fold_fixed`] instead.
+    pub fn try_fold_binary(
+        &self,
+        context: &Context,
+        op: BinaryOpKind,
+        rhs: &Constant,
+        mode: OverflowMode,
+    ) -> Option<Constant> {
+        use BinaryOpKind::*;
+        use ConstantValue::*;
+        if !matches!(op, Lsh | Rsh) && !self.ty.eq(context, &rhs.ty) {
+            return None;
+        }
+        if let (Fixed { .. }, Fixed { .. }) = (&self.value, &rhs.value) {
+            return self.try_fold_fixed(op, rhs, mode);
+        }
+        let value = match (op, &self.value, &rhs.value) {
+            (Add | Sub | Mul, Uint(l), Uint(r)) => {
+                let max = uint_max(self.ty.get_uint_width(context)?)?;
+                fold_uint_overflowing(op, *l, *r, max, mode).map(Uint)
+            }
+            (Add, U256(l), U256(r)) => match mode {
+                OverflowMode::Wrapping => Some(U256(l.wrapping_add(r))),
+                OverflowMode::Saturating => Some(U256(l.saturating_add(r))),
+                OverflowMode::Checked => l.checked_add(r).map(U256),
+            },
+
+            (Sub, U256(l), U256(r)) => match mode {
+                OverflowMode::Wrapping => Some(U256(l.wrapping_sub(r))),
+                OverflowMode::Saturating => Some(U256(l.saturating_sub(r))),
+                OverflowMode::Checked => l.checked_sub(r).map(U256),
+            },
+
+            (Mul, U256(l), U256(r)) => match mode {
+                OverflowMode::Wrapping => Some(U256(l.wrapping_mul(r))),
+                OverflowMode::Saturating => Some(U256(l.saturating_mul(r))),
+                OverflowMode::Checked => l.checked_mul(r).map(U256),
+            },
+
+            (Div, Uint(l), Uint(r)) => l.checked_div(*r).map(Uint),
+            (Div, U256(l), U256(r)) => l.checked_div(r).map(U256),
+
+            (Mod, Uint(l), Uint(r)) => l.checked_rem(*r).map(Uint),
+            (Mod, U256(l), U256(r)) => {
+                (*r != sway_types::u256::U256::from(0u64)).then(|| U256(l % r))
+            }
+
+            (And, Uint(l), Uint(r)) => Some(Uint(l & r)),
+            (And, U256(l), U256(r)) => Some(U256(l & r)),
+
+            (Or, Uint(l), Uint(r)) => Some(Uint(l | r)),
+            (Or, U256(l), U256(r)) => Some(U256(l | r)),
+
+            (Xor, Uint(l), Uint(r)) => Some(Uint(l ^ r)),
+            (Xor, U256(l), U256(r)) => Some(U256(l ^ r)),
+
+            (Rsh, Uint(l), Uint(r)) => u32::try_from(*r)
+                .ok()
+                .and_then(|r| l.checked_shr(r).map(Uint)),
+            (Rsh, U256(l), Uint(r)) => Some(U256(l.shr(r))),
+
+            (Lsh, Uint(l), Uint(r)) => {
+                let max = uint_max(self.ty.get_uint_width(context)?)?;
+                u32::try_from(*r)
+                    .ok()
+                    .and_then(|r| l.checked_shl(r))
+                    .map(|shifted| Uint(shifted & max))
+            }
+            (Lsh, U256(l), Uint(r)) => l.checked_shl(r).map(U256),
+            _ => None,
+        }?;
+        Some(Constant { ty: self.ty, value })
+    }
+
+    /// Evaluates the arithmetic `op` on two fixed-point constants, treating overflows of additions
+    /// and subtractions according to `mode`.
+    ///
+    /// The result has the same scale as the operands. Products are divided by `10^scale` and
+    /// dividends are multiplied by it, truncating the result towards zero, and are only folded if
+    /// these intermediate values don't overflow. Returns `None` if the scales of the operands
+    /// differ, for operations other than `add`, `sub`, `mul` and `div`, and on division by zero.
+    fn try_fold_fixed(&self, op: BinaryOpKind, rhs: &Constant, mode: OverflowMode) -> Option<Self> {
+        let (
+            ConstantValue::Fixed {
+                value: l,
+                scale: l_scale,
+            },
+            ConstantValue::Fixed {
+                value: r,
+                scale: r_scale,
+            },
+        ) = (&self.value, &rhs.value)
+        else {
+            return None;
+        };
+        if l_scale != r_scale {
+            return None;
+        }
+        let scale = *l_scale;
+        let one = (0..scale).try_fold(U256::from(1), |one, _| one.checked_mul(&U256::from(10)))?;
+        let value = match op {
+            BinaryOpKind::Add => match mode {
+                OverflowMode::Wrapping => Some(l.wrapping_add(r)),
+                OverflowMode::Saturating => Some(l.saturating_add(r)),
+                OverflowMode::Checked => l.checked_add(r),
+            },
+            BinaryOpKind::Sub => match mode {
+                OverflowMode::Wrapping => Some(l.wrapping_sub(r)),
+                OverflowMode::Saturating => Some(l.saturating_sub(r)),
+                OverflowMode::Checked => l.checked_sub(r),
+            },
+            BinaryOpKind::Mul => l.checked_mul(r)?.checked_div(&one),
+            BinaryOpKind::Div => l.checked_mul(&one)?.checked_div(r),
+            _ => None,
+        }?;
+        Some(Constant {
+            ty: self.ty,
+            value: ConstantValue::Fixed { value, scale },
+        })
+    }
+
+    /// Evaluates the binary operation `op` on `lhs` and `rhs`, so that passes can fold any
+    /// operation on constants with a single call.
+    ///
+    /// Arithmetic is folded with [`Constant::try_fold_binary`] using the overflow mode of the
+    /// context. Returns `None` if the operation can't be folded for the operand types, e.g. when
+    /// comparing aggregates for order or when either operand is undefined.
+    pub fn try_eval_binop(
+        context: &Context,
+        op: BinaryOp,
+        lhs: &Constant,
+        rhs: &Constant,
+    ) -> Option<Constant> {
+        match op {
+            BinaryOp::Arith(op) => lhs.try_fold_binary(context, op, rhs, context.overflow_mode),
+            BinaryOp::Cmp(pred) => Constant::try_fold_cmp(context, pred.into(), lhs, rhs),
+        }
+    }
+
+    /// Evaluates the comparison `op` of `lhs` and `rhs` to a `bool` constant.
+    ///
+    /// Integers and `b256`s are compared as unsigned numbers, and `bool`s can only be compared for
+    /// equality. Returns `None` if the operands have different types or can't be compared with
+    /// `op`.
+    pub fn try_fold_cmp(
+        context: &Context,
+        op: ComparisonOp,
+        lhs: &Constant,
+        rhs: &Constant,
+    ) -> Option<Constant> {
+        use ConstantValue::*;
+        if !lhs.ty.eq(context, &rhs.ty) {
+            return None;
+        }
+        // Whether the operands are equal, and whether `lhs` is less than `rhs` if they're ordered.
+        let (eq, lt) = match (&lhs.value, &rhs.value) {
+            (Uint(l), Uint(r)) => (l == r, Some(l < r)),
+            (U256(l), U256(r)) | (B256(l), B256(r)) => (l == r, Some(l < r)),
+            (
+                Fixed {
+                    value: l,
+                    scale: l_scale,
+                },
+                Fixed {
+                    value: r,
+                    scale: r_scale,
+                },
+            ) if l_scale == r_scale => (l == r, Some(l < r)),
+            (Bool(l), Bool(r)) => (l == r, None),
+            _ => return None,
+        };
+        let result = match op {
+            ComparisonOp::Equal => eq,
+            ComparisonOp::NotEqual => !eq,
+            ComparisonOp::LessThan => lt?,
+            ComparisonOp::LessThanOrEqual => lt? || eq,
+            ComparisonOp::GreaterThan => !(lt? || eq),
+            ComparisonOp::GreaterThanOrEqual => !lt?,
+        };
+        Some(Constant::new_bool(context, result))
+    }
+
+    /// Evaluates the unary operation `op` on `operand`.
+    ///
+    /// `not` only inverts the bits within the width of the operand's type. Returns `None` if the
+    /// operation can't be folded for the operand type.
+    pub fn try_eval_unop(
+        context: &Context,
+        op: UnaryOpKind,
+        operand: &Constant,
+    ) -> Option<Constant> {
+        use ConstantValue::*;
+        let value = match (op, &operand.value) {
+            (UnaryOpKind::Not, Uint(v)) => {
+                let max = uint_max(operand.ty.get_uint_width(context)?)?;
+                Uint((!v) & max)
+            }
+            (UnaryOpKind::Not, U256(v)) => U256(!v),
+            _ => return None,
+        };
+        Some(Constant {
+            ty: operand.ty,
+            value,
+        })
+    }
+
     /// Compare two Constant values. Can't impl PartialOrder because of context.
+    ///
+    /// Arrays and structs whose cached hashes differ are unequal without comparing their elements.
+    /// Otherwise large arrays and structs are compared by their byte representations, which is much
+    /// faster than comparing their elements one by one, unless either of them contains an `Undef`.
     pub fn eq(&self, context: &Context, other: &Self) -> bool {
+        if let (
+            ConstantValue::Array(l0) | ConstantValue::Struct(l0),
+            ConstantValue::Array(r0) | ConstantValue::Struct(r0),
+        ) = (&self.value, &other.value)
+        {
+            if l0.hash != r0.hash {
+                return false;
+            }
+        }
+        if self.is_large_aggregate() && other.is_large_aggregate() {
+            if let (Some(l0), Some(r0)) = (self.to_bytes(), other.to_bytes()) {
+                return l0 == r0 && self.ty.eq(context, &other.ty);
+            }
+        }
+        self.structural_eq(context, other)
+    }
+
+    /// Compare two Constant values element by element, without the fast path of [Constant::eq]
+    /// for large arrays and structs. The result is always the same as that of [Constant::eq].
+    pub fn structural_eq(&self, context: &Context, other: &Self) -> bool {
         self.ty.eq(context, &other.ty)
             && match (&self.value, &other.value) {
                 // Two Undefs are *NOT* equal (PartialEq allows this).
@@ -147,12 +1015,1458 @@ impl Constant {
                 (ConstantValue::Uint(l0), ConstantValue::Uint(r0)) => l0 == r0,
                 (ConstantValue::U256(l0), ConstantValue::U256(r0)) => l0 == r0,
                 (ConstantValue::B256(l0), ConstantValue::B256(r0)) => l0 == r0,
+                (
+                    ConstantValue::Fixed {
+                        value: l0,
+                        scale: l1,
+                    },
+                    ConstantValue::Fixed {
+                        value: r0,
+                        scale: r1,
+                    },
+                ) => l0 == r0 && l1 == r1,
                 (ConstantValue::String(l0), ConstantValue::String(r0)) => l0 == r0,
-                (ConstantValue::Array(l0), ConstantValue::Array(r0))
-                | (ConstantValue::Struct(l0), ConstantValue::Struct(r0)) => {
-                    l0.iter().zip(r0.iter()).all(|(l0, r0)| l0.eq(context, r0))
+                (ConstantValue::Array(l0), ConstantValue::Array(r0)) => {
+                    Self::elements_eq(context, l0, r0)
+                }
+                (ConstantValue::Struct(l0), ConstantValue::Struct(r0)) => {
+                    Self::elements_eq(context, l0, r0)
                 }
+                // An array is never equal to a struct, even one with the same elements, whatever
+                // the comparison of their types says.
+                (ConstantValue::Array(_), ConstantValue::Struct(_))
+                | (ConstantValue::Struct(_), ConstantValue::Array(_)) => false,
                 _ => false,
             }
     }
+
+    fn elements_eq(context: &Context, l0: &[Constant], r0: &[Constant]) -> bool {
+        l0.len() == r0.len() && l0.iter().zip(r0.iter()).all(|(l0, r0)| l0.eq(context, r0))
+    }
+
+    fn is_large_aggregate(&self) -> bool {
+        const MIN_ELEMENTS: usize = 16;
+        matches!(
+            &self.value,
+            ConstantValue::Array(elems) | ConstantValue::Struct(elems)
+                if elems.len() >= MIN_ELEMENTS
+        )
+    }
+
+    /// Returns a byte representation of the value, or `None` if it is or contains an `Undef`.
+    ///
+    /// Two constants of equal types have the same bytes exactly if they are [Constant::eq]. The
+    /// bytes aren't the memory layout of the value, each value is tagged with its kind and strings,
+    /// arrays and structs with their length, so that the bytes of different values never collide.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        fn write_bytes(value: &ConstantValue, bytes: &mut Vec<u8>) -> Option<()> {
+            match value {
+                ConstantValue::Undef => return None,
+                ConstantValue::Unit => bytes.push(0),
+                ConstantValue::Bool(b) => bytes.extend([1, *b as u8]),
+                ConstantValue::Uint(n) => {
+                    bytes.push(2);
+                    bytes.extend(n.to_be_bytes());
+                }
+                ConstantValue::U256(n) => {
+                    bytes.push(3);
+                    bytes.extend(n.to_be_bytes());
+                }
+                ConstantValue::B256(n) => {
+                    bytes.push(4);
+                    bytes.extend(n.to_be_bytes());
+                }
+                ConstantValue::String(s) => {
+                    bytes.push(5);
+                    bytes.extend((s.len() as u64).to_be_bytes());
+                    bytes.extend(s);
+                }
+                ConstantValue::Array(elems) | ConstantValue::Struct(elems) => {
+                    bytes.push(if matches!(value, ConstantValue::Array(_)) {
+                        6
+                    } else {
+                        7
+                    });
+                    bytes.extend((elems.len() as u64).to_be_bytes());
+                    for elem in elems {
+                        write_bytes(&elem.value, bytes)?;
+                    }
+                }
+                ConstantValue::Fixed { value, scale } => {
+                    bytes.extend([8, *scale]);
+                    bytes.extend(value.to_be_bytes());
+                }
+            }
+            Some(())
+        }
+
+        let mut bytes = Vec::new();
+        write_bytes(&self.value, &mut bytes)?;
+        Some(bytes)
+    }
+
+    /// Compare only the values of two constants, ignoring their types.
+    ///
+    /// Unlike [Constant::eq] this is *not* type-safe. Integers are compared numerically across
+    /// widths, so `u8 0`, `u64 0` and `u256 0` are all equal, and arrays and structs are equal
+    /// if their elements are, whatever their nominal types. Callers must know that constants of
+    /// the different types are interchangeable, e.g. after a cast.
+    pub fn value_eq(&self, context: &Context, other: &Self) -> bool {
+        match (&self.value, &other.value) {
+            // Two Undefs are *NOT* equal, as in `eq`.
+            (ConstantValue::Undef, _) | (_, ConstantValue::Undef) => false,
+            (
+                ConstantValue::Uint(_) | ConstantValue::U256(_),
+                ConstantValue::Uint(_) | ConstantValue::U256(_),
+            ) => self.int_value(context) == other.int_value(context),
+            (ConstantValue::Unit, ConstantValue::Unit) => true,
+            (ConstantValue::Bool(l0), ConstantValue::Bool(r0)) => l0 == r0,
+            (ConstantValue::B256(l0), ConstantValue::B256(r0)) => l0 == r0,
+            (
+                ConstantValue::Fixed {
+                    value: l0,
+                    scale: l1,
+                },
+                ConstantValue::Fixed {
+                    value: r0,
+                    scale: r1,
+                },
+            ) => l0 == r0 && l1 == r1,
+            (ConstantValue::String(l0), ConstantValue::String(r0)) => l0 == r0,
+            (ConstantValue::Array(l0), ConstantValue::Array(r0))
+            | (ConstantValue::Struct(l0), ConstantValue::Struct(r0)) => {
+                l0.len() == r0.len()
+                    && l0
+                        .iter()
+                        .zip(r0.iter())
+                        .all(|(l0, r0)| l0.value_eq(context, r0))
+            }
+            _ => false,
+        }
+    }
+
+    /// Joins the constants `a` and `b` flowing into a control-flow merge, as in constant
+    /// propagation: an `Undef` may be any value, so the defined constant wins over it, and equal
+    /// constants join to themselves.
+    ///
+    /// Returns `None` if the constants conflict, i.e. they are defined and not equal, or if their
+    /// types differ. Only a whole constant is taken as `Undef`, not the elements of an aggregate.
+    pub fn join(context: &Context, a: &Constant, b: &Constant) -> Option<Constant> {
+        if !a.ty.eq(context, &b.ty) {
+            return None;
+        }
+        match (&a.value, &b.value) {
+            (ConstantValue::Undef, _) => Some(b.clone()),
+            (_, ConstantValue::Undef) => Some(a.clone()),
+            _ if a.eq(context, b) => Some(a.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns this integer constant as an integer of `to_nbits` bits, keeping only its low
+    /// `to_nbits` bits.  Values that don't fit are masked rather than rejected, e.g. truncating
+    /// `u64 0x1234` to 8 bits gives `u8 0x34`.
+    ///
+    /// Returns `None` if this isn't an integer constant, `to_nbits` isn't a valid integer width
+    /// or it is wider than the type of this constant.
+    pub fn truncate(&self, context: &mut Context, to_nbits: u16) -> Option<Self> {
+        let value = self.int_value(context)?;
+        if !Self::is_int_width(to_nbits) || to_nbits > self.ty.get_uint_width(context)? {
+            return None;
+        }
+        if to_nbits == 256 {
+            return Some(Self::new_uint256(context, value));
+        }
+        let low_word = u64::try_from(&value & &U256::from(u64::MAX)).ok()?;
+        Some(Self::new_uint(
+            context,
+            to_nbits,
+            low_word & (u64::MAX >> (64 - to_nbits)),
+        ))
+    }
+
+    /// Returns this integer constant as an integer of `to_nbits` bits with the same value, the
+    /// new high bits being zero.
+    ///
+    /// Returns `None` if this isn't an integer constant, `to_nbits` isn't a valid integer width
+    /// or it is narrower than the type of this constant.
+    pub fn zero_extend(&self, context: &mut Context, to_nbits: u16) -> Option<Self> {
+        let value = self.int_value(context)?;
+        if !Self::is_int_width(to_nbits) || to_nbits < self.ty.get_uint_width(context)? {
+            return None;
+        }
+        if to_nbits == 256 {
+            return Some(Self::new_uint256(context, value));
+        }
+        let value = u64::try_from(value).ok()?;
+        Some(Self::new_uint(context, to_nbits, value))
+    }
+
+    /// Whether `nbits` is the width of an integer type, as created by [Type::new_uint].
+    fn is_int_width(nbits: u16) -> bool {
+        matches!(nbits, 1..=64 | 256)
+    }
+
+    /// The numeric value of an integer constant, truncated to the width of its type.
+    fn int_value(&self, context: &Context) -> Option<U256> {
+        match &self.value {
+            ConstantValue::Uint(n) => match self.ty.get_uint_width(context) {
+                Some(width @ 1..=63) => Some(U256::from(n & (u64::MAX >> (64 - width)))),
+                _ => Some(U256::from(*n)),
+            },
+            ConstantValue::U256(n) => Some(n.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// The largest value of an unsigned integer `width` bits wide, if it fits in a `u64`.
+fn uint_max(width: u16) -> Option<u64> {
+    match width {
+        8 => Some(u8::MAX as u64),
+        16 => Some(u16::MAX as u64),
+        32 => Some(u32::MAX as u64),
+        64 => Some(u64::MAX),
+        _ => None,
+    }
+}
+
+/// Evaluates the arithmetic `op`, which is one of `add`, `sub` and `mul`, on unsigned integers
+/// whose largest value is `max`, treating overflows according to `mode`.
+fn fold_uint_overflowing(
+    op: BinaryOpKind,
+    l: u64,
+    r: u64,
+    max: u64,
+    mode: OverflowMode,
+) -> Option<u64> {
+    // The wrapped result at 64 bits, which masked by `max` is the wrapped result at any width, and
+    // the exact result, or `None` if it's negative.
+    let (wrapped, exact) = match op {
+        BinaryOpKind::Add => (l.wrapping_add(r), Some(u128::from(l) + u128::from(r))),
+        BinaryOpKind::Sub => (l.wrapping_sub(r), l.checked_sub(r).map(u128::from)),
+        BinaryOpKind::Mul => (l.wrapping_mul(r), Some(u128::from(l) * u128::from(r))),
+        _ => return None,
+    };
+    match (mode, exact) {
+        (_, Some(exact)) if exact <= u128::from(max) => Some(exact as u64),
+        (OverflowMode::Wrapping, _) => Some(wrapped & max),
+        (OverflowMode::Saturating, Some(_)) => Some(max),
+        (OverflowMode::Saturating, None) => Some(0),
+        (OverflowMode::Checked, _) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sway_types::SourceEngine;
+
+    #[test]
+    fn is_zero_and_is_all_ones_for_uints() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        for width in [8, 16, 32, 64] {
+            let zero = Constant::new_uint(&mut context, width, 0);
+            assert!(zero.is_zero());
+            assert!(!zero.is_all_ones(&context));
+
+            let max = u64::MAX >> (64 - width);
+            let ones = Constant::new_uint(&mut context, width, max);
+            assert!(!ones.is_zero());
+            assert!(ones.is_all_ones(&context));
+
+            let not_ones = Constant::new_uint(&mut context, width, max - 1);
+            assert!(!not_ones.is_zero());
+            assert!(!not_ones.is_all_ones(&context));
+        }
+
+        assert!(Constant::new_uint(&mut context, 256, 0).is_zero());
+        let ones = Constant::new_uint256(&mut context, U256::from_be_bytes(&[0xff; 32]));
+        assert!(ones.is_all_ones(&context));
+        let not_ones = Constant::new_uint(&mut context, 256, u64::MAX);
+        assert!(!not_ones.is_all_ones(&context));
+    }
+
+    #[test]
+    fn is_zero_and_is_all_ones_for_bools_and_b256() {
+        let se = SourceEngine::default();
+        let context = Context::new(&se);
+
+        assert!(Constant::new_bool(&context, false).is_zero());
+        assert!(!Constant::new_bool(&context, false).is_all_ones(&context));
+        assert!(Constant::new_bool(&context, true).is_all_ones(&context));
+        assert!(!Constant::new_bool(&context, true).is_zero());
+
+        assert!(Constant::new_b256(&context, [0; 32]).is_zero());
+        assert!(Constant::new_b256(&context, [0xff; 32]).is_all_ones(&context));
+        let mut bytes = [0; 32];
+        bytes[31] = 1;
+        assert!(!Constant::new_b256(&context, bytes).is_zero());
+    }
+
+    #[test]
+    fn is_zero_and_is_all_ones_for_aggregates() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let u64_ty = Type::get_uint64(&context);
+        let bool_ty = Type::get_bool(&context);
+        let fields = vec![
+            Constant::new_uint(&mut context, 64, 0),
+            Constant::new_bool(&context, false),
+        ];
+        let zeroed = Constant::new_struct(&mut context, vec![u64_ty, bool_ty], fields);
+        assert!(zeroed.is_zero());
+        assert!(!zeroed.is_all_ones(&context));
+
+        let fields = vec![
+            Constant::new_uint(&mut context, 64, 0),
+            Constant::new_bool(&context, true),
+        ];
+        let mixed = Constant::new_struct(&mut context, vec![u64_ty, bool_ty], fields);
+        assert!(!mixed.is_zero());
+        assert!(!mixed.is_all_ones(&context));
+
+        let elems = vec![
+            Constant::new_uint(&mut context, 64, u64::MAX),
+            Constant::new_uint(&mut context, 64, u64::MAX),
+        ];
+        let ones = Constant::new_array(&mut context, u64_ty, elems);
+        assert!(ones.is_all_ones(&context));
+        assert!(!ones.is_zero());
+
+        let undef = Constant::get_undef(u64_ty);
+        assert!(!undef.is_zero());
+        assert!(!undef.is_all_ones(&context));
+    }
+
+    #[test]
+    fn string_lossy_escapes_bytes() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let plain = Constant::new_string(&mut context, b"hello world".to_vec());
+        assert_eq!(plain.string_lossy().unwrap(), "hello world");
+
+        let control = Constant::new_string(&mut context, b"a\nb\tc\"d\\\x07".to_vec());
+        assert_eq!(control.string_lossy().unwrap(), r#"a\nb\tc\"d\\\x07"#);
+
+        let utf8 = Constant::new_string(&mut context, "héllo".as_bytes().to_vec());
+        assert_eq!(utf8.string_lossy().unwrap(), "héllo");
+
+        let invalid = Constant::new_string(&mut context, vec![b'a', 0xff, 0xc3, b'b']);
+        assert_eq!(invalid.string_lossy().unwrap(), r"a\xff\xc3b");
+
+        assert_eq!(
+            format!("{:?}", control.value),
+            r#"String("a\nb\tc\"d\\\x07")"#
+        );
+        assert!(Constant::new_bool(&context, true).string_lossy().is_none());
+    }
+
+    #[test]
+    fn try_fold_binary_overflow_modes() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let max = Constant::new_uint(&mut context, 64, u64::MAX);
+        let one = Constant::new_uint(&mut context, 64, 1);
+        let zero = Constant::new_uint(&mut context, 64, 0);
+        let fold =
+            |l: &Constant, op, r: &Constant, mode| match l.try_fold_binary(&context, op, r, mode) {
+                Some(Constant {
+                    value: ConstantValue::Uint(n),
+                    ..
+                }) => Some(n),
+                _ => None,
+            };
+
+        assert_eq!(
+            fold(&max, BinaryOpKind::Add, &one, OverflowMode::Wrapping),
+            Some(0)
+        );
+        assert_eq!(
+            fold(&max, BinaryOpKind::Add, &one, OverflowMode::Saturating),
+            Some(u64::MAX)
+        );
+        assert_eq!(
+            fold(&max, BinaryOpKind::Add, &one, OverflowMode::Checked),
+            None
+        );
+        assert_eq!(
+            fold(&zero, BinaryOpKind::Sub, &one, OverflowMode::Wrapping),
+            Some(u64::MAX)
+        );
+        assert_eq!(
+            fold(&zero, BinaryOpKind::Sub, &one, OverflowMode::Saturating),
+            Some(0)
+        );
+        assert_eq!(
+            fold(&zero, BinaryOpKind::Sub, &one, OverflowMode::Checked),
+            None
+        );
+
+        // Division by zero is never folded.
+        for mode in [
+            OverflowMode::Wrapping,
+            OverflowMode::Saturating,
+            OverflowMode::Checked,
+        ] {
+            assert_eq!(fold(&one, BinaryOpKind::Div, &zero, mode), None);
+            assert_eq!(fold(&one, BinaryOpKind::Mod, &zero, mode), None);
+        }
+    }
+
+    #[test]
+    fn try_fold_binary_overflow_modes_narrow_uints() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        // Constants of each narrow width: its largest value, a value which overflows when added
+        // to itself or multiplied by two, and one.
+        let widths = [
+            (8, u8::MAX as u64, 200),
+            (16, u16::MAX as u64, 40_000),
+            (32, u32::MAX as u64, 3_000_000_000),
+        ]
+        .map(|(width, max, big)| {
+            (
+                max,
+                Constant::new_uint(&mut context, width, big),
+                Constant::new_uint(&mut context, width, 1),
+                Constant::new_uint(&mut context, width, 2),
+                Constant::new_uint(&mut context, width, 0),
+            )
+        });
+        let fold =
+            |l: &Constant, op, r: &Constant, mode| match l.try_fold_binary(&context, op, r, mode) {
+                Some(Constant {
+                    value: ConstantValue::Uint(n),
+                    ..
+                }) => Some(n),
+                _ => None,
+            };
+
+        for (max, big, one, two, zero) in &widths {
+            let Constant {
+                value: ConstantValue::Uint(big_value),
+                ..
+            } = big
+            else {
+                unreachable!()
+            };
+            let wrapped = (2 * big_value) & max;
+            for op in [BinaryOpKind::Add, BinaryOpKind::Mul] {
+                let r = if matches!(op, BinaryOpKind::Add) {
+                    big
+                } else {
+                    two
+                };
+                assert_eq!(fold(big, op, r, OverflowMode::Checked), None);
+                assert_eq!(fold(big, op, r, OverflowMode::Saturating), Some(*max));
+                assert_eq!(fold(big, op, r, OverflowMode::Wrapping), Some(wrapped));
+            }
+            assert_eq!(
+                fold(zero, BinaryOpKind::Sub, one, OverflowMode::Checked),
+                None
+            );
+            assert_eq!(
+                fold(zero, BinaryOpKind::Sub, one, OverflowMode::Saturating),
+                Some(0)
+            );
+            assert_eq!(
+                fold(zero, BinaryOpKind::Sub, one, OverflowMode::Wrapping),
+                Some(*max)
+            );
+            // Bits shifted out of the width are dropped.
+            assert_eq!(
+                fold(big, BinaryOpKind::Lsh, one, OverflowMode::Checked),
+                Some(wrapped)
+            );
+            // Results within the bounds are folded in every mode.
+            for mode in [
+                OverflowMode::Wrapping,
+                OverflowMode::Saturating,
+                OverflowMode::Checked,
+            ] {
+                assert_eq!(fold(big, BinaryOpKind::Add, one, mode), Some(big_value + 1));
+            }
+        }
+
+        // `200 + 100` doesn't fit in a `u8`.
+        let two_hundred = Constant::new_uint(&mut context, 8, 200);
+        let hundred = Constant::new_uint(&mut context, 8, 100);
+        assert!(two_hundred
+            .try_fold_binary(&context, BinaryOpKind::Add, &hundred, OverflowMode::Checked)
+            .is_none());
+    }
+
+    #[test]
+    fn try_fold_binary_mismatched_types() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let byte = Constant::new_uint(&mut context, 8, 6);
+        let word = Constant::new_uint(&mut context, 64, 3);
+        let wide = Constant::new_uint256(&mut context, U256::from(6u64));
+        for op in [
+            BinaryOpKind::Add,
+            BinaryOpKind::Sub,
+            BinaryOpKind::Mul,
+            BinaryOpKind::Div,
+            BinaryOpKind::Mod,
+            BinaryOpKind::And,
+            BinaryOpKind::Or,
+            BinaryOpKind::Xor,
+        ] {
+            assert!(byte
+                .try_fold_binary(&context, op, &word, OverflowMode::Checked)
+                .is_none());
+            assert!(word
+                .try_fold_binary(&context, op, &byte, OverflowMode::Checked)
+                .is_none());
+        }
+
+        // Only the shift amount may be of another type.
+        let shifted =
+            byte.try_fold_binary(&context, BinaryOpKind::Rsh, &word, OverflowMode::Checked);
+        assert!(matches!(
+            shifted,
+            Some(Constant {
+                value: ConstantValue::Uint(0),
+                ..
+            })
+        ));
+        let shifted =
+            wide.try_fold_binary(&context, BinaryOpKind::Lsh, &word, OverflowMode::Checked);
+        assert!(matches!(
+            shifted,
+            Some(Constant {
+                value: ConstantValue::U256(n),
+                ..
+            }) if n == U256::from(48u64)
+        ));
+    }
+
+    #[test]
+    fn try_fold_binary_overflow_modes_u256() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let max = Constant::new_uint256(&mut context, U256::max_value());
+        let two = Constant::new_uint256(&mut context, U256::from(2));
+        let fold = |context: &Context, l: &Constant, op, r: &Constant, mode| match l
+            .try_fold_binary(context, op, r, mode)
+        {
+            Some(Constant {
+                value: ConstantValue::U256(n),
+                ..
+            }) => Some(n),
+            _ => None,
+        };
+
+        assert_eq!(
+            fold(
+                &context,
+                &max,
+                BinaryOpKind::Add,
+                &two,
+                OverflowMode::Wrapping
+            ),
+            Some(U256::from(1))
+        );
+        assert_eq!(
+            fold(
+                &context,
+                &two,
+                BinaryOpKind::Sub,
+                &max,
+                OverflowMode::Wrapping
+            ),
+            Some(U256::from(3))
+        );
+        assert_eq!(
+            fold(
+                &context,
+                &max,
+                BinaryOpKind::Mul,
+                &two,
+                OverflowMode::Wrapping
+            ),
+            Some(max_minus(1))
+        );
+        assert_eq!(
+            fold(
+                &context,
+                &max,
+                BinaryOpKind::Mul,
+                &two,
+                OverflowMode::Saturating
+            ),
+            Some(U256::max_value())
+        );
+        assert_eq!(
+            fold(
+                &context,
+                &max,
+                BinaryOpKind::Mul,
+                &two,
+                OverflowMode::Checked
+            ),
+            None
+        );
+        assert_eq!(
+            fold(
+                &context,
+                &max,
+                BinaryOpKind::Mod,
+                &two,
+                OverflowMode::Checked
+            ),
+            Some(U256::from(1))
+        );
+
+        // Division by zero is never folded.
+        let zero = Constant::new_uint256(&mut context, U256::from(0));
+        for mode in [
+            OverflowMode::Wrapping,
+            OverflowMode::Saturating,
+            OverflowMode::Checked,
+        ] {
+            assert_eq!(fold(&context, &two, BinaryOpKind::Div, &zero, mode), None);
+            assert_eq!(fold(&context, &two, BinaryOpKind::Mod, &zero, mode), None);
+        }
+
+        fn max_minus(n: u64) -> U256 {
+            U256::max_value().checked_sub(&U256::from(n)).unwrap()
+        }
+    }
+
+    #[test]
+    fn value_eq_ignores_integer_widths() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let u8_zero = Constant::new_uint(&mut context, 8, 0);
+        let u64_zero = Constant::new_uint(&mut context, 64, 0);
+        let u256_zero = Constant::new_uint(&mut context, 256, 0);
+        let u64_one = Constant::new_uint(&mut context, 64, 1);
+        assert!(!u8_zero.eq(&context, &u64_zero));
+        assert!(u8_zero.value_eq(&context, &u64_zero));
+        assert!(u64_zero.value_eq(&context, &u256_zero));
+        assert!(u256_zero.value_eq(&context, &u8_zero));
+        assert!(!u64_one.value_eq(&context, &u8_zero));
+
+        let bool_false = Constant::new_bool(&context, false);
+        assert!(!bool_false.value_eq(&context, &u64_zero));
+
+        let undef = Constant::get_undef(Type::get_uint64(&context));
+        assert!(!undef.value_eq(&context, &undef));
+    }
+
+    #[test]
+    fn debug_struct_constants_with_field_names() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let u64_ty = Type::get_uint64(&context);
+        let bool_ty = Type::get_bool(&context);
+        let fields = vec![
+            Constant::new_uint(&mut context, 64, 42),
+            Constant::new_bool(&context, true),
+        ];
+        let inner = Constant::new_struct(&mut context, vec![u64_ty, bool_ty], fields);
+        inner
+            .ty
+            .set_struct_field_names(&mut context, vec!["amount".into(), "flag".into()]);
+
+        let seven = Constant::new_uint(&mut context, 64, 7);
+        let outer = Constant::new_struct(
+            &mut context,
+            vec![inner.ty, u64_ty],
+            vec![inner.clone(), seven],
+        );
+        let debug = format!("{:?}", outer.with_context(&context));
+        assert!(debug.contains("value: ConstantValue::Struct { 0: Constant"));
+        assert!(debug.contains("amount: Constant"));
+        assert!(debug.contains("ConstantValue::Uint(42)"));
+        assert!(debug.contains("flag: Constant"));
+        assert!(debug.contains("1: Constant"));
+
+        // Names must match the number of fields.
+        outer
+            .ty
+            .set_struct_field_names(&mut context, vec!["a".into()]);
+        assert!(outer.ty.get_struct_field_names(&context).is_none());
+    }
+
+    #[test]
+    fn debug_same_shaped_struct_constants_with_positional_fields() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let u64_ty = Type::get_uint64(&context);
+        let fields = vec![
+            Constant::new_uint(&mut context, 64, 1),
+            Constant::new_uint(&mut context, 64, 2),
+        ];
+        let point = Constant::new_struct(&mut context, vec![u64_ty, u64_ty], fields);
+        point
+            .ty
+            .set_struct_field_names(&mut context, vec!["x".into(), "y".into()]);
+        let debug = format!("{:?}", point.with_context(&context));
+        assert!(debug.contains("x: Constant"));
+        assert!(debug.contains("y: Constant"));
+
+        // Recording the same names again keeps them.
+        point
+            .ty
+            .set_struct_field_names(&mut context, vec!["x".into(), "y".into()]);
+        assert_eq!(
+            point.ty.get_struct_field_names(&context),
+            Some(&["x".to_string(), "y".to_string()][..])
+        );
+
+        // A second struct and a tuple with the same field types share the Type, so none of
+        // their names can be trusted and the fields are printed positionally.
+        let range_ty = Type::new_struct(&mut context, vec![u64_ty, u64_ty]);
+        assert_eq!(range_ty, point.ty);
+        range_ty.set_struct_field_names(&mut context, vec!["start".into(), "end".into()]);
+        range_ty.set_struct_field_names(&mut context, vec!["0".into(), "1".into()]);
+        assert!(point.ty.get_struct_field_names(&context).is_none());
+
+        let debug = format!("{:?}", point.with_context(&context));
+        assert!(debug.contains("0: Constant"));
+        assert!(debug.contains("1: Constant"));
+        for name in ["x", "y", "start", "end"] {
+            assert!(!debug.contains(&format!("{name}: Constant")));
+        }
+    }
+
+    #[test]
+    fn bit_width_from_type() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        for width in [8, 16, 32, 64, 256] {
+            let n = Constant::new_uint(&mut context, width, 1);
+            assert_eq!(n.bit_width(&context), Some(u32::from(width)));
+        }
+        assert_eq!(
+            Constant::new_bool(&context, true).bit_width(&context),
+            Some(1)
+        );
+        assert_eq!(
+            Constant::get_undef(Type::get_uint8(&context)).bit_width(&context),
+            Some(8)
+        );
+
+        assert_eq!(Constant::new_unit(&context).bit_width(&context), None);
+        assert_eq!(
+            Constant::new_b256(&context, [0; 32]).bit_width(&context),
+            None
+        );
+        let string = Constant::new_string(&mut context, b"abc".to_vec());
+        assert_eq!(string.bit_width(&context), None);
+        let u64_ty = Type::get_uint64(&context);
+        let elems = vec![Constant::new_uint(&mut context, 64, 1)];
+        let array = Constant::new_array(&mut context, u64_ty, elems);
+        assert_eq!(array.bit_width(&context), None);
+    }
+
+    #[test]
+    fn new_b256_from_hex() {
+        let se = SourceEngine::default();
+        let context = Context::new(&se);
+
+        let hex = "0x000000000000000000000000000000000000000000000000000000000000ff01";
+        let mut bytes = [0; 32];
+        bytes[30] = 0xff;
+        bytes[31] = 0x01;
+        let expected = Constant::new_b256(&context, bytes);
+        let parsed = Constant::new_b256_from_hex(&context, hex).unwrap();
+        assert!(parsed.eq(&context, &expected));
+        let parsed = Constant::new_b256_from_hex(&context, &hex[2..].to_uppercase()).unwrap();
+        assert!(parsed.eq(&context, &expected));
+
+        let err = Constant::new_b256_from_hex(&context, "0x1234").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid hex literal '0x1234': expected 64 hex digits, found 4."
+        );
+        let err = Constant::new_b256_from_hex(&context, &"g0".repeat(32)).unwrap_err();
+        assert!(err.to_string().ends_with("'g' is not a hex digit."));
+    }
+
+    #[test]
+    fn enum_variant_index_and_payload() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let u64_ty = Type::get_uint64(&context);
+        let bool_ty = Type::get_bool(&context);
+        let unit_ty = Type::get_unit(&context);
+        let union_ty = Type::new_union(&mut context, vec![unit_ty, bool_ty]);
+        let fields = vec![
+            Constant::new_uint(&mut context, 64, 1),
+            Constant::new_bool(&context, true),
+        ];
+        let some_true = Constant::new_struct(&mut context, vec![u64_ty, union_ty], fields);
+        assert_eq!(some_true.enum_variant_index(&context), Some(1));
+        let payload = some_true.enum_payload(&context).unwrap();
+        assert!(payload.eq(&context, &Constant::new_bool(&context, true)));
+
+        // Enums without payloads only have a tag in their type.
+        let tag_only_ty = Type::new_struct(&mut context, vec![u64_ty]);
+        let unit_variant = Constant {
+            ty: tag_only_ty,
+            value: ConstantValue::Struct(
+                vec![
+                    Constant::new_uint(&mut context, 64, 2),
+                    Constant::new_unit(&context),
+                ]
+                .into(),
+            ),
+        };
+        assert_eq!(unit_variant.enum_variant_index(&context), Some(2));
+        assert!(matches!(
+            unit_variant.enum_payload(&context),
+            Some(Constant {
+                value: ConstantValue::Unit,
+                ..
+            })
+        ));
+
+        let fields = vec![
+            Constant::new_uint(&mut context, 64, 1),
+            Constant::new_bool(&context, true),
+        ];
+        let not_enum = Constant::new_struct(&mut context, vec![u64_ty, bool_ty], fields);
+        assert_eq!(not_enum.enum_variant_index(&context), None);
+        assert!(not_enum.enum_payload(&context).is_none());
+        assert_eq!(
+            Constant::new_uint(&mut context, 64, 1).enum_variant_index(&context),
+            None
+        );
+    }
+
+    #[test]
+    fn size_in_bytes_of_nested_struct() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        // struct Inner { a: u8, b: u64 }
+        let u8_ty = Type::get_uint8(&context);
+        let u64_ty = Type::get_uint64(&context);
+        let fields = vec![
+            Constant::new_uint(&mut context, 8, 1),
+            Constant::new_uint(&mut context, 64, 2),
+        ];
+        let inner = Constant::new_struct(&mut context, vec![u8_ty, u64_ty], fields);
+        assert_eq!(inner.size_in_bytes(&context), 16);
+
+        // struct Outer { a: bool, b: Inner, c: [u8; 3], d: str[3], e: u256 }
+        let elems = (1..=3)
+            .map(|n| Constant::new_uint(&mut context, 8, n))
+            .collect();
+        let array = Constant::new_array(&mut context, u8_ty, elems);
+        assert_eq!(array.size_in_bytes(&context), 3);
+        let string = Constant::new_string(&mut context, b"abc".to_vec());
+        assert_eq!(string.size_in_bytes(&context), 8);
+        let fields = vec![
+            Constant::new_bool(&context, true),
+            inner,
+            array,
+            string,
+            Constant::new_uint(&mut context, 256, 4),
+        ];
+        let field_tys = fields.iter().map(|field| field.ty).collect();
+        let outer = Constant::new_struct(&mut context, field_tys, fields);
+        assert_eq!(outer.size_in_bytes(&context), 8 + 16 + 8 + 8 + 32);
+    }
+
+    #[test]
+    fn new_byte_array() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let bytes = Constant::new_byte_array(&mut context, &[1, 2, 0xff]);
+        let u8_ty = Type::get_uint8(&context);
+        let array_ty = Type::new_array(&mut context, u8_ty, 3);
+        assert!(bytes.ty.eq(&context, &array_ty));
+        let elems = vec![
+            Constant::new_uint(&mut context, 8, 1),
+            Constant::new_uint(&mut context, 8, 2),
+            Constant::new_uint(&mut context, 8, 0xff),
+        ];
+        let expected = Constant::new_array(&mut context, u8_ty, elems);
+        assert!(bytes.eq(&context, &expected));
+        assert_eq!(bytes.size_in_bytes(&context), 3);
+    }
+
+    #[test]
+    fn equal_constants_have_equal_hashes() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+        let hash = |constant: &Constant| {
+            let mut hasher = FxHasher::default();
+            constant.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let u64_ty = Type::get_uint64(&context);
+        let bool_ty = Type::get_bool(&context);
+        let union_ty = Type::new_union(&mut context, vec![u64_ty, bool_ty]);
+        let one = Constant::new_uint(&mut context, 64, 1);
+        let two = Constant::new_uint(&mut context, 64, 2);
+        // Equal constants have the same hash even if their types are different but equivalent.
+        let union_one = Constant {
+            ty: union_ty,
+            value: ConstantValue::Uint(1),
+        };
+        assert!(one.eq(&context, &union_one));
+        assert_eq!(hash(&one), hash(&union_one));
+        assert_ne!(hash(&one), hash(&two));
+
+        let elems: Vec<_> = (0..8)
+            .map(|n| Constant::new_uint(&mut context, 64, n))
+            .collect();
+        let array = Constant::new_array(&mut context, u64_ty, elems.clone());
+        let same_array = Constant::new_array(&mut context, u64_ty, elems);
+        assert_eq!(hash(&array), hash(&same_array));
+        assert_ne!(hash(&array), hash(&one));
+    }
+
+    #[test]
+    fn aggregates_are_compared_by_cached_hash_first() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let u64_ty = Type::get_uint64(&context);
+        let nested = |context: &mut Context, leaf| {
+            let elems = vec![
+                Constant::new_uint(context, 64, 1),
+                Constant::new_uint(context, 64, leaf),
+            ];
+            let inner = Constant::new_struct(context, vec![u64_ty, u64_ty], elems);
+            let inner_ty = inner.ty;
+            Constant::new_array(context, inner_ty, vec![inner.clone(), inner])
+        };
+        let a = nested(&mut context, 2);
+        let same_a = nested(&mut context, 2);
+        let b = nested(&mut context, 3);
+
+        let (ConstantValue::Array(a_elems), ConstantValue::Array(b_elems)) = (&a.value, &b.value)
+        else {
+            panic!("expected array constants");
+        };
+        // The cached hash covers the values nested in the elements, so a difference deep down
+        // is enough to tell the arrays apart without comparing them element by element.
+        assert_ne!(a_elems.hash, b_elems.hash);
+        assert!(!a.eq(&context, &b));
+        assert!(a.eq(&context, &same_a));
+        assert!(a.structural_eq(&context, &same_a));
+        assert!(!a.structural_eq(&context, &b));
+    }
+
+    #[test]
+    fn try_eval_binop_and_unop() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let two = Constant::new_uint(&mut context, 64, 2);
+        let three = Constant::new_uint(&mut context, 64, 3);
+        let max = Constant::new_uint(&mut context, 64, u64::MAX);
+        let five = Constant::try_eval_binop(&context, BinaryOpKind::Add.into(), &two, &three);
+        assert!(matches!(
+            five,
+            Some(Constant {
+                value: ConstantValue::Uint(5),
+                ..
+            })
+        ));
+        // Overflowing arithmetic isn't folded in the default, checked, overflow mode.
+        assert!(
+            Constant::try_eval_binop(&context, BinaryOpKind::Add.into(), &max, &three).is_none()
+        );
+
+        let lt =
+            Constant::try_eval_binop(&context, Predicate::LessThan.into(), &two, &three).unwrap();
+        assert!(lt.eq(&context, &Constant::new_bool(&context, true)));
+        let eq = Constant::try_eval_binop(&context, Predicate::Equal.into(), &two, &three).unwrap();
+        assert!(eq.eq(&context, &Constant::new_bool(&context, false)));
+        // Booleans can only be compared for equality.
+        let t = Constant::new_bool(&context, true);
+        assert!(
+            Constant::try_eval_binop(&context, Predicate::GreaterThan.into(), &t, &t).is_none()
+        );
+
+        // `not` only inverts the bits within the width of the type.
+        let byte = Constant::new_uint(&mut context, 8, 0x0f);
+        let not = Constant::try_eval_unop(&context, UnaryOpKind::Not, &byte);
+        assert!(matches!(
+            not,
+            Some(Constant {
+                value: ConstantValue::Uint(0xf0),
+                ..
+            })
+        ));
+        assert!(Constant::try_eval_unop(&context, UnaryOpKind::Not, &t).is_none());
+    }
+
+    #[test]
+    fn try_fold_cmp() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let fold = |context: &Context, op, lhs: &Constant, rhs: &Constant| {
+            Constant::try_fold_cmp(context, op, lhs, rhs).map(|result| match result.value {
+                ConstantValue::Bool(b) => b,
+                _ => panic!("Comparisons must result in a bool."),
+            })
+        };
+
+        let three = Constant::new_uint(&mut context, 64, 3);
+        let five = Constant::new_uint(&mut context, 64, 5);
+        assert_eq!(
+            fold(&context, ComparisonOp::LessThan, &three, &five),
+            Some(true)
+        );
+        assert_eq!(
+            fold(&context, ComparisonOp::LessThanOrEqual, &five, &five),
+            Some(true)
+        );
+        assert_eq!(
+            fold(&context, ComparisonOp::GreaterThan, &three, &five),
+            Some(false)
+        );
+        assert_eq!(
+            fold(&context, ComparisonOp::GreaterThanOrEqual, &five, &three),
+            Some(true)
+        );
+        assert_eq!(
+            fold(&context, ComparisonOp::NotEqual, &three, &five),
+            Some(true)
+        );
+
+        // u256s are compared as unsigned numbers, up to their maximum.
+        let max = Constant::new_uint256(&mut context, U256::max_value());
+        let below_max = Constant::new_uint256(
+            &mut context,
+            U256::max_value().checked_sub(&U256::from(1)).unwrap(),
+        );
+        let zero = Constant::new_uint256(&mut context, U256::from(0));
+        assert_eq!(
+            fold(&context, ComparisonOp::GreaterThan, &max, &zero),
+            Some(true)
+        );
+        assert_eq!(
+            fold(&context, ComparisonOp::LessThan, &below_max, &max),
+            Some(true)
+        );
+        assert_eq!(
+            fold(&context, ComparisonOp::GreaterThanOrEqual, &below_max, &max),
+            Some(false)
+        );
+        assert_eq!(fold(&context, ComparisonOp::Equal, &max, &max), Some(true));
+
+        // Bools and b256s can be compared for equality.
+        let t = Constant::new_bool(&context, true);
+        let f = Constant::new_bool(&context, false);
+        assert_eq!(fold(&context, ComparisonOp::NotEqual, &t, &f), Some(true));
+        assert_eq!(fold(&context, ComparisonOp::LessThan, &t, &f), None);
+        let b256 = Constant::new_b256(&context, [1; 32]);
+        assert_eq!(
+            fold(&context, ComparisonOp::Equal, &b256, &b256),
+            Some(true)
+        );
+
+        // Operands of different types can't be compared.
+        let byte = Constant::new_uint(&mut context, 8, 3);
+        assert_eq!(fold(&context, ComparisonOp::Equal, &byte, &three), None);
+        assert_eq!(fold(&context, ComparisonOp::Equal, &zero, &three), None);
+        assert_eq!(fold(&context, ComparisonOp::Equal, &b256, &max), None);
+    }
+
+    #[test]
+    fn new_u256_from_str() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        let n = Constant::new_u256_from_str(&mut context, max).unwrap();
+        assert_eq!(n.as_u256(), Some(U256::max_value()));
+        let u256_ty = Type::new_uint(&mut context, 256);
+        assert!(n.ty.eq(&context, &u256_ty));
+        let hex_max = format!("0x{}", "f".repeat(64));
+        let n = Constant::new_u256_from_str(&mut context, &hex_max).unwrap();
+        assert_eq!(n.as_u256(), Some(U256::max_value()));
+        let n = Constant::new_u256_from_str(&mut context, "0x2a").unwrap();
+        assert_eq!(n.as_u256(), Some(U256::from(42)));
+
+        // One past the largest value overflows.
+        let past_max =
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+        assert!(matches!(
+            Constant::new_u256_from_str(&mut context, past_max),
+            Err(IrError::InvalidIntegerLiteral(..))
+        ));
+        let hex_past_max = format!("0x1{}", "0".repeat(64));
+        assert!(Constant::new_u256_from_str(&mut context, &hex_past_max).is_err());
+
+        assert!(Constant::new_u256_from_str(&mut context, "").is_err());
+        assert!(Constant::new_u256_from_str(&mut context, "0x").is_err());
+        assert!(Constant::new_u256_from_str(&mut context, "12a").is_err());
+        assert!(Constant::new_u256_from_str(&mut context, "-1").is_err());
+    }
+
+    #[test]
+    fn new_string_from_str() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let s = Constant::new_string_from_str(&mut context, "fuel");
+        let expected = Constant::new_string(&mut context, b"fuel".to_vec());
+        assert!(s.eq(&context, &expected));
+        let string_ty = Type::new_string_array(&mut context, 4);
+        assert!(s.ty.eq(&context, &string_ty));
+
+        let v = Constant::get_string_from_str(&mut context, "");
+        let empty = v.get_constant(&context).unwrap().clone();
+        assert_eq!(empty.string_lossy().as_deref(), Some(""));
+    }
+
+    #[test]
+    fn constants_of_type() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let address = [1; 32];
+        Constant::get_b256(&mut context, address);
+        Constant::get_uint(&mut context, 64, 1);
+        Constant::get_b256(&mut context, [2; 32]);
+        Constant::get_b256(&mut context, address);
+
+        let b256_ty = Type::get_b256(&context);
+        let b256s = context.constants_of_type(b256_ty);
+        assert_eq!(b256s.len(), 2);
+        assert!(b256s.iter().all(|c| c.ty.eq(&context, &b256_ty)));
+        assert!(b256s
+            .iter()
+            .any(|c| c.as_b256() == Some(B256::from_be_bytes(&address))));
+
+        let u64_ty = Type::get_uint64(&context);
+        assert_eq!(context.constants_of_type(u64_ty).len(), 1);
+        let bool_ty = Type::get_bool(&context);
+        assert!(context.constants_of_type(bool_ty).is_empty());
+    }
+
+    #[test]
+    fn array_and_struct_with_same_elements_are_not_equal() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let u64_ty = Type::get_uint64(&context);
+        let elems = vec![
+            Constant::new_uint(&mut context, 64, 1),
+            Constant::new_uint(&mut context, 64, 2),
+        ];
+        let array = Constant::new_array(&mut context, u64_ty, elems.clone());
+        let strukt = Constant::new_struct(&mut context, vec![u64_ty, u64_ty], elems.clone());
+
+        assert!(array.eq(&context, &array.clone()));
+        assert!(strukt.eq(&context, &strukt.clone()));
+        assert!(!array.eq(&context, &strukt));
+        assert!(!strukt.eq(&context, &array));
+        assert!(!array.value_eq(&context, &strukt));
+
+        // Even if the types were to compare equal, the values differ in kind.
+        let struct_value_with_array_type = Constant {
+            ty: array.ty,
+            value: ConstantValue::Struct(elems.into()),
+        };
+        assert!(!array.eq(&context, &struct_value_with_array_type));
+    }
+
+    #[test]
+    fn large_aggregates_are_compared_by_bytes() {
+        let source_engine = SourceEngine::default();
+        let mut context = Context::new(&source_engine);
+        let u64_ty = Type::get_uint64(&context);
+        let array = |context: &mut Context, last: Constant| {
+            let mut elems = (0..31)
+                .map(|n| Constant::new_uint(context, 64, n))
+                .collect::<Vec<_>>();
+            elems.push(last);
+            Constant::new_array(context, u64_ty, elems)
+        };
+
+        let last = Constant::new_uint(&mut context, 64, 31);
+        let a = array(&mut context, last.clone());
+        let b = array(&mut context, last);
+        assert!(a.eq(&context, &b));
+        assert!(a.structural_eq(&context, &b));
+
+        let other = Constant::new_uint(&mut context, 64, 32);
+        let c = array(&mut context, other);
+        assert!(!a.eq(&context, &c));
+
+        // Undef is never equal, not even to itself, so it can't take the fast path.
+        let undef = array(&mut context, Constant::get_undef(u64_ty));
+        assert!(undef.to_bytes().is_none());
+        assert!(!undef.eq(&context, &undef));
+        assert!(!a.eq(&context, &undef));
+    }
+
+    #[test]
+    fn to_ir_text_round_trips() {
+        let source_engine = SourceEngine::default();
+        let mut context = Context::new(&source_engine);
+        let u64_ty = Type::get_uint64(&context);
+        let bool_ty = Type::get_bool(&context);
+        let one = Constant::new_uint(&mut context, 64, 1);
+        let t = Constant::new_bool(&context, true);
+        let strukt =
+            Constant::new_struct(&mut context, vec![u64_ty, bool_ty], vec![one.clone(), t]);
+        let elems = (1..=3)
+            .map(|n| Constant::new_uint(&mut context, 64, n))
+            .collect();
+        let array = Constant::new_array(&mut context, u64_ty, elems);
+        let fixed = Constant::new_fixed(&mut context, U256::from(125), 2);
+
+        assert_eq!(one.to_ir_text(&context), "u64 1");
+        assert_eq!(
+            strukt.to_ir_text(&context),
+            "{ u64, bool } { u64 1, bool true }"
+        );
+        assert_eq!(array.to_ir_text(&context), "[u64; 3] [u64 1, u64 2, u64 3]");
+
+        assert_eq!(
+            fixed.to_ir_text(&context),
+            format!("u256 fixed(0x{:064x}, 2)", 125)
+        );
+
+        for constant in [one, strukt, array, fixed] {
+            let text = constant.to_ir_text(&context);
+            let ty = constant.ty.as_string(&context);
+            let ir = format!(
+                "script {{\n    entry fn main() -> {ty} {{\n        entry():\n        v0 = const {text}\n        ret {ty} v0\n    }}\n}}\n"
+            );
+            let parsed = crate::parser::parse(&ir, &source_engine).unwrap();
+            let parsed_text = parsed
+                .values
+                .iter()
+                .find_map(|(_, value)| match &value.value {
+                    crate::value::ValueDatum::Constant(constant) => {
+                        Some(constant.to_ir_text(&parsed))
+                    }
+                    _ => None,
+                })
+                .unwrap();
+            assert_eq!(parsed_text, text);
+        }
+    }
+
+    #[test]
+    fn into_cached_value_reuses_equal_constants() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let one = Constant::new_uint(&mut context, 64, 1).into_cached_value(&mut context);
+        let same_one = Constant::new_uint(&mut context, 64, 1).into_cached_value(&mut context);
+        assert_eq!(one, same_one);
+
+        let two = Constant::new_uint(&mut context, 64, 2).into_cached_value(&mut context);
+        assert_ne!(one, two);
+        let byte_one = Constant::new_uint(&mut context, 8, 1).into_cached_value(&mut context);
+        assert_ne!(one, byte_one);
+
+        // Values created without the cache are never reused.
+        let new_one = Constant::get_uint(&mut context, 64, 1);
+        assert_ne!(one, new_one);
+        assert_eq!(
+            Constant::new_uint(&mut context, 64, 1).into_cached_value(&mut context),
+            one
+        );
+    }
+
+    #[test]
+    fn truncate_and_zero_extend_integers() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+
+        let word = Constant::new_uint(&mut context, 64, 0x1234);
+        let byte = word.truncate(&mut context, 8).unwrap();
+        assert_eq!(byte.ty.get_uint_width(&context), Some(8));
+        assert_eq!(byte.as_uint(), Some(0x34));
+        assert!(word.truncate(&mut context, 256).is_none());
+        assert!(word.truncate(&mut context, 0).is_none());
+
+        let byte = Constant::new_uint(&mut context, 8, 0xff);
+        let wide = byte.zero_extend(&mut context, 256).unwrap();
+        assert_eq!(wide.ty.get_uint_width(&context), Some(256));
+        assert_eq!(wide.as_u256(), Some(U256::from(0xff)));
+        assert!(byte.zero_extend(&mut context, 4).is_none());
+
+        let wide = Constant::new_uint256(&mut context, U256::max_value());
+        let word = wide.truncate(&mut context, 64).unwrap();
+        assert_eq!(word.as_uint(), Some(u64::MAX));
+        assert!(Constant::new_bool(&context, true)
+            .zero_extend(&mut context, 64)
+            .is_none());
+    }
+
+    #[test]
+    fn empty_arrays() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+        let u64_ty = Type::get_uint64(&context);
+        let u8_ty = Type::get_uint8(&context);
+
+        let empty = Constant::new_empty_array(&mut context, u64_ty);
+        assert_eq!(empty.ty.get_array_len(&context), Some(0));
+        assert_eq!(empty.ty.get_array_elem_type(&context), Some(u64_ty));
+        let from_elems = Constant::new_array(&mut context, u64_ty, vec![]);
+        assert!(empty.eq(&context, &from_elems));
+        let empty_u8 = Constant::new_empty_array(&mut context, u8_ty);
+        assert!(!empty.eq(&context, &empty_u8));
+        let one = Constant::new_uint(&mut context, 64, 1);
+        let singleton = Constant::new_array(&mut context, u64_ty, vec![one]);
+        assert!(!empty.eq(&context, &singleton));
+
+        // An empty array nested in a struct keeps its element type.
+        let new_struct = |context: &mut Context, elm_ty| {
+            let empty = Constant::new_empty_array(context, elm_ty);
+            let one = Constant::new_uint(context, 64, 1);
+            Constant::new_struct(context, vec![empty.ty, one.ty], vec![empty, one])
+        };
+        let s = new_struct(&mut context, u64_ty);
+        let same = new_struct(&mut context, u64_ty);
+        assert!(s.eq(&context, &same));
+        let other = new_struct(&mut context, u8_ty);
+        assert!(!s.eq(&context, &other));
+        let fields = s.struct_fields(&context).unwrap();
+        assert_eq!(fields[0].0.get_array_elem_type(&context), Some(u64_ty));
+    }
+
+    #[test]
+    fn fixed_point_constants() {
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+        let fixed = |context: &mut Context, value: u64, scale| {
+            Constant::new_fixed(context, U256::from(value), scale)
+        };
+
+        // 1.25 and 0.50 with two decimals.
+        let a = fixed(&mut context, 125, 2);
+        let b = fixed(&mut context, 50, 2);
+        let a_tenths = fixed(&mut context, 12, 1);
+        let a_again = fixed(&mut context, 125, 2);
+        let zero = fixed(&mut context, 0, 2);
+
+        assert!(a.eq(&context, &a_again));
+        assert!(!a.eq(&context, &b));
+        // The same digits at another scale are a different number.
+        assert!(!fixed(&mut context, 125, 1).eq(&context, &a));
+        assert_eq!(
+            format!("{:?}", a.value),
+            "Fixed { value: U256(125), scale: 2 }"
+        );
+
+        let fold = |op, lhs: &Constant, rhs| {
+            lhs.try_fold_binary(&context, op, rhs, OverflowMode::Checked)
+                .map(|result| result.value)
+        };
+        let value = |value: u64, scale| ConstantValue::Fixed {
+            value: U256::from(value),
+            scale,
+        };
+        let folds_to = |op, lhs, rhs, expected: ConstantValue| {
+            let result = fold(op, lhs, rhs).unwrap();
+            assert_eq!(format!("{result:?}"), format!("{expected:?}"));
+        };
+        folds_to(BinaryOpKind::Add, &a, &b, value(175, 2));
+        folds_to(BinaryOpKind::Sub, &a, &b, value(75, 2));
+        folds_to(BinaryOpKind::Mul, &a, &b, value(62, 2));
+        folds_to(BinaryOpKind::Div, &a, &b, value(250, 2));
+
+        assert!(fold(BinaryOpKind::Add, &a, &a_tenths).is_none());
+        assert!(fold(BinaryOpKind::Sub, &b, &a).is_none());
+        assert!(fold(BinaryOpKind::Div, &a, &zero).is_none());
+        assert!(fold(BinaryOpKind::Mod, &a, &b).is_none());
+    }
+
+    #[test]
+    fn as_string_truncated_elides_long_arrays() {
+        let source_engine = SourceEngine::default();
+        let mut context = Context::new(&source_engine);
+        let u64_ty = Type::get_uint64(&context);
+        let elems = (0..100)
+            .map(|n| Constant::new_uint(&mut context, 64, n))
+            .collect();
+        let long = Constant::new_array(&mut context, u64_ty, elems);
+        let elems = (0..2)
+            .map(|n| Constant::new_uint(&mut context, 64, n))
+            .collect();
+        let short = Constant::new_array(&mut context, u64_ty, elems);
+        let strukt = Constant::new_struct(
+            &mut context,
+            vec![long.ty, short.ty],
+            vec![long.clone(), short.clone()],
+        );
+
+        assert_eq!(
+            long.as_string_truncated(&context, 3),
+            "[u64; 100] [u64 0, u64 1, ... 97 more ..., u64 99]"
+        );
+        assert_eq!(
+            long.as_string_truncated(&context, 4),
+            "[u64; 100] [u64 0, u64 1, ... 96 more ..., u64 98, u64 99]"
+        );
+        assert_eq!(
+            long.as_string_truncated(&context, 0),
+            "[u64; 100] [... 100 more ...]"
+        );
+        assert_eq!(
+            short.as_string_truncated(&context, 2),
+            short.to_ir_text(&context)
+        );
+        assert_eq!(
+            strukt.as_string_truncated(&context, 1),
+            "{ [u64; 100], [u64; 2] } { [u64; 100] [u64 0, ... 99 more ...], [u64; 2] [u64 0, ... 1 more ...] }"
+        );
+        assert_eq!(long.to_ir_text(&context).matches("u64 ").count(), 100);
+    }
+
+    #[test]
+    fn join_merges_undef_and_equal_constants() {
+        let source_engine = SourceEngine::default();
+        let mut context = Context::new(&source_engine);
+        let u64_ty = Type::get_uint64(&context);
+        let undef = Constant::get_undef(u64_ty);
+        let one = Constant::new_uint(&mut context, 64, 1);
+        let other_one = Constant::new_uint(&mut context, 64, 1);
+        let two = Constant::new_uint(&mut context, 64, 2);
+        let t = Constant::new_bool(&context, true);
+        let bool_undef = Constant::get_undef(Type::get_bool(&context));
+
+        // The defined side wins over undef, on either side.
+        let joined = Constant::join(&context, &undef, &one).unwrap();
+        assert!(joined.eq(&context, &one));
+        let joined = Constant::join(&context, &one, &undef).unwrap();
+        assert!(joined.eq(&context, &one));
+        let joined = Constant::join(&context, &undef, &undef).unwrap();
+        assert!(matches!(joined.value, ConstantValue::Undef));
+
+        // Equal constants join to their common value.
+        let joined = Constant::join(&context, &one, &other_one).unwrap();
+        assert!(joined.eq(&context, &one));
+
+        // Conflicting values or types don't join.
+        assert!(Constant::join(&context, &one, &two).is_none());
+        assert!(Constant::join(&context, &one, &t).is_none());
+        assert!(Constant::join(&context, &bool_undef, &one).is_none());
+    }
 }
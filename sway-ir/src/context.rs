@@ -7,13 +7,14 @@
 //! It is passed around as a mutable reference to many of the Sway-IR APIs.
 
 use generational_arena::Arena;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHasher};
+use std::hash::{Hash, Hasher};
 use sway_types::SourceEngine;
 
 use crate::{
     block::BlockContent, function::FunctionContent, local_var::LocalVarContent,
     metadata::Metadatum, module::Kind, module::ModuleContent, module::ModuleIterator,
-    value::ValueContent, Type, TypeContent,
+    value::ValueContent, value::ValueDatum, Constant, OverflowMode, Type, TypeContent, Value,
 };
 
 /// The main IR context handle.
@@ -30,9 +31,14 @@ pub struct Context<'eng> {
     pub(crate) local_vars: Arena<LocalVarContent>,
     pub(crate) types: Arena<TypeContent>,
     pub(crate) type_map: FxHashMap<TypeContent, Type>,
+    pub(crate) struct_field_names: FxHashMap<Type, Option<Vec<String>>>,
     pub(crate) metadata: Arena<Metadatum>,
+    /// The values created by [`Constant::into_cached_value`], keyed by the hash of their constant.
+    pub(crate) constant_values: FxHashMap<u64, Vec<Value>>,
 
     pub program_kind: Kind,
+    /// How constant folding treats arithmetic overflow, which depends on the target.
+    pub overflow_mode: OverflowMode,
 
     next_unique_sym_tag: u64,
 }
@@ -48,9 +54,12 @@ impl<'eng> Context<'eng> {
             local_vars: Default::default(),
             types: Default::default(),
             type_map: Default::default(),
+            struct_field_names: Default::default(),
             metadata: Default::default(),
+            constant_values: Default::default(),
             next_unique_sym_tag: Default::default(),
             program_kind: Kind::Contract,
+            overflow_mode: OverflowMode::default(),
         };
         Type::create_basic_types(&mut def);
         def
@@ -78,6 +87,33 @@ impl<'eng> Context<'eng> {
         self.next_unique_sym_tag += 1;
         sym
     }
+
+    /// Returns every distinct constant value of type `ty` in this context, e.g. all the `b256`
+    /// literals.
+    ///
+    /// Constants are told apart with [`Constant::eq`]. Configurables aren't included, since their
+    /// values aren't fixed.
+    pub fn constants_of_type(&self, ty: Type) -> Vec<&Constant> {
+        let mut seen: FxHashMap<u64, Vec<&Constant>> = FxHashMap::default();
+        self.values
+            .iter()
+            .filter_map(|(_, value)| match &value.value {
+                ValueDatum::Constant(constant) if constant.ty.eq(self, &ty) => Some(constant),
+                _ => None,
+            })
+            .filter(|constant| {
+                let mut hasher = FxHasher::default();
+                constant.hash(&mut hasher);
+                let same_hash = seen.entry(hasher.finish()).or_default();
+                if same_hash.iter().any(|other| other.eq(self, constant)) {
+                    false
+                } else {
+                    same_hash.push(constant);
+                    true
+                }
+            })
+            .collect()
+    }
 }
 
 use std::fmt::{Display, Error, Formatter};
@@ -6,6 +6,8 @@
 #[derive(Debug)]
 pub enum IrError {
     FunctionLocalClobbered(String, String),
+    InvalidHexLiteral(String, String),
+    InvalidIntegerLiteral(String, String),
     InvalidMetadatum(String),
     InvalidPhi,
     MisplacedTerminator(String),
@@ -79,6 +81,12 @@ impl fmt::Display for IrError {
                 f,
                 "Local storage for function {fn_str} already has an entry for variable {var_str}."
             ),
+            IrError::InvalidHexLiteral(literal, why_str) => {
+                write!(f, "Invalid hex literal '{literal}': {why_str}.")
+            }
+            IrError::InvalidIntegerLiteral(literal, why_str) => {
+                write!(f, "Invalid integer literal '{literal}': {why_str}.")
+            }
             IrError::InvalidMetadatum(why_str) => {
                 write!(f, "Unable to convert from invalid metadatum: {why_str}.")
             }
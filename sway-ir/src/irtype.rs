@@ -412,6 +412,37 @@ impl Type {
         }
     }
 
+    /// Record the names of the fields of a struct Type, used when debug printing its constants.
+    ///
+    /// Struct types are uniqued by their field types, so structs and tuples with the same field
+    /// types share a Type. If different names are recorded for the same Type they can't be told
+    /// apart, and its fields are printed by their positional indices instead. Names are ignored
+    /// if the Type isn't a struct with that many fields.
+    pub fn set_struct_field_names(&self, context: &mut Context, names: Vec<String>) {
+        match self.get_content(context) {
+            TypeContent::Struct(fields) if fields.len() == names.len() => {
+                context
+                    .struct_field_names
+                    .entry(*self)
+                    .and_modify(|recorded| {
+                        if recorded.as_ref() != Some(&names) {
+                            *recorded = None;
+                        }
+                    })
+                    .or_insert_with(|| Some(names));
+            }
+            _ => {}
+        }
+    }
+
+    /// Get the field names recorded for a struct Type, if any and they are unambiguous.
+    pub fn get_struct_field_names<'a>(&self, context: &'a Context) -> Option<&'a [String]> {
+        context
+            .struct_field_names
+            .get(self)
+            .and_then(|names| names.as_deref())
+    }
+
     pub fn size_in_bytes(&self, context: &Context) -> u64 {
         match self.get_content(context) {
             TypeContent::Uint(8) | TypeContent::Bool | TypeContent::Unit => 1,
@@ -130,53 +130,37 @@ fn combine_cmp(context: &mut Context, function: &Function) -> bool {
                 }) if val1.is_constant(context) && val2.is_constant(context) => {
                     let val1 = val1.get_constant(context).unwrap();
                     let val2 = val2.get_constant(context).unwrap();
-
-                    use ConstantValue::*;
-                    match pred {
-                        Predicate::Equal => Some((inst_val, block, val1.eq(context, val2))),
-                        Predicate::GreaterThan => {
-                            let r = match (&val1.value, &val2.value) {
-                                (Uint(val1), Uint(val2)) => val1 > val2,
-                                (U256(val1), U256(val2)) => val1 > val2,
-                                (B256(val1), B256(val2)) => val1 > val2,
-                                _ => {
-                                    unreachable!(
-                                        "Type checker allowed non integer value for GreaterThan"
-                                    )
-                                }
-                            };
-                            Some((inst_val, block, r))
-                        }
-                        Predicate::LessThan => {
-                            let r = match (&val1.value, &val2.value) {
-                                (Uint(val1), Uint(val2)) => val1 < val2,
-                                (U256(val1), U256(val2)) => val1 < val2,
-                                (B256(val1), B256(val2)) => val1 < val2,
-                                _ => {
-                                    unreachable!(
-                                        "Type checker allowed non integer value for GreaterThan"
-                                    )
-                                }
-                            };
-                            Some((inst_val, block, r))
-                        }
-                    }
+                    Constant::try_eval_binop(context, (*pred).into(), val1, val2)
+                        .or_else(|| fold_aggregate_eq(context, pred, val1, val2))
+                        .map(|new_value| (inst_val, block, new_value))
                 }
                 _ => None,
             },
         );
 
-    candidate.map_or(false, |(inst_val, block, cn_replace)| {
+    candidate.map_or(false, |(inst_val, block, new_value)| {
         // Replace this `cmp` instruction with a constant.
-        inst_val.replace(
-            context,
-            ValueDatum::Constant(Constant::new_bool(context, cn_replace)),
-        );
+        inst_val.replace(context, ValueDatum::Constant(new_value));
         block.remove_instruction(context, inst_val);
         true
     })
 }
 
+/// Folds the equality of constants which aren't scalars, like structs, arrays and strings, which
+/// [`Constant::try_eval_binop`] doesn't compare.
+fn fold_aggregate_eq(
+    context: &Context,
+    pred: &Predicate,
+    val1: &Constant,
+    val2: &Constant,
+) -> Option<Constant> {
+    use ConstantValue::*;
+    let is_aggregate =
+        |val: &Constant| matches!(val.value, Unit | String(_) | Array(_) | Struct(_));
+    (matches!(pred, Predicate::Equal) && is_aggregate(val1) && is_aggregate(val2))
+        .then(|| Constant::new_bool(context, val1.eq(context, val2)))
+}
+
 fn combine_binary_op(context: &mut Context, function: &Function) -> bool {
     let candidate = function
         .instruction_iter(context)
@@ -188,45 +172,8 @@ fn combine_binary_op(context: &mut Context, function: &Function) -> bool {
                 }) if arg1.is_constant(context) && arg2.is_constant(context) => {
                     let val1 = arg1.get_constant(context).unwrap();
                     let val2 = arg2.get_constant(context).unwrap();
-                    use crate::BinaryOpKind::*;
-                    use ConstantValue::*;
-                    let v = match (op, &val1.value, &val2.value) {
-                        (Add, Uint(l), Uint(r)) => l.checked_add(*r).map(Uint),
-                        (Add, U256(l), U256(r)) => l.checked_add(r).map(U256),
-
-                        (Sub, Uint(l), Uint(r)) => l.checked_sub(*r).map(Uint),
-                        (Sub, U256(l), U256(r)) => l.checked_sub(r).map(U256),
-
-                        (Mul, Uint(l), Uint(r)) => l.checked_mul(*r).map(Uint),
-                        (Mul, U256(l), U256(r)) => l.checked_mul(r).map(U256),
-
-                        (Div, Uint(l), Uint(r)) => l.checked_div(*r).map(Uint),
-                        (Div, U256(l), U256(r)) => l.checked_div(r).map(U256),
-
-                        (And, Uint(l), Uint(r)) => Some(Uint(l & r)),
-                        (And, U256(l), U256(r)) => Some(U256(l & r)),
-
-                        (Or, Uint(l), Uint(r)) => Some(Uint(l | r)),
-                        (Or, U256(l), U256(r)) => Some(U256(l | r)),
-
-                        (Xor, Uint(l), Uint(r)) => Some(Uint(l ^ r)),
-                        (Xor, U256(l), U256(r)) => Some(U256(l ^ r)),
-
-                        (Mod, Uint(l), Uint(r)) => Some(Uint(l % r)),
-                        (Mod, U256(l), U256(r)) => Some(U256(l % r)),
-
-                        (Rsh, Uint(l), Uint(r)) => u32::try_from(*r)
-                            .ok()
-                            .and_then(|r| l.checked_shr(r).map(Uint)),
-                        (Rsh, U256(l), Uint(r)) => Some(U256(l.shr(r))),
-
-                        (Lsh, Uint(l), Uint(r)) => u32::try_from(*r)
-                            .ok()
-                            .and_then(|r| l.checked_shl(r).map(Uint)),
-                        (Lsh, U256(l), Uint(r)) => l.checked_shl(r).map(U256),
-                        _ => None,
-                    };
-                    v.map(|value| (inst_val, block, Constant { ty: val1.ty, value }))
+                    Constant::try_eval_binop(context, (*op).into(), val1, val2)
+                        .map(|new_value| (inst_val, block, new_value))
                 }
                 _ => None,
             },
@@ -250,23 +197,8 @@ fn combine_unary_op(context: &mut Context, function: &Function) -> bool {
                     ..
                 }) if arg.is_constant(context) => {
                     let val = arg.get_constant(context).unwrap();
-                    use crate::UnaryOpKind::*;
-                    use ConstantValue::*;
-                    let v = match (op, &val.value) {
-                        (Not, Uint(v)) => val.ty.get_uint_width(context).and_then(|width| {
-                            let max = match width {
-                                8 => u8::MAX as u64,
-                                16 => u16::MAX as u64,
-                                32 => u32::MAX as u64,
-                                64 => u64::MAX,
-                                _ => return None,
-                            };
-                            Some(Uint((!v) & max))
-                        }),
-                        (Not, U256(v)) => Some(U256(!v)),
-                        _ => None,
-                    };
-                    v.map(|value| (inst_val, block, Constant { ty: val.ty, value }))
+                    Constant::try_eval_unop(context, *op, val)
+                        .map(|new_value| (inst_val, block, new_value))
                 }
                 _ => None,
             },
@@ -392,4 +324,64 @@ mod tests {
             Some(["const u64 6"]),
         );
     }
+
+    /// Comparisons of aggregates don't pass verification, so the `cmp` is built directly rather
+    /// than parsed.
+    #[test]
+    fn aggregate_eq_is_optimized() {
+        use crate::{Constant, Context, Function, Kind, Module, Predicate, Type, Value};
+        use sway_types::SourceEngine;
+
+        let se = SourceEngine::default();
+        let mut context = Context::new(&se);
+        let module = Module::new(&mut context, Kind::Script);
+        let bool_ty = Type::get_bool(&context);
+        let function = Function::new(
+            &mut context,
+            module,
+            "main".into(),
+            vec![],
+            bool_ty,
+            None,
+            false,
+            true,
+            None,
+        );
+        let block = function.get_entry_block(&context);
+
+        let u64_ty = Type::get_uint64(&context);
+        let cmp_eq = |context: &mut Context, lhs: Constant, rhs: Constant| {
+            let lhs = Value::new_constant(context, lhs);
+            let rhs = Value::new_constant(context, rhs);
+            block.ins(context).cmp(Predicate::Equal, lhs, rhs)
+        };
+        let structs = [7, 7].map(|n| {
+            let fields = vec![
+                Constant::new_uint(&mut context, 64, n),
+                Constant::new_bool(&context, true),
+            ];
+            Constant::new_struct(&mut context, vec![u64_ty, bool_ty], fields)
+        });
+        let [lhs, rhs] = structs;
+        let struct_eq = cmp_eq(&mut context, lhs, rhs);
+        let arrays = [1, 2].map(|n| {
+            let elems = vec![Constant::new_uint(&mut context, 64, n)];
+            Constant::new_array(&mut context, u64_ty, elems)
+        });
+        let [lhs, rhs] = arrays;
+        let array_eq = cmp_eq(&mut context, lhs, rhs);
+        block.ins(&mut context).ret(struct_eq, bool_ty);
+
+        while super::combine_cmp(&mut context, &function) {}
+
+        let folded = |value: Value| match value.get_constant(&context) {
+            Some(Constant {
+                value: crate::ConstantValue::Bool(b),
+                ..
+            }) => Some(*b),
+            _ => None,
+        };
+        assert_eq!(folded(struct_eq), Some(true));
+        assert_eq!(folded(array_eq), Some(false));
+    }
 }
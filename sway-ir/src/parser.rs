@@ -460,6 +460,11 @@ mod ir_builder {
                 / "0x" s:$(hex_digit()*<64>) _ {
                     IrAstConstValue::Hex256(string_to_hex::<32>(s))
                 }
+                / "fixed" _ "(" _ "0x" s:$(hex_digit()*<64>) _ "," _ scale:decimal() ")" _ {?
+                    u8::try_from(scale)
+                        .map(|scale| IrAstConstValue::Fixed(string_to_hex::<32>(s), scale))
+                        .or(Err("fixed-point scale below 256"))
+                }
                 / n:decimal() { IrAstConstValue::Number(n) }
                 / string_const()
                 / array_const()
@@ -754,6 +759,7 @@ mod ir_builder {
         Unit,
         Bool(bool),
         Hex256([u8; 32]),
+        Fixed([u8; 32], u8),
         Number(u64),
         String(Vec<u8>),
         Array(IrAstTy, Vec<IrAstConst>),
@@ -791,6 +797,10 @@ mod ir_builder {
                     }
                     _ => unreachable!("invalid type for hex number"),
                 },
+                IrAstConstValue::Fixed(bs, scale) => ConstantValue::Fixed {
+                    value: U256::from_be_bytes(bs),
+                    scale: *scale,
+                },
                 IrAstConstValue::Number(n) => ConstantValue::Uint(*n),
                 IrAstConstValue::String(bs) => ConstantValue::String(bs.clone()),
                 IrAstConstValue::Array(el_ty, els) => {
@@ -798,14 +808,14 @@ mod ir_builder {
                         .iter()
                         .map(|cv| cv.value.as_constant(context, el_ty.clone()))
                         .collect();
-                    ConstantValue::Array(els)
+                    ConstantValue::Array(els.into())
                 }
                 IrAstConstValue::Struct(flds) => {
                     let fields: Vec<_> = flds
                         .iter()
                         .map(|(ty, cv)| cv.value.as_constant(context, ty.clone()))
                         .collect::<Vec<_>>();
-                    ConstantValue::Struct(fields)
+                    ConstantValue::Struct(fields.into())
                 }
             }
         }
@@ -830,6 +840,9 @@ mod ir_builder {
                     IrAstTy::B256 => Constant::get_b256(context, *bs),
                     _ => unreachable!("invalid type for hex number"),
                 },
+                IrAstConstValue::Fixed(bs, scale) => {
+                    Constant::get_fixed(context, U256::from_be_bytes(bs), *scale)
+                }
                 IrAstConstValue::Number(n) => match val_ty {
                     IrAstTy::U8 => Constant::get_uint(context, 8, *n),
                     IrAstTy::U64 => Constant::get_uint(context, 64, *n),
@@ -31,7 +31,7 @@ where
 
 impl<'a, 'c, 'eng, T> fmt::Debug for WithContext<'a, 'c, 'eng, T>
 where
-    T: DebugWithContext,
+    T: DebugWithContext + ?Sized,
 {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         let WithContext { thing, context } = self;
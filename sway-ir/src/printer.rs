@@ -279,10 +279,9 @@ fn function_to_doc<'a>(
                         .map(|(name, var)| {
                             let var_content = &context.local_vars[var.0];
                             let init_doc = match &var_content.initializer {
-                                Some(const_val) => Doc::text(format!(
-                                    " = const {}",
-                                    const_val.as_lit_string(context)
-                                )),
+                                Some(const_val) => {
+                                    Doc::text(format!(" = const {}", const_val.to_ir_text(context)))
+                                }
                                 None => Doc::Empty,
                             };
                             let mut_str = if var_content.mutable { "mut " } else { "" };
@@ -359,7 +358,7 @@ fn config_to_doc(
             Doc::text(format!(
                 "{} = config {}",
                 global_namer.name(context, const_val),
-                configurable.as_lit_string(context)
+                configurable.to_ir_text(context)
             ))
             .append(md_namer.md_idx_to_doc(context, metadata)),
         )
@@ -383,7 +382,7 @@ fn constant_to_doc(
             Doc::text(format!(
                 "{} = const {}",
                 namer.name(context, const_val),
-                constant.as_lit_string(context)
+                constant.to_ir_text(context)
             ))
             .append(md_namer.md_idx_to_doc(context, metadata)),
         )
@@ -1038,7 +1037,29 @@ fn asm_block_to_doc(
 }
 
 impl Constant {
-    fn as_lit_string(&self, context: &Context) -> String {
+    /// Returns the constant as it's written in the textual IR, e.g. `u64 42`,
+    /// `{ u64, bool } { u64 1, bool true }` or `[u64; 2] [u64 1, u64 2]`, i.e. its type followed
+    /// by its value.
+    ///
+    /// This is the text that follows `const` in the printed IR, which the IR parser reads back as
+    /// the same constant.
+    pub fn to_ir_text(&self, context: &Context) -> String {
+        self.to_ir_text_with(context, None)
+    }
+
+    /// Returns the constant like [Constant::to_ir_text], but with arrays of more than `max_elems`
+    /// elements, at any depth, shortened to their first and last few elements around a
+    /// `... N more ...` marker, e.g. `[u64; 100] [u64 0, u64 1, ... 97 more ..., u64 99]` for
+    /// a `max_elems` of 3.
+    ///
+    /// This keeps large constants like lookup tables readable in diagnostics and dumps. The text
+    /// can't be parsed back, so [Constant::to_ir_text] should be used where it's read by the IR
+    /// parser.
+    pub fn as_string_truncated(&self, context: &Context, max_elems: usize) -> String {
+        self.to_ir_text_with(context, Some(max_elems))
+    }
+
+    fn to_ir_text_with(&self, context: &Context, max_elems: Option<usize>) -> String {
         match &self.value {
             ConstantValue::Undef => format!("{} undef", self.ty.as_string(context)),
             ConstantValue::Unit => "unit ()".into(),
@@ -1055,6 +1076,17 @@ impl Constant {
                         .concat()
                 )
             }
+            ConstantValue::Fixed { value, scale } => {
+                let bytes = value.to_be_bytes();
+                format!(
+                    "u256 fixed(0x{}, {scale})",
+                    bytes
+                        .iter()
+                        .map(|b| format!("{b:02x}"))
+                        .collect::<Vec<String>>()
+                        .concat()
+                )
+            }
             ConstantValue::B256(v) => {
                 let bytes = v.to_be_bytes();
                 format!(
@@ -1080,21 +1112,33 @@ impl Constant {
                     .collect::<Vec<_>>()
                     .join("")
             ),
-            ConstantValue::Array(elems) => format!(
-                "{} [{}]",
-                self.ty.as_string(context),
-                elems
-                    .iter()
-                    .map(|elem| elem.as_lit_string(context))
-                    .collect::<Vec<String>>()
-                    .join(", ")
-            ),
+            ConstantValue::Array(elems) => {
+                let elem_text = |elem: &Constant| elem.to_ir_text_with(context, max_elems);
+                let elems_text = match max_elems {
+                    Some(max_elems) if elems.len() > max_elems => {
+                        // The first elements get the extra one if `max_elems` is odd.
+                        let tail_len = max_elems / 2;
+                        let head_len = max_elems - tail_len;
+                        elems[..head_len]
+                            .iter()
+                            .map(elem_text)
+                            .chain(std::iter::once(format!(
+                                "... {} more ...",
+                                elems.len() - max_elems
+                            )))
+                            .chain(elems[elems.len() - tail_len..].iter().map(elem_text))
+                            .collect::<Vec<String>>()
+                    }
+                    _ => elems.iter().map(elem_text).collect::<Vec<String>>(),
+                };
+                format!("{} [{}]", self.ty.as_string(context), elems_text.join(", "))
+            }
             ConstantValue::Struct(fields) => format!(
                 "{} {{ {} }}",
                 self.ty.as_string(context),
                 fields
                     .iter()
-                    .map(|field| field.as_lit_string(context))
+                    .map(|field| field.to_ir_text_with(context, max_elems))
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
@@ -0,0 +1,71 @@
+use crate::{
+    core::{session::Session, token::get_range_from_span},
+    lsp_ext::AbiMethod,
+};
+use lsp_types::Url;
+use sway_core::language::ty;
+use sway_error::handler::Handler;
+use sway_types::Spanned;
+
+/// Returns the entry points of the contract, i.e. the methods of the ABIs it implements, with
+/// the selectors that calls to them are dispatched with.
+///
+/// Methods whose selector can't be computed are returned with the errors instead. Returns `None`
+/// if the program isn't a contract, or hasn't been type checked.
+pub fn abi_methods(session: &Session) -> Option<Vec<AbiMethod>> {
+    let compiled_program = session.compiled_program.read();
+    let program = compiled_program.typed.as_ref()?;
+    let ty::TyProgramKind::Contract { abi_entries } = &program.kind else {
+        return None;
+    };
+    let engines = session.engines.read();
+    Some(
+        abi_entries
+            .iter()
+            .map(|decl_id| {
+                let fn_decl = engines.de().get_function(decl_id);
+                let uri = fn_decl
+                    .name
+                    .span()
+                    .source_id()
+                    .map(|source_id| engines.se().get_path(source_id))
+                    .and_then(|path| Url::from_file_path(path).ok())
+                    .and_then(|url| session.sync.to_workspace_url(url));
+                let handler = Handler::default();
+                let selector = fn_decl.to_fn_selector_value(&handler, &engines);
+                // Parameters whose selector names fail are left out of the selector, with an
+                // error, rather than failing it.
+                let (errors, _) = handler.consume();
+                let (selector, error) = match selector {
+                    Ok(selector) if errors.is_empty() => {
+                        let hex = selector
+                            .iter()
+                            .map(|b| format!("{b:02x}"))
+                            .collect::<String>();
+                        (Some(format!("0x{hex}")), None)
+                    }
+                    _ if errors.is_empty() => {
+                        (None, Some("The selector couldn't be computed.".to_string()))
+                    }
+                    _ => (
+                        None,
+                        Some(
+                            errors
+                                .iter()
+                                .map(|err| err.to_string())
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                        ),
+                    ),
+                };
+                AbiMethod {
+                    name: fn_decl.name.to_string(),
+                    uri,
+                    range: get_range_from_span(&fn_decl.name.span()),
+                    selector,
+                    error,
+                }
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,158 @@
+use crate::{
+    capabilities::{
+        code_actions::{
+            common::generate_impl::TAB, CodeActionContext, CODE_ACTION_MISSING_METHODS_TITLE,
+        },
+        diagnostic::DiagnosticData,
+    },
+    core::token::{AstToken, TypedAstToken},
+};
+use lsp_types::{
+    CodeAction as LspCodeAction, CodeActionKind, CodeActionOrCommand, Position, Range, TextEdit,
+    WorkspaceEdit,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use sway_core::{
+    language::{
+        parsed::{Declaration, ImplTrait},
+        ty::{self, TyTraitFn},
+    },
+    transform::AttributeKind,
+};
+use sway_types::Spanned;
+
+/// Returns a [CodeActionOrCommand] that inserts stubs for the methods missing from an `impl` of a
+/// trait or ABI into the `impl` block.
+///
+/// The signatures of the stubs are copied verbatim from the trait declaration, including their
+/// generic parameters and where clauses, and their bodies revert.
+pub(crate) fn missing_trait_methods_code_action(
+    ctx: &CodeActionContext,
+    diagnostics: &mut impl Iterator<Item = (Range, DiagnosticData)>,
+) -> Option<Vec<CodeActionOrCommand>> {
+    // Find a diagnostic that has the attached metadata listing the missing methods. Its range is
+    // the whole `impl` block.
+    let (missing_methods, range) = diagnostics.find_map(|(range, diag)| {
+        let names = diag.missing_trait_methods?;
+        Some((names, range))
+    })?;
+
+    let impl_trait = impl_trait_in_range(ctx, range)?;
+    let trait_fns = trait_fns(ctx, &impl_trait)?
+        .into_iter()
+        .filter(|trait_fn| missing_methods.contains(&trait_fn.name.to_string()))
+        .collect::<Vec<_>>();
+    if trait_fns.is_empty() {
+        return None;
+    }
+
+    let indentation = " ".repeat(range.start.character as usize);
+    let stubs = trait_fns
+        .iter()
+        .map(|trait_fn| stub_string(trait_fn, &indentation))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    // Stubs go on their own lines before the closing brace of the block, separated from the
+    // existing items by an empty line.
+    let text_edit = if range.end.line > range.start.line {
+        let separator = if impl_trait.items.is_empty() {
+            ""
+        } else {
+            "\n"
+        };
+        let position = Position {
+            line: range.end.line,
+            character: 0,
+        };
+        TextEdit {
+            range: Range::new(position, position),
+            new_text: format!("{separator}{stubs}\n"),
+        }
+    } else {
+        let position = Position {
+            line: range.end.line,
+            character: range.end.character.saturating_sub(1),
+        };
+        TextEdit {
+            range: Range::new(position, position),
+            new_text: format!("\n{stubs}\n{indentation}"),
+        }
+    };
+    let changes = HashMap::from([(ctx.uri.clone(), vec![text_edit])]);
+
+    Some(vec![CodeActionOrCommand::CodeAction(LspCodeAction {
+        title: format!(
+            "{} `{}`",
+            CODE_ACTION_MISSING_METHODS_TITLE, impl_trait.trait_name.suffix
+        ),
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        data: Some(Value::String(ctx.uri.to_string())),
+        ..Default::default()
+    })])
+}
+
+/// Returns the parsed `impl` of a trait whose trait name lies within `range`.
+fn impl_trait_in_range(ctx: &CodeActionContext, range: Range) -> Option<ImplTrait> {
+    ctx.tokens
+        .tokens_for_file(ctx.temp_uri)
+        .find_map(|(ident, token)| match token.parsed {
+            AstToken::Declaration(Declaration::ImplTrait(impl_trait))
+                if range.start <= ident.range.start && ident.range.end <= range.end =>
+            {
+                Some(impl_trait)
+            }
+            _ => None,
+        })
+}
+
+/// Returns the methods of the trait or ABI implemented by `impl_trait`, in the order they are
+/// declared, using the typed declaration from the token map.
+fn trait_fns(ctx: &CodeActionContext, impl_trait: &ImplTrait) -> Option<Vec<TyTraitFn>> {
+    let decl_engine = ctx.engines.de();
+    let trait_name = impl_trait.trait_name.suffix.to_string();
+    let interface_surface = ctx
+        .tokens
+        .tokens_for_name(&trait_name)
+        .find_map(|(_, token)| match token.typed? {
+            TypedAstToken::TypedDeclaration(ty::TyDecl::TraitDecl(ty::TraitDecl {
+                decl_id,
+                ..
+            })) => Some(decl_engine.get_trait(&decl_id).interface_surface),
+            TypedAstToken::TypedDeclaration(ty::TyDecl::AbiDecl(ty::AbiDecl {
+                decl_id, ..
+            })) => Some(decl_engine.get_abi(&decl_id).interface_surface),
+            _ => None,
+        })?;
+    Some(
+        interface_surface
+            .iter()
+            .filter_map(|item| match item {
+                ty::TyTraitInterfaceItem::TraitFn(decl_ref) => {
+                    Some(decl_engine.get_trait_fn(decl_ref))
+                }
+                _ => None,
+            })
+            .collect(),
+    )
+}
+
+/// Returns a [String] of a stub for `trait_fn` with its attributes, excluding doc comments.
+fn stub_string(trait_fn: &TyTraitFn, indentation: &str) -> String {
+    let attributes = trait_fn
+        .attributes
+        .iter()
+        .filter(|(kind, _)| !matches!(kind, AttributeKind::DocComment { .. }))
+        .flat_map(|(_, attrs)| attrs.iter())
+        .map(|attr| format!("{indentation}{TAB}{}\n", attr.span.as_str()))
+        .collect::<String>();
+    format!(
+        "{attributes}{indentation}{TAB}{} {{\n{indentation}{TAB}{TAB}revert(0)\n{indentation}{TAB}}}",
+        trait_fn.span().as_str()
+    )
+}
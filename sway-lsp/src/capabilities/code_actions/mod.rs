@@ -30,6 +30,7 @@ pub(crate) const CODE_ACTION_NEW_TITLE: &str = "Generate `new`";
 pub(crate) const CODE_ACTION_DOC_TITLE: &str = "Generate a documentation template";
 pub(crate) const CODE_ACTION_IMPORT_TITLE: &str = "Import";
 pub(crate) const CODE_ACTION_QUALIFY_TITLE: &str = "Qualify as";
+pub(crate) const CODE_ACTION_MISSING_METHODS_TITLE: &str = "Implement missing methods of";
 
 #[derive(Clone)]
 pub(crate) struct CodeActionContext<'a> {
@@ -1,30 +1,112 @@
-use crate::core::token::TokenIdent;
+use crate::{
+    capabilities::hover::{extract_fn_signature, format_doc_attributes},
+    core::{
+        session::Session,
+        token::{TokenIdent, TypedAstToken},
+    },
+};
 use lsp_types::{
-    CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionTextEdit, Position,
-    Range, TextEdit,
+    CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionTextEdit,
+    Documentation, MarkupContent, MarkupKind, Position, Range, TextEdit, Url,
 };
+use serde::{Deserialize, Serialize};
 use sway_core::{
     language::ty::{TyAstNodeContent, TyDecl, TyFunctionDecl},
     namespace::Items,
     Engines, TypeId, TypeInfo,
 };
+use sway_types::{Ident, Spanned};
+
+/// The data attached to a completion item, used to find the symbol it completes when the item is
+/// resolved.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionItemData {
+    /// The document the completion was requested in, used to find its session.
+    pub uri: Url,
+    /// The ident of the declaration of the completed field or method.
+    pub decl_ident: TokenIdent,
+}
 
 pub(crate) fn to_completion_items(
     namespace: &Items,
     engines: &Engines,
+    uri: &Url,
     ident_to_complete: &TokenIdent,
     fn_decl: &TyFunctionDecl,
     position: Position,
 ) -> Vec<CompletionItem> {
     type_id_of_raw_ident(engines, namespace, &ident_to_complete.name, fn_decl)
-        .map(|type_id| completion_items_for_type_id(engines, namespace, type_id, position))
+        .map(|type_id| completion_items_for_type_id(engines, namespace, uri, type_id, position))
         .unwrap_or_default()
 }
 
+/// Fills in the `documentation` and `detail` of a completion item returned by
+/// [to_completion_items], which are left out of the completion list to keep it fast.
+///
+/// Items whose declaration can no longer be found are returned unchanged.
+pub(crate) fn resolve_completion_item(
+    session: &Session,
+    mut item: CompletionItem,
+    data: CompletionItemData,
+) -> CompletionItem {
+    let Some(token) = session
+        .token_map()
+        .try_get(&data.decl_ident)
+        .try_unwrap()
+        .map(|item| item.value().clone())
+    else {
+        return item;
+    };
+    let doc_comment = format_doc_attributes(&token);
+    if !doc_comment.is_empty() {
+        item.documentation = Some(Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: doc_comment,
+        }));
+    }
+    item.detail = match token.typed {
+        Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) => {
+            Some(extract_fn_signature(&fn_decl.span()))
+        }
+        Some(TypedAstToken::TypedStructField(field)) => Some(format!(
+            "{}: {}",
+            field.name,
+            field.type_argument.span.as_str()
+        )),
+        _ => item.detail,
+    };
+    item
+}
+
+/// Returns the data to attach to the completion item of the field or method declared by `ident`.
+fn completion_item_data(engines: &Engines, uri: &Url, ident: &Ident) -> Option<serde_json::Value> {
+    let data = CompletionItemData {
+        uri: uri.clone(),
+        decl_ident: TokenIdent::new(ident, engines.se()),
+    };
+    serde_json::to_value(data).ok()
+}
+
+/// Returns a completion item for each reserved keyword, sorted alphabetically.
+pub(crate) fn keyword_completion_items() -> Vec<CompletionItem> {
+    let mut keywords = sway_parse::RESERVED_KEYWORDS.iter().collect::<Vec<_>>();
+    keywords.sort();
+    keywords
+        .into_iter()
+        .map(|keyword| CompletionItem {
+            label: keyword.to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            ..Default::default()
+        })
+        .collect()
+}
+
 /// Gathers the given [TypeId] struct's fields and methods and builds completion items.
 fn completion_items_for_type_id(
     engines: &Engines,
     namespace: &Items,
+    uri: &Url,
     type_id: TypeId,
     position: Position,
 ) -> Vec<CompletionItem> {
@@ -40,6 +122,7 @@ fn completion_items_for_type_id(
                     description: Some(field.type_argument.span.str()),
                     detail: None,
                 }),
+                data: completion_item_data(engines, uri, &field.name),
                 ..Default::default()
             };
             completion_items.push(item);
@@ -80,6 +163,7 @@ fn completion_items_for_type_id(
                     description: Some(fn_signature_string(engines, &fn_decl, &type_id)),
                     detail: None,
                 }),
+                data: completion_item_data(engines, uri, &fn_decl.name),
                 ..Default::default()
             };
             completion_items.push(item);
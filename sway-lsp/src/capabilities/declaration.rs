@@ -0,0 +1,67 @@
+use crate::core::{
+    session::Session,
+    token::{TokenIdent, TypedAstToken},
+};
+use lsp_types::{GotoDefinitionResponse, Location, Position, Url};
+use std::sync::Arc;
+use sway_core::{decl_engine::InterfaceDeclId, language::ty, Engines};
+use sway_types::Ident;
+
+/// Returns the location of the declaration of the symbol at the given position.
+///
+/// For methods that implement a trait or ABI, this is the method in the trait or ABI rather than
+/// its definition in the `impl` block. All other symbols are declared where they are defined.
+pub fn goto_declaration(
+    session: Arc<Session>,
+    uri: Url,
+    position: Position,
+) -> Option<GotoDefinitionResponse> {
+    let (_, token) = session.token_map().token_at_position(&uri, position)?;
+    let interface_fn_ident = {
+        let engines = session.engines.read();
+        let fn_decl = match token.typed {
+            Some(TypedAstToken::TypedExpression(ty::TyExpression {
+                expression: ty::TyExpressionVariant::FunctionApplication { fn_ref, .. },
+                ..
+            })) => Some(engines.de().get_function(&fn_ref)),
+            Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) => Some(fn_decl),
+            _ => None,
+        };
+        fn_decl
+            .and_then(|fn_decl| interface_fn_name(&engines, &fn_decl))
+            .map(|ident| TokenIdent::new(&ident, engines.se()))
+    };
+    match interface_fn_ident {
+        Some(decl_ident) => decl_ident.path.and_then(|path| {
+            Url::from_file_path(path).ok().and_then(|url| {
+                session
+                    .sync
+                    .to_workspace_url(url)
+                    .map(|url| GotoDefinitionResponse::Scalar(Location::new(url, decl_ident.range)))
+            })
+        }),
+        None => session.token_definition_response(uri, position),
+    }
+}
+
+/// Returns the name of the method in the trait or ABI that the given function implements.
+fn interface_fn_name(engines: &Engines, fn_decl: &ty::TyFunctionDecl) -> Option<Ident> {
+    let interface_surface = match fn_decl.implementing_type.as_ref()? {
+        ty::TyDecl::ImplTrait(ty::ImplTrait { decl_id, .. }) => {
+            match engines.de().get_impl_trait(decl_id).trait_decl_ref?.id() {
+                InterfaceDeclId::Abi(decl_id) => engines.de().get_abi(decl_id).interface_surface,
+                InterfaceDeclId::Trait(decl_id) => {
+                    engines.de().get_trait(decl_id).interface_surface
+                }
+            }
+        }
+        _ => return None,
+    };
+    interface_surface.iter().find_map(|item| match item {
+        ty::TyTraitInterfaceItem::TraitFn(decl_ref) => {
+            let trait_fn = engines.de().get_trait_fn(decl_ref);
+            (trait_fn.name.as_str() == fn_decl.name.as_str()).then_some(trait_fn.name)
+        }
+        _ => None,
+    })
+}
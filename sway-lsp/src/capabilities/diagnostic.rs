@@ -99,6 +99,8 @@ fn get_warning_diagnostic_tags(warning: &Warning) -> Option<Vec<DiagnosticTag>>
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct DiagnosticData {
     pub unknown_symbol_name: Option<String>,
+    /// The names of the trait methods missing from an `impl` block.
+    pub missing_trait_methods: Option<Vec<String>>,
 }
 
 impl TryFrom<CompileWarning> for DiagnosticData {
@@ -116,9 +118,22 @@ impl TryFrom<CompileError> for DiagnosticData {
         match value {
             CompileError::SymbolNotFound { name, .. } => Ok(DiagnosticData {
                 unknown_symbol_name: Some(name.to_string()),
+                ..Default::default()
             }),
             CompileError::UnknownVariable { var_name, .. } => Ok(DiagnosticData {
                 unknown_symbol_name: Some(var_name.to_string()),
+                ..Default::default()
+            }),
+            CompileError::MissingInterfaceSurfaceMethods {
+                missing_functions, ..
+            } => Ok(DiagnosticData {
+                missing_trait_methods: Some(
+                    missing_functions
+                        .iter()
+                        .map(|ident| ident.as_str().to_string())
+                        .collect(),
+                ),
+                ..Default::default()
             }),
             _ => anyhow::bail!("Not implemented"),
         }
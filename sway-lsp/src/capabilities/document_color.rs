@@ -0,0 +1,120 @@
+use crate::core::{session::Session, token::SymbolKind};
+use lsp_types::{Color, ColorInformation, ColorPresentation, Range, TextEdit, Url};
+use std::sync::Arc;
+
+/// Returns a color for each `b256` literal of the document, e.g. those of addresses and contract
+/// IDs, so that clients decorate them with a swatch.
+///
+/// The color is made of the first three bytes of the value, so that occurrences of the same value
+/// can be recognized at a glance.
+pub fn document_colors(session: Arc<Session>, uri: &Url) -> Vec<ColorInformation> {
+    let mut colors: Vec<_> = session
+        .token_map()
+        .tokens_for_file(uri)
+        .filter(|(_, token)| token.kind == SymbolKind::ByteLiteral)
+        .filter_map(|(ident, _)| {
+            let bytes = parse_b256(&ident.name)?;
+            Some(ColorInformation {
+                range: ident.range,
+                color: Color {
+                    red: f32::from(bytes[0]) / 255.0,
+                    green: f32::from(bytes[1]) / 255.0,
+                    blue: f32::from(bytes[2]) / 255.0,
+                    alpha: 1.0,
+                },
+            })
+        })
+        .collect();
+    colors.sort_by_key(|color| (color.range.start, color.range.end));
+    colors.dedup_by_key(|color| color.range);
+    colors
+}
+
+/// Returns the ways the `b256` literal at the requested range can be written: as lowercase and
+/// uppercase hex, and in binary. Picking one replaces the literal with it.
+///
+/// The requested color is ignored, since the color of a literal is derived from its value.
+pub fn color_presentations(
+    session: Arc<Session>,
+    uri: &Url,
+    range: Range,
+) -> Vec<ColorPresentation> {
+    let Some(bytes) = session
+        .token_map()
+        .tokens_for_file(uri)
+        .find(|(ident, token)| token.kind == SymbolKind::ByteLiteral && ident.range == range)
+        .and_then(|(ident, _)| parse_b256(&ident.name))
+    else {
+        return vec![];
+    };
+
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    let binary: String = bytes.iter().map(|byte| format!("{byte:08b}")).collect();
+    [
+        format!("0x{hex}"),
+        format!("0x{}", hex.to_uppercase()),
+        format!("0b{binary}"),
+    ]
+    .into_iter()
+    .map(|literal| presentation(range, literal))
+    .collect()
+}
+
+fn presentation(range: Range, literal: String) -> ColorPresentation {
+    ColorPresentation {
+        label: literal.clone(),
+        text_edit: Some(TextEdit {
+            range,
+            new_text: literal,
+        }),
+        additional_text_edits: None,
+    }
+}
+
+/// Parses the value of a `b256` literal written in hex or binary, ignoring `_` separators.
+fn parse_b256(literal: &str) -> Option<[u8; 32]> {
+    let (digits, bits_per_digit) = if let Some(digits) = literal.strip_prefix("0x") {
+        (digits, 4)
+    } else if let Some(digits) = literal.strip_prefix("0b") {
+        (digits, 1)
+    } else {
+        return None;
+    };
+    let digits: Vec<u32> = digits
+        .chars()
+        .filter(|c| *c != '_')
+        .map(|c| c.to_digit(1 << bits_per_digit))
+        .collect::<Option<_>>()?;
+    if digits.len() * bits_per_digit != 256 {
+        return None;
+    }
+
+    let mut bytes = [0u8; 32];
+    let digits_per_byte = 8 / bits_per_digit;
+    for (byte, chunk) in bytes.iter_mut().zip(digits.chunks(digits_per_byte)) {
+        *byte = chunk
+            .iter()
+            .fold(0, |byte, digit| (byte << bits_per_digit) | *digit as u8);
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_b256_literals() {
+        let hex = format!("0x{}", "9ae5b658".repeat(8));
+        let bytes = parse_b256(&hex).unwrap();
+        assert_eq!(bytes[..4], [0x9a, 0xe5, 0xb6, 0x58]);
+        let separated = format!("0x{}", ["9AE5B658"; 8].join("_"));
+        assert_eq!(parse_b256(&separated), Some(bytes));
+        let binary = format!("0b{}", "10011010".repeat(32));
+        assert_eq!(parse_b256(&binary), Some([0x9a; 32]));
+
+        assert_eq!(parse_b256("0x9ae5"), None);
+        assert_eq!(parse_b256(&format!("0x{}", "g".repeat(64))), None);
+        assert_eq!(parse_b256("42"), None);
+    }
+}
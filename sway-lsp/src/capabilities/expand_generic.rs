@@ -0,0 +1,142 @@
+use crate::core::{session::Session, token::TypedAstToken};
+use lsp_types::{Position, Url};
+use std::{collections::HashMap, sync::Arc};
+use sway_ast::token::{CommentedTokenStream, CommentedTokenTree, CommentedTree};
+use sway_core::{language::ty, Engines};
+use sway_error::handler::Handler;
+use sway_types::Spanned;
+
+/// Returns the monomorphized form of the generic function called at the given position.
+///
+/// The type engine already resolves the type parameters of every call site, so the
+/// function declaration referenced by the call holds the concrete types for this instantiation.
+/// The result contains the type parameter bindings, followed by the instantiated signature and
+/// body. In the body, the identifiers naming a type parameter are replaced by the concrete type,
+/// but the calls it makes aren't expanded any further.
+pub fn expand_generic(session: Arc<Session>, url: &Url, position: Position) -> Option<String> {
+    let (_, token) = session.token_map().token_at_position(url, position)?;
+    let engines = session.engines.read();
+    match token.typed {
+        Some(TypedAstToken::TypedExpression(ty::TyExpression {
+            expression: ty::TyExpressionVariant::FunctionApplication { fn_ref, .. },
+            ..
+        })) => {
+            let fn_decl = engines.de().get_function(&fn_ref);
+            if fn_decl.type_parameters.is_empty() {
+                return None;
+            }
+            Some(format_instantiation(&engines, &fn_decl))
+        }
+        _ => None,
+    }
+}
+
+fn format_instantiation(engines: &Engines, fn_decl: &ty::TyFunctionDecl) -> String {
+    let substitutions = fn_decl
+        .type_parameters
+        .iter()
+        .map(|type_param| {
+            (
+                type_param.name_ident.as_str(),
+                engines.help_out(type_param.type_id).to_string(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    let bindings = fn_decl
+        .type_parameters
+        .iter()
+        .map(|type_param| {
+            let name = type_param.name_ident.as_str();
+            format!("// {name} = {}\n", substitutions[name])
+        })
+        .collect::<String>();
+
+    let params = fn_decl
+        .parameters
+        .iter()
+        .map(|param| {
+            let mutability = if param.is_mutable { "mut " } else { "" };
+            format!(
+                "{mutability}{}: {}",
+                param.name.as_str(),
+                engines.help_out(param.type_argument.type_id)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let body = fn_decl
+        .span()
+        .as_str()
+        .find('{')
+        .map(|start| substitute_type_params(&fn_decl.span().as_str()[start..], &substitutions))
+        .unwrap_or_default();
+
+    format!(
+        "{bindings}fn {}({params}) -> {} {body}",
+        fn_decl.name.as_str(),
+        engines.help_out(fn_decl.return_type.type_id)
+    )
+}
+
+/// Replaces the identifiers of `body` that are keys of `substitutions` with their values.
+///
+/// The body is lexed so that comments and literals are left as written. If it can't be lexed, it
+/// is returned unchanged.
+fn substitute_type_params(body: &str, substitutions: &HashMap<&str, String>) -> String {
+    let src: Arc<str> = body.into();
+    let handler = Handler::default();
+    let stream = match sway_parse::lex_commented(&handler, &src, 0, src.len(), &None) {
+        Ok(stream) if !handler.has_errors() => stream,
+        _ => return body.to_string(),
+    };
+    let mut idents = vec![];
+    collect_idents(&stream, substitutions, &mut idents);
+
+    let mut substituted = String::with_capacity(body.len());
+    let mut pos = 0;
+    for (start, end, ty) in idents {
+        substituted.push_str(&body[pos..start]);
+        substituted.push_str(ty);
+        pos = end;
+    }
+    substituted.push_str(&body[pos..]);
+    substituted
+}
+
+/// Collects the spans of the identifiers of `stream` to substitute, in source order.
+fn collect_idents<'s>(
+    stream: &CommentedTokenStream,
+    substitutions: &'s HashMap<&str, String>,
+    idents: &mut Vec<(usize, usize, &'s str)>,
+) {
+    for tree in &stream.token_trees {
+        match tree {
+            CommentedTokenTree::Tree(CommentedTree::Ident(ident)) => {
+                if let Some(ty) = substitutions.get(ident.as_str()) {
+                    let span = ident.span();
+                    idents.push((span.start(), span.end(), ty));
+                }
+            }
+            CommentedTokenTree::Tree(CommentedTree::Group(group)) => {
+                collect_idents(&group.token_stream, substitutions, idents);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_type_params_in_body() {
+        let substitutions = HashMap::from([("T", "Option<u32>".to_string())]);
+        let body = "{\n    // T is generic\n    let v: Vec<T> = Vec::new();\n    let s = \"T\";\n    TT\n}";
+        assert_eq!(
+            substitute_type_params(body, &substitutions),
+            "{\n    // T is generic\n    let v: Vec<Option<u32>> = Vec::new();\n    let s = \"T\";\n    TT\n}"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{
+    capabilities::show_ast::ast_to_string, core::session::Session, lsp_ext::FileCheckResult,
+};
+use lsp_types::Url;
+use std::sync::Arc;
+
+/// Runs the FileCheck `directives` against the "lexed", "parsed" or "typed" AST of the file at `url`,
+/// as it would be displayed by `sway/show_ast`.
+///
+/// Directives use the same syntax as the e2e test harness, e.g. `# check: fn main`, and are matched
+/// by the same `filecheck` crate. Returns `None` if the AST for `stage` is not available.
+pub fn filecheck(
+    session: Arc<Session>,
+    url: &Url,
+    directives: &str,
+    stage: &str,
+) -> Option<FileCheckResult> {
+    let ast = ast_to_string(&session, &url.to_file_path().ok(), stage)?;
+    let mut builder = filecheck::CheckerBuilder::new();
+    if let Err(err) = builder.text(directives) {
+        return Some(FileCheckResult::error(format!(
+            "FileCheck directive error: {err}"
+        )));
+    }
+    let checker = builder.finish();
+    if checker.is_empty() {
+        return Some(FileCheckResult::error(
+            "No FileCheck directives found.".to_string(),
+        ));
+    }
+    let result = match checker.explain(&ast, filecheck::NO_VARIABLES) {
+        Ok((success, report)) => FileCheckResult { success, report },
+        Err(err) => FileCheckResult::error(format!("FileCheck directive error: {err}")),
+    };
+    Some(result)
+}
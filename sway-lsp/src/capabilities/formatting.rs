@@ -1,5 +1,5 @@
 use crate::error::LanguageServerError;
-use lsp_types::{Position, Range, TextEdit};
+use lsp_types::{FormattingOptions, Position, Range, TextEdit};
 use std::sync::Arc;
 use swayfmt::Formatter;
 
@@ -21,3 +21,67 @@ pub fn get_page_text_edit(
         new_text: formatted_code,
     })
 }
+
+/// Formats a snippet of Sway code that isn't a document of the workspace, e.g. generated code,
+/// indenting it as requested by `options`.
+///
+/// Snippets without a module kind, e.g. a single function, are formatted as the items of a library.
+pub fn format_snippet(
+    text: &str,
+    options: &FormattingOptions,
+) -> Result<String, LanguageServerError> {
+    let mut formatter = Formatter::default();
+    formatter.config.whitespace.hard_tabs = !options.insert_spaces;
+    formatter.config.whitespace.tab_spaces = options.tab_size as usize;
+
+    let error = match formatter.format(Arc::from(text), None) {
+        Ok(formatted_code) => return Ok(formatted_code),
+        Err(error) => error,
+    };
+    let mut formatter = Formatter {
+        config: formatter.config,
+        ..Default::default()
+    };
+    formatter
+        .format(Arc::from(format!("{LIBRARY_PREFIX}{text}")), None)
+        .ok()
+        .and_then(|formatted_code| {
+            formatted_code
+                .strip_prefix(LIBRARY_PREFIX.trim_end())
+                .map(|items| items.trim_start_matches(['\r', '\n']).to_string())
+        })
+        // Report why the snippet itself failed to format, rather than the wrapped one.
+        .ok_or(LanguageServerError::FormatError(error))
+}
+
+const LIBRARY_PREFIX: &str = "library;\n\n";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(tab_size: u32) -> FormattingOptions {
+        FormattingOptions {
+            tab_size,
+            insert_spaces: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn format_snippets() {
+        let module = "library;\nfn foo()->u64{1}\n";
+        assert_eq!(
+            format_snippet(module, &options(4)).unwrap(),
+            "library;\nfn foo() -> u64 {\n    1\n}\n"
+        );
+
+        let items = "fn foo()->u64{1}";
+        assert_eq!(
+            format_snippet(items, &options(2)).unwrap(),
+            "fn foo() -> u64 {\n  1\n}\n"
+        );
+
+        assert!(format_snippet("fn foo( {", &options(4)).is_err());
+    }
+}
@@ -80,12 +80,12 @@ fn visibility_as_str(visibility: &Visibility) -> &'static str {
 }
 
 /// Expects a span from either a `FunctionDeclaration` or a `TypedFunctionDeclaration`.
-fn extract_fn_signature(span: &Span) -> String {
+pub(crate) fn extract_fn_signature(span: &Span) -> String {
     let value = span.as_str();
     value.split('{').take(1).map(|v| v.trim()).collect()
 }
 
-fn format_doc_attributes(token: &Token) -> String {
+pub(crate) fn format_doc_attributes(token: &Token) -> String {
     let mut doc_comment = String::new();
     if let Some(attributes) = doc_comment_attributes(token) {
         doc_comment = attributes
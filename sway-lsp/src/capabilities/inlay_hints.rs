@@ -2,18 +2,28 @@ use crate::{
     config::InlayHintsConfig,
     core::{
         session::Session,
-        token::{get_range_from_span, TypedAstToken},
+        token::{get_range_from_span, AstToken, TypedAstToken},
     },
 };
 use lsp_types::{self, Range, Url};
 use std::sync::Arc;
-use sway_core::{language::ty::TyDecl, type_system::TypeInfo};
-use sway_types::Spanned;
+use sway_core::{
+    ir_generation::enum_layout,
+    language::{
+        parsed::{Expression, ExpressionKind},
+        ty::{self, TyDecl},
+        Literal,
+    },
+    type_system::TypeInfo,
+    Engines,
+};
+use sway_types::{integer_bits::IntegerBits, Span, Spanned};
 
 // Future PR's will add more kinds
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum InlayKind {
     TypeHint,
+    SizeHint,
 }
 
 #[derive(Debug)]
@@ -29,63 +39,191 @@ pub fn inlay_hints(
     range: &Range,
     config: &InlayHintsConfig,
 ) -> Option<Vec<lsp_types::InlayHint>> {
-    // 1. Loop through all our tokens and filter out all tokens that aren't TypedVariableDeclaration tokens
+    // 1. Loop through all our tokens and filter out all tokens that aren't TypedVariableDeclaration
+    //    tokens, or unsuffixed integer literals inferred to be something other than `u64` if
+    //    literal hints are enabled
     // 2. Also filter out all tokens that have a span that fall outside of the provided range
     // 3. Filter out all variable tokens that have a type_ascription
     // 4. Look up the type id for the remaining tokens
     // 5. Convert the type into a string
-    if !config.type_hints {
+    // 6. Add the sizes of enum declarations if enum size hints are enabled
+    if !config.type_hints && !config.literal_hints && !config.enum_size_hints {
         return None;
     }
 
     let engines = session.engines.read();
     let type_engine = engines.te();
+    let in_range = |span: &Span| {
+        let token_range = get_range_from_span(span);
+        token_range.start >= range.start && token_range.end <= range.end
+    };
 
-    let hints: Vec<lsp_types::InlayHint> = session
+    let mut hints: Vec<lsp_types::InlayHint> = session
         .token_map()
         .tokens_for_file(uri)
         .filter_map(|(_, token)| {
-            token.typed.as_ref().and_then(|t| match t {
-                TypedAstToken::TypedDeclaration(TyDecl::VariableDecl(var_decl)) => {
-                    match var_decl.type_ascription.call_path_tree {
-                        Some(_) => None,
-                        None => {
-                            let var_range = get_range_from_span(&var_decl.name.span());
-                            if var_range.start >= range.start && var_range.end <= range.end {
-                                Some(var_decl.clone())
-                            } else {
-                                None
-                            }
+            let (span, type_id) = match token.typed.as_ref()? {
+                TypedAstToken::TypedDeclaration(TyDecl::VariableDecl(var_decl))
+                    if config.type_hints && var_decl.type_ascription.call_path_tree.is_none() =>
+                {
+                    (var_decl.name.span(), var_decl.type_ascription.type_id)
+                }
+                TypedAstToken::TypedExpression(expr)
+                    if config.literal_hints && is_unsuffixed_integer(&token.parsed) =>
+                {
+                    // Integer literals are `u64` unless inferred otherwise, so only the surprising
+                    // widths are worth a hint.
+                    match type_engine.get(expr.return_type) {
+                        TypeInfo::UnsignedInteger(bits) if bits != IntegerBits::SixtyFour => {
+                            (expr.span.clone(), expr.return_type)
                         }
+                        _ => return None,
                     }
                 }
-                _ => None,
-            })
-        })
-        .filter_map(|var| {
-            let type_info = type_engine.get(var.type_ascription.type_id);
-            match type_info {
+                _ => return None,
+            };
+            if !in_range(&span) {
+                return None;
+            }
+            match type_engine.get(type_id) {
                 TypeInfo::Unknown | TypeInfo::UnknownGeneric { .. } => None,
-                _ => Some(var),
+                _ => Some((span, type_id)),
             }
         })
-        .map(|var| {
-            let range = get_range_from_span(&var.name.span());
+        .map(|(span, type_id)| {
+            let range = get_range_from_span(&span);
             let kind = InlayKind::TypeHint;
-            let label = format!("{}", engines.help_out(var.type_ascription));
+            let label = truncate_label(format!("{}", engines.help_out(type_id)), config.max_length);
             let inlay_hint = InlayHint { range, kind, label };
             self::inlay_hint(config.render_colons, inlay_hint)
         })
         .collect();
 
+    if config.enum_size_hints {
+        hints.extend(
+            session
+                .token_map()
+                .tokens_for_file(uri)
+                .filter_map(|(ident, token)| match token.typed? {
+                    // Only the name of the declaration itself, not the usages of the enum.
+                    TypedAstToken::TypedDeclaration(TyDecl::EnumDecl(ty::EnumDecl {
+                        name,
+                        decl_id,
+                        ..
+                    })) if ident.range == get_range_from_span(&name.span()) => {
+                        Some(engines.de().get_enum(&decl_id))
+                    }
+                    _ => None,
+                })
+                .filter(|decl| in_range(&decl.span))
+                .flat_map(|decl| enum_size_hints(&engines, &decl))
+                .map(|inlay_hint| self::inlay_hint(config.render_colons, inlay_hint)),
+        );
+    }
+
     Some(hints)
 }
 
+/// Returns hints with the in-memory size of the enum declaration `decl`, after its name, and the
+/// size of the payload of each of its variants, after the variant.
+///
+/// Generic enums have no hints, as their size depends on their type arguments.
+fn enum_size_hints(engines: &Engines, decl: &ty::TyEnumDecl) -> Vec<InlayHint> {
+    if !decl.type_parameters.is_empty() {
+        return vec![];
+    }
+    let Ok(layout) = enum_layout(engines, &decl.variants) else {
+        return vec![];
+    };
+    let size_hint = |span: &Span, size: u64| InlayHint {
+        range: get_range_from_span(span),
+        kind: InlayKind::SizeHint,
+        label: format!("{size} bytes"),
+    };
+    std::iter::once(size_hint(
+        &decl.call_path.suffix.span(),
+        layout.size_in_bytes,
+    ))
+    .chain(
+        decl.variants
+            .iter()
+            .zip(layout.variant_sizes_in_bytes)
+            .map(|(variant, size)| size_hint(&variant.span, size)),
+    )
+    .collect()
+}
+
+/// Returns `true` if the token is an integer literal written without a type suffix, e.g. `5`
+/// rather than `5u8`.
+fn is_unsuffixed_integer(token: &AstToken) -> bool {
+    matches!(
+        token,
+        AstToken::Expression(Expression {
+            kind: ExpressionKind::Literal(Literal::Numeric(_)),
+            ..
+        })
+    )
+}
+
+/// Shortens a type label to at most `max_length` characters.
+///
+/// The arguments of the most deeply nested generic types are collapsed into `…` first, so that
+/// e.g. `Option<Result<u64, u64>>` becomes `Option<Result<…>>` and then `Option<…>`. Labels that
+/// are still too long are cut off and end with `…`.
+fn truncate_label(label: String, max_length: Option<usize>) -> String {
+    let max_length = match max_length {
+        Some(max_length) => max_length,
+        None => return label,
+    };
+    let max_depth = label
+        .chars()
+        .scan(0usize, |depth, c| {
+            match c {
+                '<' => *depth += 1,
+                '>' => *depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            Some(*depth)
+        })
+        .max()
+        .unwrap_or(0);
+    let mut label = label;
+    for collapse_depth in (1..=max_depth).rev() {
+        if label.chars().count() <= max_length {
+            return label;
+        }
+        let mut depth = 0;
+        label = label
+            .chars()
+            .filter_map(|c| {
+                match c {
+                    '<' => depth += 1,
+                    '>' => depth = usize::saturating_sub(depth, 1),
+                    _ => {}
+                }
+                match (c, depth) {
+                    ('<', depth) if depth == collapse_depth => Some("<…".to_string()),
+                    (_, depth) if depth >= collapse_depth => None,
+                    _ => Some(c.to_string()),
+                }
+            })
+            .collect();
+    }
+    if label.chars().count() <= max_length {
+        return label;
+    }
+    let truncated = label
+        .chars()
+        .take(max_length.saturating_sub(1))
+        .collect::<String>();
+    format!("{truncated}…")
+}
+
 fn inlay_hint(render_colons: bool, inlay_hint: InlayHint) -> lsp_types::InlayHint {
     lsp_types::InlayHint {
         position: match inlay_hint.kind {
             // after annotated thing
-            InlayKind::TypeHint => inlay_hint.range.end,
+            InlayKind::TypeHint | InlayKind::SizeHint => inlay_hint.range.end,
         },
         label: lsp_types::InlayHintLabel::String(match inlay_hint.kind {
             InlayKind::TypeHint if render_colons => format!(": {}", inlay_hint.label),
@@ -93,15 +231,43 @@ fn inlay_hint(render_colons: bool, inlay_hint: InlayHint) -> lsp_types::InlayHin
         }),
         kind: match inlay_hint.kind {
             InlayKind::TypeHint => Some(lsp_types::InlayHintKind::TYPE),
+            InlayKind::SizeHint => None,
         },
         tooltip: None,
         padding_left: Some(match inlay_hint.kind {
             InlayKind::TypeHint => !render_colons,
+            InlayKind::SizeHint => true,
         }),
         padding_right: Some(match inlay_hint.kind {
-            InlayKind::TypeHint => false,
+            InlayKind::TypeHint | InlayKind::SizeHint => false,
         }),
         text_edits: None,
         data: None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::truncate_label;
+
+    #[test]
+    fn truncate_label_collapses_nested_generics() {
+        let label = "Option<Result<u64, Vec<u8>>>".to_string();
+        assert_eq!(truncate_label(label.clone(), None), label);
+        assert_eq!(truncate_label(label.clone(), Some(28)), label);
+        assert_eq!(
+            truncate_label(label.clone(), Some(27)),
+            "Option<Result<u64, Vec<…>>>"
+        );
+        assert_eq!(truncate_label(label.clone(), Some(20)), "Option<Result<…>>");
+        assert_eq!(truncate_label(label.clone(), Some(10)), "Option<…>");
+        assert_eq!(truncate_label(label, Some(5)), "Opti…");
+    }
+
+    #[test]
+    fn truncate_label_without_generics() {
+        let label = "VeryLongStructName".to_string();
+        assert_eq!(truncate_label(label.clone(), Some(18)), label);
+        assert_eq!(truncate_label(label, Some(8)), "VeryLon…");
+    }
+}
@@ -0,0 +1,62 @@
+use crate::core::{
+    session::Session,
+    token::{get_range_from_span, SymbolKind, TypedAstToken},
+};
+use lsp_types::{InlineValue, InlineValueParams, InlineValueVariableLookup, Position, Range, Url};
+use std::sync::Arc;
+
+/// Returns the locations of the variables in scope of the stack frame the debugger stopped in, so
+/// that the client can look up their values from the debugger and show them inline.
+///
+/// The scope is the innermost function containing the stopped location. Only the variables and
+/// parameters of that function in the requested range that appear before the stopped location are
+/// returned, since later ones either don't exist yet or still have stale values.
+pub fn inline_values(
+    session: Arc<Session>,
+    uri: &Url,
+    params: &InlineValueParams,
+) -> Option<Vec<InlineValue>> {
+    let stopped_location = params.context.stopped_location;
+    let tokens: Vec<_> = session.token_map().tokens_for_file(uri).collect();
+
+    let scope = tokens
+        .iter()
+        .filter_map(|(_, token)| match &token.typed {
+            Some(TypedAstToken::TypedFunctionDeclaration(decl)) => {
+                Some(get_range_from_span(&decl.span))
+            }
+            _ => None,
+        })
+        .filter(|range| contains(range, stopped_location.start))
+        .max_by_key(|range| range.start)?;
+
+    let mut values: Vec<_> = tokens
+        .into_iter()
+        .filter(|(ident, token)| {
+            matches!(token.kind, SymbolKind::Variable | SymbolKind::ValueParam)
+                && contains(&scope, ident.range.start)
+                && contains(&params.range, ident.range.start)
+                && ident.range.end <= stopped_location.end
+        })
+        .map(|(ident, _)| (ident.range, ident.name))
+        .collect();
+    values.sort_by_key(|(range, _)| (range.start, range.end));
+    values.dedup_by_key(|(range, _)| *range);
+
+    Some(
+        values
+            .into_iter()
+            .map(|(range, name)| {
+                InlineValue::VariableLookup(InlineValueVariableLookup {
+                    range,
+                    variable_name: Some(name),
+                    case_sensitive_lookup: true,
+                })
+            })
+            .collect(),
+    )
+}
+
+fn contains(range: &Range, position: Position) -> bool {
+    range.start <= position && position <= range.end
+}
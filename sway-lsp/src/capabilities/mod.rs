@@ -1,16 +1,28 @@
+pub mod abi_methods;
 pub mod code_actions;
 pub mod code_lens;
 pub mod completion;
+pub mod declaration;
 pub mod diagnostic;
+pub mod document_color;
 pub mod document_symbol;
+pub mod expand_generic;
+pub mod filecheck;
 pub mod formatting;
 pub mod highlight;
 pub mod hover;
 pub mod inlay_hints;
+pub mod inline_value;
+pub mod moniker;
 pub mod on_enter;
+pub mod on_type_formatting;
 pub mod rename;
+pub mod rename_files;
+pub mod run_test;
 pub mod runnable;
 pub mod semantic_tokens;
+pub mod show_ast;
+pub mod syntax_tree;
 
 pub(crate) use code_actions::code_actions;
 pub(crate) use on_enter::on_enter;
@@ -0,0 +1,153 @@
+//! Monikers identify a symbol across projects, which lets indexers link references in one
+//! package to declarations in another.
+//!
+//! Two schemes are used:
+//!
+//! - `sway`: symbols that can be referenced from other modules. The identifier is the package
+//!   name, followed by the module path of the declaring file and the symbol name, separated
+//!   by `::`. The entry file of a package (e.g. `main.sw` or `lib.sw`) is the package root, so
+//!   `Option` declared in `src/option.sw` of `std` is `std::option::Option`. Struct fields and enum
+//!   variants are qualified with their parent type, e.g. `std::option::Option::Some`. Methods are
+//!   qualified with the type they're implemented for, e.g. `std::vec::Vec::new`, or for trait and
+//!   ABI implementations with the type and the trait, e.g. `std::u128::<U128 as Add>::add`.
+//!   Methods declared in a trait or ABI itself are qualified with its name.
+//! - `local`: symbols that are only visible in the scope that declares them, such as variables,
+//!   function parameters and type parameters. The identifier is the symbol name followed by the
+//!   zero-based line and character of its declaration, e.g. `x:24:8`, and is unique within the
+//!   document only.
+use crate::core::{
+    session::Session,
+    token::{SymbolKind, TokenIdent, TypedAstToken},
+};
+use forc_pkg::PackageManifestFile;
+use lsp_types::{Moniker, MonikerKind, Position, UniquenessLevel, Url};
+use std::{path::Path, sync::Arc};
+use sway_core::language::ty;
+
+/// The scheme of monikers for symbols that are visible outside of their declaring scope.
+pub const SWAY_SCHEME: &str = "sway";
+/// The scheme of monikers for symbols that are only visible in their declaring scope.
+pub const LOCAL_SCHEME: &str = "local";
+
+pub fn monikers(session: Arc<Session>, url: &Url, position: Position) -> Option<Vec<Moniker>> {
+    let (_, token) = session.token_map().token_at_position(url, position)?;
+    let decl_ident = match &token.typed {
+        // The type definition of an enum variant is the type of its value, not the variant.
+        Some(TypedAstToken::TypedEnumVariant(variant)) => {
+            TokenIdent::new(&variant.name, session.engines.read().se())
+        }
+        _ => token.declared_token_ident(&session.engines.read())?,
+    };
+    let moniker = match token.kind {
+        SymbolKind::Variable
+        | SymbolKind::ValueParam
+        | SymbolKind::TypeParameter
+        | SymbolKind::SelfKeyword
+        | SymbolKind::SelfTypeKeyword => Moniker {
+            scheme: LOCAL_SCHEME.to_string(),
+            identifier: format!(
+                "{}:{}:{}",
+                decl_ident.name, decl_ident.range.start.line, decl_ident.range.start.character
+            ),
+            unique: UniquenessLevel::Document,
+            kind: Some(MonikerKind::Local),
+        },
+        SymbolKind::Const
+        | SymbolKind::Enum
+        | SymbolKind::Field
+        | SymbolKind::Function
+        | SymbolKind::Module
+        | SymbolKind::Struct
+        | SymbolKind::Trait
+        | SymbolKind::TraitType
+        | SymbolKind::TypeAlias
+        | SymbolKind::Variant => {
+            let path = decl_ident.path.as_ref()?;
+            let decl_url = Url::from_file_path(path).ok()?;
+            let kind = if session.sync.is_path_in_temp_workspace(&decl_url) {
+                MonikerKind::Export
+            } else {
+                MonikerKind::Import
+            };
+            let mut segments = package_module_path(path)?;
+            match token.kind {
+                SymbolKind::Field | SymbolKind::Variant => {
+                    segments.push(parent_name(&session, &decl_url, &decl_ident)?);
+                }
+                SymbolKind::Function => {
+                    segments.extend(method_qualifier(&session, &decl_url, &decl_ident));
+                }
+                _ => {}
+            }
+            segments.push(decl_ident.name.clone());
+            Moniker {
+                scheme: SWAY_SCHEME.to_string(),
+                identifier: segments.join("::"),
+                unique: UniquenessLevel::Scheme,
+                kind: Some(kind),
+            }
+        }
+        _ => return None,
+    };
+    Some(vec![moniker])
+}
+
+/// Returns the package name followed by the module path of the given sway file.
+pub(crate) fn package_module_path(path: &Path) -> Option<Vec<String>> {
+    let manifest_dir = sway_utils::find_parent_manifest_dir(path)?;
+    let manifest = PackageManifestFile::from_dir(&manifest_dir).ok()?;
+    let module_path = path
+        .strip_prefix(manifest_dir.join(sway_utils::constants::SRC_DIR))
+        .ok()?
+        .with_extension("");
+    let mut segments = vec![manifest.project_name().to_string()];
+    if module_path != Path::new(&manifest.project.entry).with_extension("") {
+        segments.extend(
+            module_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().to_string()),
+        );
+    }
+    Some(segments)
+}
+
+/// Returns the name of the declaration that encloses the struct field or enum variant.
+fn parent_name(session: &Session, url: &Url, field_ident: &TokenIdent) -> Option<String> {
+    let engines = session.engines.read();
+    session
+        .token_map()
+        .parent_decl_at_position(engines.se(), url, field_ident.range.start)
+        .map(|(ident, _)| ident.name)
+}
+
+/// Returns the qualifier of a method: the type it's implemented for, along with the trait for
+/// trait implementations, or the trait or ABI that declares it. Returns `None` for free functions.
+fn method_qualifier(session: &Session, url: &Url, fn_ident: &TokenIdent) -> Option<String> {
+    let engines = session.engines.read();
+    session
+        .token_map()
+        .tokens_at_position(engines.se(), url, fn_ident.range.start, None)
+        .into_iter()
+        .find_map(|(_, token)| match token.typed? {
+            TypedAstToken::TypedDeclaration(ty::TyDecl::ImplTrait(ty::ImplTrait {
+                decl_id,
+                ..
+            })) => {
+                let impl_trait = engines.de().get_impl_trait(&decl_id);
+                let implementing_for = &impl_trait.implementing_for;
+                let type_name = match &implementing_for.call_path_tree {
+                    Some(tree) => tree.qualified_call_path.call_path.suffix.to_string(),
+                    None => engines.help_out(implementing_for.type_id).to_string(),
+                };
+                Some(match impl_trait.trait_decl_ref {
+                    Some(_) => format!("<{type_name} as {}>", impl_trait.trait_name.suffix),
+                    None => type_name,
+                })
+            }
+            TypedAstToken::TypedDeclaration(
+                ty::TyDecl::TraitDecl(ty::TraitDecl { name, .. })
+                | ty::TyDecl::AbiDecl(ty::AbiDecl { name, .. }),
+            ) => Some(name.to_string()),
+            _ => None,
+        })
+}
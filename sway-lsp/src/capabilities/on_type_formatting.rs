@@ -0,0 +1,209 @@
+use crate::core::session::Session;
+use lsp_types::{
+    DocumentOnTypeFormattingParams, FormattingOptions, Position, Range, TextEdit, Url,
+};
+use std::sync::Arc;
+
+/// The characters that trigger `textDocument/onTypeFormatting`, as declared in the server capabilities.
+pub const FIRST_TRIGGER_CHARACTER: &str = "}";
+/// The additional trigger characters, besides [FIRST_TRIGGER_CHARACTER].
+pub const MORE_TRIGGER_CHARACTERS: [&str; 2] = [";", "\n"];
+
+/// Fixes the indentation of the line being edited after a `}`, `;` or newline is typed, without
+/// running the full formatter.
+///
+/// The indentation is derived from the nesting of brackets before the line. If a newline is typed
+/// between a pair of brackets, the closing bracket is moved down to its own line as well.
+pub fn on_type_formatting(
+    session: &Arc<Session>,
+    temp_uri: &Url,
+    params: &DocumentOnTypeFormattingParams,
+) -> Option<Vec<TextEdit>> {
+    let text_document = session.get_text_document(temp_uri).ok()?;
+    indentation_edits(
+        &text_document.get_text(),
+        params.text_document_position.position,
+        &params.ch,
+        &params.options,
+    )
+}
+
+fn indentation_edits(
+    text: &str,
+    position: Position,
+    ch: &str,
+    options: &FormattingOptions,
+) -> Option<Vec<TextEdit>> {
+    let lines: Vec<&str> = text.lines().collect();
+    let line_idx = position.line as usize;
+    let line = lines.get(line_idx).copied().unwrap_or_default();
+    let trimmed = line.trim_start();
+    let starts_with_closer = trimmed.starts_with(['}', ')', ']']);
+    let previous_line = lines[..line_idx.min(lines.len())]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty());
+
+    match ch {
+        "}" if !trimmed.starts_with('}') => return None,
+        // Only reindent the first line of a statement, not the continuation lines of one.
+        ";" if matches!(previous_line, Some(line) if !line.ends_with(['{', '}', ';', '(', '[', ','])) => {
+            return None
+        }
+        "}" | ";" | "\n" => {}
+        _ => return None,
+    }
+
+    let preceding_text = lines[..line_idx.min(lines.len())].join("\n");
+    let mut depth = bracket_depth(&preceding_text);
+    let opens_block = matches!(previous_line, Some(line) if line.ends_with(['{', '(', '[']));
+    if starts_with_closer && !(ch == "\n" && opens_block) {
+        depth = depth.saturating_sub(1);
+    }
+
+    let indent_unit = if options.insert_spaces {
+        " ".repeat(options.tab_size as usize)
+    } else {
+        "\t".to_string()
+    };
+    let indent = indent_unit.repeat(depth);
+    let current_indent = &line[..line.len() - trimmed.len()];
+
+    // Typing a newline between a pair of brackets moves the closing bracket to its own line.
+    let new_text = if ch == "\n" && starts_with_closer && opens_block {
+        format!("{indent}\n{}", indent_unit.repeat(depth.saturating_sub(1)))
+    } else if current_indent == indent {
+        return None;
+    } else {
+        indent
+    };
+
+    Some(vec![TextEdit {
+        range: Range::new(
+            Position::new(position.line, 0),
+            Position::new(position.line, current_indent.len() as u32),
+        ),
+        new_text,
+    }])
+}
+
+/// Returns how deeply nested the end of `text` is in brackets, ignoring those in comments and
+/// string literals.
+fn bracket_depth(text: &str) -> usize {
+    let mut depth = 0usize;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '"' => {
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    match c {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> FormattingOptions {
+        FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            ..Default::default()
+        }
+    }
+
+    fn edit(line: u32, end: u32, new_text: &str) -> Option<Vec<TextEdit>> {
+        Some(vec![TextEdit {
+            range: Range::new(Position::new(line, 0), Position::new(line, end)),
+            new_text: new_text.to_string(),
+        }])
+    }
+
+    #[test]
+    fn newline_indents_block() {
+        let text = "fn main() {\n\n}\n";
+        assert_eq!(
+            indentation_edits(text, Position::new(1, 0), "\n", &options()),
+            edit(1, 0, "    ")
+        );
+
+        // Pressing enter between braces moves the closing brace to its own line.
+        let text = "fn main() {\n}\n";
+        assert_eq!(
+            indentation_edits(text, Position::new(1, 0), "\n", &options()),
+            edit(1, 0, "    \n")
+        );
+    }
+
+    #[test]
+    fn closing_brace_is_dedented() {
+        let text = "fn main() {\n    if true {\n        let a = 1;\n        }\n}\n";
+        assert_eq!(
+            indentation_edits(text, Position::new(3, 9), "}", &options()),
+            edit(3, 8, "    ")
+        );
+        // Nothing to do if the indentation is already right.
+        let text = "fn main() {\n    let a = \"{\"; // {\n}\n";
+        assert_eq!(
+            indentation_edits(text, Position::new(2, 1), "}", &options()),
+            None
+        );
+    }
+
+    #[test]
+    fn semicolon_normalizes_statement_indentation() {
+        let text = "fn main() {\n    let a = 1;\n          let b = 2;\n}\n";
+        assert_eq!(
+            indentation_edits(text, Position::new(2, 20), ";", &options()),
+            edit(2, 10, "    ")
+        );
+
+        // Continuation lines of a statement are left alone.
+        let text = "fn main() {\n    let a = foo()\n        .bar();\n}\n";
+        assert_eq!(
+            indentation_edits(text, Position::new(2, 15), ";", &options()),
+            None
+        );
+
+        let tabs = FormattingOptions {
+            insert_spaces: false,
+            ..options()
+        };
+        let text = "fn main() {\nlet a = 1;\n}\n";
+        assert_eq!(
+            indentation_edits(text, Position::new(1, 10), ";", &tabs),
+            edit(1, 0, "\t")
+        );
+    }
+}
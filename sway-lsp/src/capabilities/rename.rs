@@ -10,7 +10,7 @@ use crate::{
 use lsp_types::{Position, PrepareRenameResponse, TextEdit, Url, WorkspaceEdit};
 use std::{collections::HashMap, sync::Arc};
 use sway_core::{language::ty, Engines};
-use sway_types::SourceEngine;
+use sway_types::{SourceEngine, Spanned};
 
 const RAW_IDENTIFIER: &str = "r#";
 
@@ -52,7 +52,7 @@ pub fn rename(
 
     // If the token is a function, find the parent declaration
     // and collect idents for all methods of ABI Decl, Trait Decl, and Impl Trait
-    let map_of_changes: HashMap<Url, Vec<TextEdit>> = (if token.kind == SymbolKind::Function {
+    let mut idents = if token.kind == SymbolKind::Function {
         find_all_methods_for_decl(&session, &engines, &url, position)?
     } else {
         // otherwise, just find all references of the token in the token map
@@ -62,40 +62,69 @@ pub fn rename(
             .all_references_of_token(&token, &engines)
             .map(|(ident, _)| ident)
             .collect::<Vec<TokenIdent>>()
-    })
-    .into_iter()
-    .filter(|ident| {
-        // we want to rename the type that self refers to, not the self ident itself
-        ident.name != "self"
-    })
-    .filter_map(|ident| {
-        let mut range = ident.range;
-        if ident.is_raw_ident() {
-            // Make sure the start char starts at the begining,
-            // taking the r# tokens into account.
-            range.start.character -= RAW_IDENTIFIER.len() as u32;
+    };
+
+    // A shorthand field initializer, e.g. the `x` of `Point { x }`, refers to both a field and a
+    // variable, so renaming either of them has to spell out the other one.
+    let shorthand_fields = shorthand_struct_fields(&session, &engines);
+    let mut shorthand_edits = HashMap::new();
+    if token.kind == SymbolKind::Field {
+        let field_decl = token.declared_token_ident(&engines);
+        for (ident, field) in shorthand_fields {
+            if Some(&field) == field_decl.as_ref() {
+                let new_text = format!("{new_name}: {}", formatted_name(&ident));
+                idents.retain(|existing| existing != &ident);
+                idents.push(ident.clone());
+                shorthand_edits.insert(ident, new_text);
+            }
         }
-        if let Some(path) = ident.path {
-            let url = get_url_from_path(&path).ok()?;
-            if let Some(url) = session.sync.to_workspace_url(url) {
-                let edit = TextEdit::new(range, new_name.clone());
-                return Some((url, vec![edit]));
-            };
+    } else {
+        for (ident, _) in shorthand_fields {
+            if idents.contains(&ident) {
+                let new_text = format!("{}: {new_name}", formatted_name(&ident));
+                shorthand_edits.insert(ident, new_text);
+            }
         }
+    }
 
-        None
-    })
-    .fold(HashMap::new(), |mut map, (k, mut v)| {
-        map.entry(k)
-            .and_modify(|existing| {
-                existing.append(&mut v);
-                // Sort the TextEdits by their range in reverse order so the client applies edits
-                // from the end of the document to the beginning, preventing issues with offset changes.
-                existing.sort_unstable_by(|a, b| b.range.start.cmp(&a.range.start))
-            })
-            .or_insert(v);
-        map
-    });
+    let map_of_changes: HashMap<Url, Vec<TextEdit>> = idents
+        .into_iter()
+        .filter(|ident| {
+            // we want to rename the type that self refers to, not the self ident itself
+            ident.name != "self"
+        })
+        .filter_map(|ident| {
+            let new_text = shorthand_edits
+                .get(&ident)
+                .cloned()
+                .unwrap_or_else(|| new_name.clone());
+            let mut range = ident.range;
+            if ident.is_raw_ident() {
+                // Make sure the start char starts at the begining,
+                // taking the r# tokens into account.
+                range.start.character -= RAW_IDENTIFIER.len() as u32;
+            }
+            if let Some(path) = ident.path {
+                let url = get_url_from_path(&path).ok()?;
+                if let Some(url) = session.sync.to_workspace_url(url) {
+                    let edit = TextEdit::new(range, new_text);
+                    return Some((url, vec![edit]));
+                };
+            }
+
+            None
+        })
+        .fold(HashMap::new(), |mut map, (k, mut v)| {
+            map.entry(k)
+                .and_modify(|existing| {
+                    existing.append(&mut v);
+                    // Sort the TextEdits by their range in reverse order so the client applies edits
+                    // from the end of the document to the beginning, preventing issues with offset changes.
+                    existing.sort_unstable_by(|a, b| b.range.start.cmp(&a.range.start))
+                })
+                .or_insert(v);
+            map
+        });
     Ok(WorkspaceEdit::new(map_of_changes))
 }
 
@@ -137,6 +166,49 @@ pub fn prepare_rename(
     })
 }
 
+/// Returns the shorthand field initializers of all struct expressions, e.g. the `x` of
+/// `Point { x }`, along with the declaration of the field each of them initializes.
+fn shorthand_struct_fields(session: &Session, engines: &Engines) -> Vec<(TokenIdent, TokenIdent)> {
+    let token_map = session.token_map();
+    // Collect the expressions first, so that the token map isn't read while it's being iterated.
+    let struct_expressions: Vec<_> = token_map
+        .iter()
+        .filter_map(|(_, token)| match token.typed {
+            Some(TypedAstToken::TypedExpression(ty::TyExpression {
+                expression: ty::TyExpressionVariant::StructExpression { fields, .. },
+                return_type,
+                ..
+            })) => Some((fields, return_type)),
+            _ => None,
+        })
+        .collect();
+    struct_expressions
+        .into_iter()
+        .flat_map(|(fields, return_type)| {
+            let struct_decl = token_map.struct_declaration_of_type_id(engines, &return_type);
+            fields.into_iter().filter_map(move |field| {
+                let is_shorthand = matches!(
+                    &field.value.expression,
+                    ty::TyExpressionVariant::VariableExpression { span, .. }
+                        if *span == field.name.span()
+                );
+                if !is_shorthand {
+                    return None;
+                }
+                let decl_field = struct_decl
+                    .as_ref()?
+                    .fields
+                    .iter()
+                    .find(|decl_field| decl_field.name == field.name)?;
+                Some((
+                    TokenIdent::new(&field.name, engines.se()),
+                    TokenIdent::new(&decl_field.name, engines.se()),
+                ))
+            })
+        })
+        .collect()
+}
+
 /// Returns the name of the identifier, prefixed with r# if the identifier is raw.
 fn formatted_name(ident: &TokenIdent) -> String {
     let name = ident.name.to_string();
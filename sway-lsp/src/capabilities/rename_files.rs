@@ -0,0 +1,299 @@
+use crate::{
+    core::token::get_range_from_span,
+    utils::document::{get_path_from_url, get_url_from_path},
+};
+use forc_pkg::PackageManifestFile;
+use lsp_types::{FileRename, RenameFilesParams, TextEdit, Url, WorkspaceEdit};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use sway_ast::{ItemKind, UseTree};
+use sway_error::handler::Handler;
+use sway_types::{Ident, Spanned};
+
+/// The rename of a single module of a package.
+#[derive(Debug)]
+struct ModuleRename {
+    /// The `src` directory of the package.
+    src_dir: PathBuf,
+    /// The entry file of the package, i.e. `main.sw` or `lib.sw`.
+    entry: PathBuf,
+    /// The path of the module that declares the renamed module.
+    parent: Vec<String>,
+    old_name: String,
+    new_name: String,
+}
+
+impl ModuleRename {
+    fn old_path(&self) -> Vec<String> {
+        let mut path = self.parent.clone();
+        path.push(self.old_name.clone());
+        path
+    }
+}
+
+/// Computes the edits that keep a package compiling when its `.sw` files are renamed, i.e. the
+/// `mod` declarations of the renamed modules and the `use` paths that refer to them.
+///
+/// Only renames of a module within its directory are supported. Returns `None`, and logs why, if
+/// any of the renames would break paths that can't be rewritten, e.g. because the file is moved to
+/// another directory or package.
+pub fn will_rename_files(params: &RenameFilesParams) -> Option<WorkspaceEdit> {
+    let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    for file in &params.files {
+        let rename = match module_rename(file) {
+            Ok(Some(rename)) => rename,
+            Ok(None) => continue,
+            Err(reason) => {
+                tracing::info!(
+                    "Not updating imports for the rename of {} to {}: {}",
+                    file.old_uri,
+                    file.new_uri,
+                    reason
+                );
+                return None;
+            }
+        };
+        for path in sway_utils::get_sway_files(rename.src_dir.clone()) {
+            let Ok(src) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let edits = module_edits(src.into(), &module_path(&rename, &path), &rename);
+            if edits.is_empty() {
+                continue;
+            }
+            // Edits to the renamed file itself are applied to it before the rename.
+            if let Ok(url) = get_url_from_path(&path) {
+                changes.entry(url).or_default().extend(edits);
+            }
+        }
+    }
+    if changes.is_empty() {
+        return None;
+    }
+    Some(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    })
+}
+
+/// Returns the module rename described by `file`, `None` if it isn't a rename of a Sway file, or
+/// the reason why its imports can't be updated.
+fn module_rename(file: &FileRename) -> Result<Option<ModuleRename>, String> {
+    let (Ok(old_url), Ok(new_url)) = (Url::parse(&file.old_uri), Url::parse(&file.new_uri)) else {
+        return Err("invalid file URI".to_string());
+    };
+    let (Ok(old_path), Ok(new_path)) = (get_path_from_url(&old_url), get_path_from_url(&new_url))
+    else {
+        return Err("not a file URI".to_string());
+    };
+    match (
+        sway_utils::is_sway_file(&old_path),
+        sway_utils::is_sway_file(&new_path),
+    ) {
+        (true, true) => {}
+        (false, false) => return Ok(None),
+        _ => return Err("the file extension changes".to_string()),
+    }
+
+    let manifest_dir = sway_utils::find_parent_manifest_dir(&old_path)
+        .ok_or_else(|| "the file isn't in a package".to_string())?;
+    if sway_utils::find_parent_manifest_dir(&new_path).as_ref() != Some(&manifest_dir) {
+        return Err("the file is moved to another package".to_string());
+    }
+    let manifest = PackageManifestFile::from_dir(&manifest_dir).map_err(|err| err.to_string())?;
+    let src_dir = manifest_dir.join(sway_utils::constants::SRC_DIR);
+    let entry = src_dir.join(&manifest.project.entry);
+    if old_path == entry {
+        return Err("the entry file of the package can't be renamed".to_string());
+    }
+
+    let (Ok(old_module), Ok(new_module)) = (
+        old_path
+            .with_extension("")
+            .strip_prefix(&src_dir)
+            .map(segments),
+        new_path
+            .with_extension("")
+            .strip_prefix(&src_dir)
+            .map(segments),
+    ) else {
+        return Err(format!("the file isn't in {}", src_dir.display()));
+    };
+    let (Some((old_name, old_parent)), Some((new_name, new_parent))) =
+        (old_module.split_last(), new_module.split_last())
+    else {
+        return Err("the file isn't a module".to_string());
+    };
+    if old_parent != new_parent {
+        return Err("the module is moved to another directory".to_string());
+    }
+    if !is_valid_module_name(new_name) {
+        return Err(format!("`{new_name}` isn't a valid module name"));
+    }
+    if old_path.with_extension("").is_dir() {
+        return Err(format!(
+            "the submodules in {} would also have to be moved",
+            old_path.with_extension("").display()
+        ));
+    }
+
+    Ok(Some(ModuleRename {
+        src_dir,
+        entry,
+        parent: old_parent.to_vec(),
+        old_name: old_name.clone(),
+        new_name: new_name.clone(),
+    }))
+}
+
+fn segments(path: &Path) -> Vec<String> {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect()
+}
+
+fn is_valid_module_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !sway_parse::RESERVED_KEYWORDS.contains(name)
+}
+
+/// Returns the path of the module defined by the file at `path`.
+fn module_path(rename: &ModuleRename, path: &Path) -> Vec<String> {
+    if path == rename.entry {
+        return vec![];
+    }
+    path.with_extension("")
+        .strip_prefix(&rename.src_dir)
+        .map(segments)
+        .unwrap_or_default()
+}
+
+/// Returns the edits to the source of `module` that `rename` requires.
+fn module_edits(src: Arc<str>, module: &[String], rename: &ModuleRename) -> Vec<TextEdit> {
+    let handler = Handler::default();
+    let Ok(parsed) = sway_parse::parse_file(&handler, src, None) else {
+        return vec![];
+    };
+    let mut edits = vec![];
+    for item in &parsed.value.items {
+        match &item.value {
+            ItemKind::Submodule(submodule)
+                if module == rename.parent && submodule.name.as_str() == rename.old_name =>
+            {
+                edits.push(edit(&submodule.name, rename.new_name.clone()));
+            }
+            ItemKind::Use(item_use) => {
+                // Paths that don't start with `::` may also be relative to the current module.
+                let mut roots = vec![vec![]];
+                if item_use.root_import.is_none() && !module.is_empty() {
+                    roots.push(module.to_vec());
+                }
+                for root in roots {
+                    use_tree_edits(&item_use.tree, root, rename, &mut edits);
+                }
+            }
+            _ => {}
+        }
+    }
+    edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+    edits.dedup_by_key(|edit| edit.range);
+    edits
+}
+
+fn use_tree_edits(
+    tree: &UseTree,
+    mut path: Vec<String>,
+    rename: &ModuleRename,
+    edits: &mut Vec<TextEdit>,
+) {
+    let old_path = rename.old_path();
+    match tree {
+        UseTree::Path { prefix, suffix, .. } => {
+            path.push(prefix.as_str().to_string());
+            if path == old_path {
+                edits.push(edit(prefix, rename.new_name.clone()));
+            }
+            use_tree_edits(suffix, path, rename, edits);
+        }
+        UseTree::Group { imports } => {
+            for import in imports.get() {
+                use_tree_edits(import, path.clone(), rename, edits);
+            }
+        }
+        UseTree::Name { name } => {
+            path.push(name.as_str().to_string());
+            if path == old_path {
+                // Keep the module bound to its old name, so that the paths using it still resolve.
+                edits.push(edit(name, format!("{} as {}", rename.new_name, name)));
+            }
+        }
+        UseTree::Rename { name, .. } => {
+            path.push(name.as_str().to_string());
+            if path == old_path {
+                edits.push(edit(name, rename.new_name.clone()));
+            }
+        }
+        UseTree::Glob { .. } | UseTree::Error { .. } => {}
+    }
+}
+
+fn edit(ident: &Ident, new_text: String) -> TextEdit {
+    TextEdit {
+        range: get_range_from_span(&ident.span()),
+        new_text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename(parent: &[&str], old_name: &str, new_name: &str) -> ModuleRename {
+        ModuleRename {
+            src_dir: PathBuf::from("/pkg/src"),
+            entry: PathBuf::from("/pkg/src/main.sw"),
+            parent: parent.iter().map(|s| s.to_string()).collect(),
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
+        }
+    }
+
+    fn apply(src: &str, module: &[&str], rename: &ModuleRename) -> String {
+        let module: Vec<_> = module.iter().map(|s| s.to_string()).collect();
+        let edits = module_edits(src.into(), &module, rename);
+        let mut lines: Vec<String> = src.lines().map(str::to_string).collect();
+        for edit in edits.iter().rev() {
+            let line = &mut lines[edit.range.start.line as usize];
+            line.replace_range(
+                edit.range.start.character as usize..edit.range.end.character as usize,
+                &edit.new_text,
+            );
+        }
+        lines.join("\n")
+    }
+
+    #[test]
+    fn rename_module_updates_imports() {
+        let rename = rename(&["utils"], "math", "arith");
+        let src = "library;\n\nuse ::utils::math::add;\nuse utils::{math::{sub, mul}, io};\nuse ::utils::math;\nuse ::utils::math as m;\nuse ::utils::math::*;\nuse ::other::math::add;";
+        assert_eq!(
+            apply(src, &["foo"], &rename),
+            "library;\n\nuse ::utils::arith::add;\nuse utils::{arith::{sub, mul}, io};\nuse ::utils::arith as math;\nuse ::utils::arith as m;\nuse ::utils::arith::*;\nuse ::other::math::add;"
+        );
+
+        // Paths relative to the module declaring the renamed module, and its `mod` declaration.
+        let src = "library;\n\nmod math;\nmod io;\n\nuse math::add;";
+        assert_eq!(
+            apply(src, &["utils"], &rename),
+            "library;\n\nmod arith;\nmod io;\n\nuse arith::add;"
+        );
+        assert_eq!(apply(src, &[], &rename), src);
+    }
+}
@@ -0,0 +1,90 @@
+use super::moniker::package_module_path;
+use crate::lsp_ext::{RunTestResult, TestStatus};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+/// Builds the package of the file at `path` with its tests, as `forc test` does, and runs the test
+/// whose fully-qualified name is `test_name`.
+///
+/// Tests are qualified like their monikers, by the package name and the module path of their file,
+/// e.g. `my_pkg::utils::test_add` for `test_add` in `src/utils.sw`. Tests in the entry file of the
+/// package are only qualified by the package name, e.g. `my_pkg::test_add`.
+pub fn run_test(path: &Path, test_name: &str) -> RunTestResult {
+    let opts = forc_test::Opts {
+        pkg: forc_pkg::PkgOpts {
+            path: Some(path.to_string_lossy().into_owned()),
+            terse: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    // Catch compiler panics, as when compiling the project for the other features.
+    let built_tests = match panic::catch_unwind(AssertUnwindSafe(|| forc_test::build(opts))) {
+        Ok(Ok(built_tests)) => built_tests,
+        Ok(Err(err)) => return RunTestResult::error(TestStatus::CompileError, err.to_string()),
+        Err(_) => {
+            return RunTestResult::error(
+                TestStatus::CompileError,
+                "The Sway compiler crashed while compiling the tests.".to_string(),
+            )
+        }
+    };
+
+    let fn_name = test_name.rsplit("::").next().unwrap_or(test_name);
+    let test_filter = forc_test::TestFilter {
+        filter_phrase: fn_name,
+        exact_match: true,
+    };
+    let tested = match built_tests.run(forc_test::TestRunnerCount::Auto, Some(test_filter)) {
+        Ok(tested) => tested,
+        Err(err) => return RunTestResult::error(TestStatus::Failed, err.to_string()),
+    };
+    let tests: Vec<_> = match tested {
+        forc_test::Tested::Package(tested_pkg) => tested_pkg.tests,
+        forc_test::Tested::Workspace(tested_pkgs) => tested_pkgs
+            .into_iter()
+            .flat_map(|tested_pkg| tested_pkg.tests)
+            .collect(),
+    };
+    let Some(test) = tests
+        .into_iter()
+        .find(|test| qualified_test_name(test).as_deref() == Some(test_name))
+    else {
+        return RunTestResult::error(
+            TestStatus::NotFound,
+            format!("No test named `{test_name}` was found."),
+        );
+    };
+
+    let passed = test.passed();
+    RunTestResult {
+        status: if passed {
+            TestStatus::Passed
+        } else {
+            TestStatus::Failed
+        },
+        // Words are formatted as decimal integers and data as hex strings.
+        logs: test
+            .logs
+            .iter()
+            .filter_map(|receipt| match receipt.data() {
+                Some(data) => Some(format!(
+                    "0x{}",
+                    data.iter().map(|b| format!("{b:02x}")).collect::<String>()
+                )),
+                None => receipt.ra().map(|ra| ra.to_string()),
+            })
+            .collect(),
+        gas_used: Some(test.gas_used),
+        error: (!passed).then(|| format!("The test ended in the state {:?}.", test.state)),
+    }
+}
+
+/// Returns the name of the test qualified by its package and module path.
+fn qualified_test_name(test: &forc_test::TestResult) -> Option<String> {
+    let mut segments = package_module_path(&test.file_path)?;
+    segments.push(test.name.clone());
+    Some(segments.join("::"))
+}
@@ -0,0 +1,98 @@
+use crate::{core::session::Session, utils::debug};
+use std::path::PathBuf;
+use sway_types::{Ident, Spanned};
+
+/// Returns the formatted "lexed", "parsed" or "typed" AST of the module at `path`.
+///
+/// The AST of the program root is returned if `path` is not one of its submodules.
+pub fn ast_to_string(session: &Session, path: &Option<PathBuf>, ast_kind: &str) -> Option<String> {
+    // Returns true if the current path matches the path of a submodule
+    let path_is_submodule = |ident: &Ident| -> bool {
+        let engines = session.engines.read();
+        ident.span().source_id().map(|p| engines.se().get_path(p)) == *path
+    };
+
+    let program = session.compiled_program.read();
+    match ast_kind {
+        "lexed" => program.lexed.as_ref().map(|lexed_program| {
+            let mut formatted_ast = format!("{:#?}", program.lexed);
+            for (ident, submodule) in &lexed_program.root.submodules {
+                if path_is_submodule(ident) {
+                    // overwrite the root AST with the submodule AST
+                    formatted_ast = format!("{:#?}", submodule.module.tree);
+                }
+            }
+            formatted_ast
+        }),
+        "parsed" => program.parsed.as_ref().map(|parsed_program| {
+            // Initialize the string with the AST from the root
+            let mut formatted_ast = format!("{:#?}", parsed_program.root.tree.root_nodes);
+            for (ident, submodule) in &parsed_program.root.submodules {
+                if path_is_submodule(ident) {
+                    // overwrite the root AST with the submodule AST
+                    formatted_ast = format!("{:#?}", submodule.module.tree.root_nodes);
+                }
+            }
+            formatted_ast
+        }),
+        "typed" => program.typed.as_ref().map(|typed_program| {
+            // Initialize the string with the AST from the root
+            let mut formatted_ast = debug::print_decl_engine_types(
+                &typed_program.root.all_nodes,
+                session.engines.read().de(),
+            );
+            for (ident, submodule) in &typed_program.root.submodules {
+                if path_is_submodule(ident) {
+                    // overwrite the root AST with the submodule AST
+                    formatted_ast = debug::print_decl_engine_types(
+                        &submodule.module.all_nodes,
+                        session.engines.read().de(),
+                    );
+                }
+            }
+            formatted_ast
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the page of `ast` that starts at the byte `offset`, and the offset of the next page if
+/// there is one.
+///
+/// Pages are at most `page_size` bytes long, and end at a line break unless a single line is
+/// longer than that. Returns `None` if `offset` isn't the start of a page.
+pub fn ast_page(ast: &str, offset: usize, page_size: usize) -> Option<(&str, Option<usize>)> {
+    if offset > ast.len() || !ast.is_char_boundary(offset) {
+        return None;
+    }
+    let rest = &ast[offset..];
+    if rest.len() <= page_size {
+        return Some((rest, None));
+    }
+    let mut end = page_size.max(1);
+    while !rest.is_char_boundary(end) {
+        end += 1;
+    }
+    if let Some(line_end) = rest[..end].rfind('\n') {
+        end = line_end + 1;
+    }
+    Some((&rest[..end], Some(offset + end)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ast_pages() {
+        let ast = "Module {\n    items: [],\n}";
+        assert_eq!(ast_page(ast, 0, 100), Some((ast, None)));
+        assert_eq!(ast_page(ast, 0, 12), Some(("Module {\n", Some(9))));
+        assert_eq!(ast_page(ast, 9, 12), Some(("    items: [", Some(21))));
+        assert_eq!(ast_page(ast, 21, 12), Some(("],\n}", None)));
+        assert_eq!(ast_page(ast, 30, 12), None);
+        // Pages don't end in the middle of a character.
+        assert_eq!(ast_page("ééé", 0, 3), Some(("éé", Some(4))));
+        assert_eq!(ast_page("ééé", 1, 3), None);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{
+    core::session::Session,
+    lsp_ext::{SyntaxNode, SyntaxNodeKind},
+};
+use lsp_types::Url;
+use std::sync::Arc;
+use sway_ast::token::{CommentedTokenStream, CommentedTokenTree, CommentedTree};
+use sway_error::handler::Handler;
+use sway_types::Spanned;
+
+/// Returns the lossless syntax tree of the document at `uri`, as produced by the lexer.
+///
+/// Unlike the parsed and typed ASTs, the tree also has nodes for comments, whitespace and
+/// delimiters, so concatenating the text of its leaves gives back the exact source. Returns `None`
+/// if the document can't be lexed, e.g. because of an unclosed delimiter.
+pub fn syntax_tree(session: Arc<Session>, uri: &Url) -> Option<SyntaxNode> {
+    let text: Arc<str> = session.get_text_document(uri).ok()?.get_text().into();
+    let handler = Handler::default();
+    let stream = sway_parse::lex_commented(&handler, &text, 0, text.len(), &None).ok()?;
+    if handler.has_errors() {
+        return None;
+    }
+    Some(SyntaxNode {
+        kind: SyntaxNodeKind::Root,
+        start: 0,
+        end: text.len(),
+        text: None,
+        children: children(&text, &stream, 0, text.len()),
+    })
+}
+
+/// Converts the token trees of `stream` into nodes, filling the gaps between them in
+/// `start..end` with whitespace nodes.
+fn children(src: &str, stream: &CommentedTokenStream, start: usize, end: usize) -> Vec<SyntaxNode> {
+    let mut nodes = vec![];
+    let mut pos = start;
+    for tree in &stream.token_trees {
+        let node = node(src, tree);
+        push_whitespace(src, &mut nodes, pos, node.start);
+        pos = node.end;
+        nodes.push(node);
+    }
+    push_whitespace(src, &mut nodes, pos, end);
+    nodes
+}
+
+fn node(src: &str, tree: &CommentedTokenTree) -> SyntaxNode {
+    let (kind, span) = match tree {
+        CommentedTokenTree::Comment(comment) => (SyntaxNodeKind::Comment, comment.span()),
+        CommentedTokenTree::Tree(CommentedTree::Punct(punct)) => {
+            (SyntaxNodeKind::Punct, punct.span())
+        }
+        CommentedTokenTree::Tree(CommentedTree::Ident(ident)) => {
+            (SyntaxNodeKind::Ident, ident.span())
+        }
+        CommentedTokenTree::Tree(CommentedTree::Literal(literal)) => {
+            (SyntaxNodeKind::Literal, literal.span())
+        }
+        CommentedTokenTree::Tree(CommentedTree::DocComment(doc_comment)) => {
+            (SyntaxNodeKind::DocComment, doc_comment.span())
+        }
+        CommentedTokenTree::Tree(CommentedTree::Group(group)) => {
+            // The span of a group includes its delimiters, which are a single character each.
+            let (start, end) = (group.span.start(), group.span.end());
+            let mut children = vec![leaf(src, SyntaxNodeKind::OpenDelimiter, start, start + 1)];
+            children.extend(self::children(src, &group.token_stream, start + 1, end - 1));
+            children.push(leaf(src, SyntaxNodeKind::CloseDelimiter, end - 1, end));
+            return SyntaxNode {
+                kind: SyntaxNodeKind::Group,
+                start,
+                end,
+                text: None,
+                children,
+            };
+        }
+    };
+    leaf(src, kind, span.start(), span.end())
+}
+
+fn push_whitespace(src: &str, nodes: &mut Vec<SyntaxNode>, start: usize, end: usize) {
+    if start < end {
+        nodes.push(leaf(src, SyntaxNodeKind::Whitespace, start, end));
+    }
+}
+
+fn leaf(src: &str, kind: SyntaxNodeKind, start: usize, end: usize) -> SyntaxNode {
+    SyntaxNode {
+        kind,
+        start,
+        end,
+        text: Some(src[start..end].to_string()),
+        children: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex(src: &str) -> SyntaxNode {
+        let handler = Handler::default();
+        let src: Arc<str> = src.into();
+        let stream = sway_parse::lex_commented(&handler, &src, 0, src.len(), &None).unwrap();
+        SyntaxNode {
+            kind: SyntaxNodeKind::Root,
+            start: 0,
+            end: src.len(),
+            text: None,
+            children: children(&src, &stream, 0, src.len()),
+        }
+    }
+
+    fn source_text(node: &SyntaxNode) -> String {
+        match &node.text {
+            Some(text) => text.clone(),
+            None => node.children.iter().map(source_text).collect(),
+        }
+    }
+
+    #[test]
+    fn syntax_tree_is_lossless() {
+        let src =
+            "script;\n\n// main\nfn main() -> u64 {\n    let a = [1, 2]; /* b */\n    a[0]\n}\n";
+        let tree = lex(src);
+        assert_eq!(source_text(&tree), src);
+
+        let kinds: Vec<_> = tree.children.iter().map(|node| node.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SyntaxNodeKind::Ident,
+                SyntaxNodeKind::Punct,
+                SyntaxNodeKind::Whitespace,
+                SyntaxNodeKind::Comment,
+                SyntaxNodeKind::Whitespace,
+                SyntaxNodeKind::Ident,
+                SyntaxNodeKind::Whitespace,
+                SyntaxNodeKind::Ident,
+                SyntaxNodeKind::Group,
+                SyntaxNodeKind::Whitespace,
+                SyntaxNodeKind::Punct,
+                SyntaxNodeKind::Punct,
+                SyntaxNodeKind::Whitespace,
+                SyntaxNodeKind::Ident,
+                SyntaxNodeKind::Whitespace,
+                SyntaxNodeKind::Group,
+                SyntaxNodeKind::Whitespace,
+            ]
+        );
+        let body = &tree.children[15];
+        assert_eq!(body.children[0].text.as_deref(), Some("{"));
+        assert_eq!(body.children.last().unwrap().text.as_deref(), Some("}"));
+
+        let json = serde_json::to_value(body).unwrap();
+        assert_eq!(json["kind"], "group");
+        assert_eq!(json["children"][0]["kind"], "openDelimiter");
+    }
+}
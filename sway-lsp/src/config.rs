@@ -14,6 +14,10 @@ pub struct Config {
     pub diagnostic: DiagnosticConfig,
     #[serde(default)]
     pub on_enter: OnEnterConfig,
+    #[serde(default)]
+    pub features: FeaturesConfig,
+    #[serde(default)]
+    pub build: BuildConfig,
     #[serde(default, skip_serializing)]
     trace: TraceConfig,
 }
@@ -42,6 +46,14 @@ impl Default for DebugConfig {
 pub struct DiagnosticConfig {
     pub show_warnings: bool,
     pub show_errors: bool,
+    /// How long to wait for further changes to a document before recompiling it and publishing
+    /// diagnostics, in milliseconds. Set to 0 to recompile on every change.
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+}
+
+fn default_debounce_ms() -> u64 {
+    100
 }
 
 impl Default for DiagnosticConfig {
@@ -49,6 +61,7 @@ impl Default for DiagnosticConfig {
         Self {
             show_warnings: true,
             show_errors: true,
+            debounce_ms: default_debounce_ms(),
         }
     }
 }
@@ -99,6 +112,14 @@ pub struct InlayHintsConfig {
     pub render_colons: bool,
     /// Whether to show inlay type hints for variables.
     pub type_hints: bool,
+    /// Whether to show the inferred type of integer literals without a type suffix, when it isn't
+    /// the default of `u64`.
+    #[serde(default)]
+    pub literal_hints: bool,
+    /// Whether to show the in-memory size of enum declarations and of the payload of each of
+    /// their variants.
+    #[serde(default)]
+    pub enum_size_hints: bool,
     /// Maximum length for inlay hints. Set to null to have an unlimited length.
     pub max_length: Option<usize>,
 }
@@ -108,6 +129,8 @@ impl Default for InlayHintsConfig {
         Self {
             render_colons: true,
             type_hints: true,
+            literal_hints: false,
+            enum_size_hints: false,
             max_length: Some(25),
         }
     }
@@ -130,6 +153,46 @@ impl Default for OnEnterConfig {
     }
 }
 
+// Options for turning off features, so that the server never does their work.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FeaturesConfig {
+    pub inlay_hints: bool,
+    pub semantic_tokens: bool,
+    pub code_lens: bool,
+    pub document_symbols: bool,
+}
+
+impl Default for FeaturesConfig {
+    fn default() -> Self {
+        Self {
+            inlay_hints: true,
+            semantic_tokens: true,
+            code_lens: true,
+            document_symbols: true,
+        }
+    }
+}
+
+// Options for how much of a project is compiled to answer requests.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BuildConfig {
+    pub mode: BuildMode,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildMode {
+    /// Type check the project and its dependencies, which all features need.
+    #[default]
+    Full,
+    /// Only parse the project, for huge projects that are too slow to type check. Only syntactic
+    /// features, like document symbols, semantic highlighting and keyword completion, work, and
+    /// requests that need type information return nothing.
+    Off,
+}
+
 impl<'de> serde::Deserialize<'de> for Warnings {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -23,14 +23,20 @@ use lsp_types::{
     TextDocumentContentChangeEvent, TextEdit, Url,
 };
 use parking_lot::RwLock;
-use pkg::{manifest::ManifestFile, BuildPlan};
+use pkg::{
+    manifest::{ManifestFile, PackageManifestFile},
+    BuildPlan,
+};
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use std::{
     fs::File,
     io::Write,
     ops::Deref,
     path::PathBuf,
-    sync::{atomic::Ordering, Arc},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     vec,
 };
 use sway_core::{
@@ -41,6 +47,7 @@ use sway_core::{
         ty::{self},
         HasSubmodules,
     },
+    namespace::Module,
     BuildTarget, Engines, Namespace, Programs,
 };
 use sway_error::{error::CompileError, handler::Handler, warning::CompileWarning};
@@ -66,7 +73,8 @@ pub struct ParseResult {
     pub(crate) token_map: TokenMap,
     pub(crate) lexed: LexedProgram,
     pub(crate) parsed: ParseProgram,
-    pub(crate) typed: ty::TyProgram,
+    /// `None` if the project was only parsed, because building is turned off.
+    pub(crate) typed: Option<ty::TyProgram>,
     pub(crate) metrics: DashMap<SourceId, PerformanceData>,
 }
 
@@ -88,6 +96,8 @@ pub struct Session {
     // Cached diagnostic results that require a lock to access. Readers will wait for writers to complete.
     pub diagnostics: Arc<RwLock<DiagnosticMap>>,
     pub metrics: DashMap<SourceId, PerformanceData>,
+    // The number of parses of changed documents that completed, used to schedule garbage collection.
+    pub completed_parses: AtomicUsize,
 }
 
 impl Default for Session {
@@ -108,6 +118,7 @@ impl Session {
             sync: SyncWorkspace::new(),
             parse_permits: Arc::new(Semaphore::new(2)),
             diagnostics: Arc::new(RwLock::new(DiagnosticMap::new())),
+            completed_parses: AtomicUsize::new(0),
         }
     }
 
@@ -174,14 +185,12 @@ impl Session {
             self.metrics.insert(*s, t.clone());
         });
 
-        self.create_runnables(
-            &res.typed,
-            self.engines.read().de(),
-            self.engines.read().se(),
-        );
+        if let Some(typed) = &res.typed {
+            self.create_runnables(typed, self.engines.read().de(), self.engines.read().se());
+        }
         self.compiled_program.write().lexed = Some(res.lexed);
         self.compiled_program.write().parsed = Some(res.parsed);
-        self.compiled_program.write().typed = Some(res.typed);
+        self.compiled_program.write().typed = res.typed;
     }
 
     pub fn token_ranges(&self, url: &Url, position: Position) -> Option<Vec<Range>> {
@@ -238,9 +247,11 @@ impl Session {
         let compiled_program = &*self.compiled_program.read();
         if let Some(TypedAstToken::TypedFunctionDeclaration(fn_decl)) = fn_token.typed.clone() {
             let program = compiled_program.typed.clone()?;
+            let workspace_uri = self.sync.to_workspace_url(uri.clone())?;
             return Some(capabilities::completion::to_completion_items(
                 &program.root.namespace,
                 &self.engines.read(),
+                &workspace_uri,
                 &ident_to_complete,
                 &fn_decl,
                 position,
@@ -557,11 +568,58 @@ pub fn parse_project(uri: &Url, engines: &Engines) -> Result<ParseResult, Langua
         token_map,
         lexed,
         parsed,
-        typed,
+        typed: Some(typed),
         metrics,
     })
 }
 
+/// Only parses the package at `uri`, without type checking it or even reading its dependencies,
+/// for when building is turned off. Only the tokens of the lexed and parsed programs are collected.
+pub fn parse_project_without_building(
+    uri: &Url,
+    engines: &Engines,
+) -> Result<ParseResult, LanguageServerError> {
+    let manifest_dir = PathBuf::from(uri.path());
+    let manifest = PackageManifestFile::from_dir(&manifest_dir).map_err(|_| {
+        DocumentError::ManifestFileNotFound {
+            dir: uri.path().into(),
+        }
+    })?;
+    let build_config = pkg::sway_build_config(
+        manifest.dir(),
+        &manifest.entry_path(),
+        BuildTarget::default(),
+        &pkg::BuildProfile::debug(),
+    )
+    .map_err(LanguageServerError::FailedToCompile)?;
+    let input = manifest
+        .entry_string()
+        .map_err(LanguageServerError::FailedToCompile)?;
+
+    let handler = Handler::default();
+    let programs = sway_core::parse(input, &handler, engines, Some(&build_config));
+    let diagnostics = handler.consume();
+    let (lexed, parsed) = programs.map_err(|_| LanguageServerError::FailedToParse)?;
+
+    let token_map = TokenMap::new();
+    // Nothing is resolved without type checking, so the namespace stays empty.
+    let namespace = Module::default();
+    let ctx = ParseContext::new(&token_map, engines, &namespace);
+    lexed_tree::parse(&lexed, &ctx);
+    let parsed_tree = ParsedTree::new(&ctx);
+    parsed_tree.collect_module_spans(&parsed);
+    parse_ast_to_tokens(&parsed, &ctx, |an, _ctx| parsed_tree.traverse_node(an));
+
+    Ok(ParseResult {
+        diagnostics,
+        token_map,
+        lexed,
+        parsed,
+        typed: None,
+        metrics: DashMap::new(),
+    })
+}
+
 /// Parse the [ParseProgram] AST to populate the [TokenMap] with parsed AST nodes.
 fn parse_ast_to_tokens(
     parse_program: &ParseProgram,
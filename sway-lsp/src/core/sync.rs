@@ -13,7 +13,11 @@ use std::{
     fs::{self, File},
     io::{Read, Write},
     path::{Path, PathBuf},
-    sync::{atomic::AtomicBool, mpsc, Arc},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
     thread::JoinHandle,
     time::Duration,
 };
@@ -196,6 +200,7 @@ impl SyncWorkspace {
                     edit_manifest_dependency_paths(&manifest, &temp_manifest_path);
 
                     let (tx, rx) = mpsc::channel();
+                    let should_end = self.should_end.clone();
                     let handle = std::thread::spawn(move || {
                         // Setup debouncer. No specific tickrate, max debounce time 2 seconds
                         let mut debouncer =
@@ -211,10 +216,17 @@ impl SyncWorkspace {
                             .watch(manifest_dir.as_ref().path(), RecursiveMode::NonRecursive)
                             .unwrap();
 
-                        while let Ok(_events) = rx.recv() {
-                            // Rescan the Forc.toml and convert
-                            // relative paths to absolute. Save into our temp directory.
-                            edit_manifest_dependency_paths(&manifest, &temp_manifest_path);
+                        // Poll the flag between events, so that the thread can be joined on shutdown.
+                        while !should_end.load(Ordering::Relaxed) {
+                            match rx.recv_timeout(Duration::from_millis(100)) {
+                                Ok(_events) => {
+                                    // Rescan the Forc.toml and convert
+                                    // relative paths to absolute. Save into our temp directory.
+                                    edit_manifest_dependency_paths(&manifest, &temp_manifest_path);
+                                }
+                                Err(RecvTimeoutError::Timeout) => {}
+                                Err(RecvTimeoutError::Disconnected) => break,
+                            }
                         }
                     });
 
@@ -1,4 +1,5 @@
 use lsp_types::{Position, Range};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use sway_ast::Intrinsic;
 use sway_core::{
@@ -188,7 +189,7 @@ impl Token {
 ///
 /// [TokenMap]: crate::core::token_map::TokenMap
 /// [SourceId]: sway_types::SourceId
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TokenIdent {
     pub name: String,
     pub range: Range,
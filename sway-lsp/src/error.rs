@@ -18,10 +18,16 @@ pub enum LanguageServerError {
     FailedToCompile(anyhow::Error),
     #[error("Failed to parse document")]
     FailedToParse,
+    #[error("The compiler panicked: {0}")]
+    CompilerPanicked(String),
     #[error("Error formatting document: {0}")]
     FormatError(FormatterError),
     #[error("Unable to acquire a semaphore permit for parsing")]
     UnableToAcquirePermit,
+    #[error("Parsing was cancelled because the server is shutting down")]
+    Cancelled,
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(serde_json::Error),
 }
 
 #[derive(Debug, Error, PartialEq, Eq)]
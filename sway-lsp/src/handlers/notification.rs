@@ -1,10 +1,12 @@
 //! This module is responsible for implementing handlers for Language Server
 //! Protocol. This module specifically handles notification messages sent by the Client.
 
-use crate::{core::document, error::LanguageServerError, server_state::ServerState};
+use crate::{
+    config::Config, core::document, error::LanguageServerError, server_state::ServerState,
+};
 use lsp_types::{
-    DidChangeTextDocumentParams, DidChangeWatchedFilesParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, FileChangeType,
+    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, FileChangeType,
 };
 
 pub async fn handle_did_open_text_document(
@@ -35,6 +37,9 @@ pub async fn handle_did_change_text_document(
         .sessions
         .uri_and_session_from_workspace(&params.text_document.uri)?;
     session.write_changes_to_file(&uri, params.content_changes)?;
+    if !state.debounce_change(&uri).await {
+        return Ok(());
+    }
     state
         .parse_project(
             uri,
@@ -74,3 +79,22 @@ pub(crate) fn handle_did_change_watched_files(
     }
     Ok(())
 }
+
+/// Replaces the server's configuration with the settings sent by the client.
+///
+/// The settings can be sent either as the configuration itself or nested under a `sway-lsp` key.
+/// They're deserialized in full before being applied, so invalid settings leave the current
+/// configuration untouched.
+pub async fn handle_did_change_configuration(
+    state: &ServerState,
+    params: DidChangeConfigurationParams,
+) -> Result<(), LanguageServerError> {
+    let settings = match params.settings.get("sway-lsp") {
+        Some(settings) => settings.clone(),
+        None => params.settings,
+    };
+    let config: Config =
+        serde_json::from_value(settings).map_err(LanguageServerError::InvalidConfig)?;
+    state.update_config(config).await;
+    Ok(())
+}
@@ -2,12 +2,14 @@
 //! Protocol. This module specifically handles requests.
 
 use crate::{
-    capabilities, core::session::build_plan, lsp_ext, server_state::ServerState, utils::debug,
+    capabilities, config::BuildMode, core::session::build_plan, lsp_ext, server_state::ServerState,
 };
 use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions, TracingWriterMode};
 use lsp_types::{
-    CodeLens, CompletionResponse, DocumentFormattingParams, DocumentSymbolResponse,
-    InitializeResult, InlayHint, InlayHintParams, PrepareRenameResponse, RenameParams,
+    CodeLens, ColorInformation, ColorPresentation, ColorPresentationParams, CompletionResponse,
+    DocumentColorParams, DocumentFormattingParams, DocumentOnTypeFormattingParams,
+    DocumentSymbolResponse, InitializeResult, InlayHint, InlayHintParams, InlineValue,
+    InlineValueParams, PrepareRenameResponse, RenameFilesParams, RenameParams,
     SemanticTokensParams, SemanticTokensResult, TextDocumentIdentifier, Url, WorkspaceEdit,
 };
 use std::{
@@ -15,7 +17,6 @@ use std::{
     io::Write,
     path::{Path, PathBuf},
 };
-use sway_types::{Ident, Spanned};
 use sway_utils::PerformanceData;
 use tower_lsp::jsonrpc::Result;
 use tracing::metadata::LevelFilter;
@@ -43,7 +44,7 @@ pub fn handle_initialize(
     tracing::info!("Initializing the Sway Language Server");
     Ok(InitializeResult {
         server_info: None,
-        capabilities: crate::server_capabilities(),
+        capabilities: crate::server_capabilities(&config.features),
         ..InitializeResult::default()
     })
 }
@@ -52,6 +53,9 @@ pub fn handle_document_symbol(
     state: &ServerState,
     params: lsp_types::DocumentSymbolParams,
 ) -> Result<Option<lsp_types::DocumentSymbolResponse>> {
+    if !state.config.read().features.document_symbols {
+        return Ok(None);
+    }
     match state
         .sessions
         .uri_and_session_from_workspace(&params.text_document.uri)
@@ -88,6 +92,46 @@ pub fn handle_goto_definition(
     }
 }
 
+pub fn handle_moniker(
+    state: &ServerState,
+    params: lsp_types::MonikerParams,
+) -> Result<Option<Vec<lsp_types::Moniker>>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position_params.position;
+            Ok(capabilities::moniker::monikers(session, &uri, position))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub fn handle_goto_declaration(
+    state: &ServerState,
+    params: lsp_types::request::GotoDeclarationParams,
+) -> Result<Option<lsp_types::request::GotoDeclarationResponse>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position_params.position;
+            Ok(capabilities::declaration::goto_declaration(
+                session, uri, position,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
 pub fn handle_completion(
     state: &ServerState,
     params: lsp_types::CompletionParams,
@@ -97,6 +141,12 @@ pub fn handle_completion(
         .as_ref()
         .and_then(|ctx| ctx.trigger_character.as_deref())
         .unwrap_or("");
+    // Without type information, only keywords can be completed, and nothing after a `.`.
+    if state.config.read().build.mode == BuildMode::Off {
+        return Ok(trigger_char.is_empty().then(|| {
+            CompletionResponse::Array(capabilities::completion::keyword_completion_items())
+        }));
+    }
     let position = params.text_document_position.position;
     match state
         .sessions
@@ -112,6 +162,26 @@ pub fn handle_completion(
     }
 }
 
+pub fn handle_completion_resolve(
+    state: &ServerState,
+    item: lsp_types::CompletionItem,
+) -> Result<lsp_types::CompletionItem> {
+    let Some(data) = item.data.clone().and_then(|data| {
+        serde_json::from_value::<capabilities::completion::CompletionItemData>(data).ok()
+    }) else {
+        return Ok(item);
+    };
+    match state.sessions.uri_and_session_from_workspace(&data.uri) {
+        Ok((_, session)) => Ok(capabilities::completion::resolve_completion_item(
+            &session, item, data,
+        )),
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(item)
+        }
+    }
+}
+
 pub fn handle_hover(
     state: &ServerState,
     params: lsp_types::HoverParams,
@@ -218,6 +288,24 @@ pub fn handle_formatting(
         })
 }
 
+pub fn handle_on_type_formatting(
+    state: &ServerState,
+    params: DocumentOnTypeFormattingParams,
+) -> Result<Option<Vec<lsp_types::TextEdit>>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document_position.text_document.uri)
+    {
+        Ok((uri, session)) => Ok(capabilities::on_type_formatting::on_type_formatting(
+            &session, &uri, &params,
+        )),
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
 pub fn handle_code_action(
     state: &ServerState,
     params: lsp_types::CodeActionParams,
@@ -244,6 +332,9 @@ pub fn handle_code_lens(
     state: &ServerState,
     params: lsp_types::CodeLensParams,
 ) -> Result<Option<Vec<CodeLens>>> {
+    if !state.config.read().features.code_lens {
+        return Ok(None);
+    }
     match state
         .sessions
         .uri_and_session_from_workspace(&params.text_document.uri)
@@ -263,6 +354,9 @@ pub fn handle_semantic_tokens_full(
     state: &ServerState,
     params: SemanticTokensParams,
 ) -> Result<Option<SemanticTokensResult>> {
+    if !state.config.read().features.semantic_tokens {
+        return Ok(None);
+    }
     match state
         .sessions
         .uri_and_session_from_workspace(&params.text_document.uri)
@@ -284,6 +378,9 @@ pub(crate) fn handle_inlay_hints(
     state: &ServerState,
     params: InlayHintParams,
 ) -> Result<Option<Vec<InlayHint>>> {
+    if !state.config.read().features.inlay_hints {
+        return Ok(None);
+    }
     match state
         .sessions
         .uri_and_session_from_workspace(&params.text_document.uri)
@@ -305,6 +402,76 @@ pub(crate) fn handle_inlay_hints(
     }
 }
 
+pub fn handle_inline_value(
+    state: &ServerState,
+    params: InlineValueParams,
+) -> Result<Option<Vec<InlineValue>>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            let _ = session.wait_for_parsing();
+            Ok(capabilities::inline_value::inline_values(
+                session, &uri, &params,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub fn handle_document_color(
+    state: &ServerState,
+    params: DocumentColorParams,
+) -> Result<Vec<ColorInformation>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            let _ = session.wait_for_parsing();
+            Ok(capabilities::document_color::document_colors(session, &uri))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(vec![])
+        }
+    }
+}
+
+pub fn handle_color_presentation(
+    state: &ServerState,
+    params: ColorPresentationParams,
+) -> Result<Vec<ColorPresentation>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            let _ = session.wait_for_parsing();
+            Ok(capabilities::document_color::color_presentations(
+                session,
+                &uri,
+                params.range,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(vec![])
+        }
+    }
+}
+
+pub fn handle_will_rename_files(
+    _state: &ServerState,
+    params: RenameFilesParams,
+) -> Result<Option<WorkspaceEdit>> {
+    Ok(capabilities::rename_files::will_rename_files(&params))
+}
+
 /// This method is triggered by a command palette request in VScode
 /// The 3 commands are: "show lexed ast", "show parsed ast" or "show typed ast"
 ///
@@ -317,10 +484,14 @@ pub(crate) fn handle_inlay_hints(
 /// A formatted AST is written to a temporary file and the URI is
 /// returned to the client so it can be opened and displayed in a
 /// seperate side panel.
+///
+/// If the client requests a `page_size`, the AST is instead returned in pages, so that the AST of
+/// large files can be fetched incrementally. Each page comes with a continuation token to request
+/// the next page with.
 pub fn handle_show_ast(
     state: &ServerState,
     params: lsp_ext::ShowAstParams,
-) -> Result<Option<TextDocumentIdentifier>> {
+) -> Result<Option<lsp_ext::ShowAstResult>> {
     match state
         .sessions
         .uri_and_session_from_workspace(&params.text_document.uri)
@@ -342,65 +513,40 @@ pub fn handle_show_ast(
                     None
                 };
 
-            // Returns true if the current path matches the path of a submodule
-            let path_is_submodule = |ident: &Ident, path: &Option<PathBuf>| -> bool {
-                let engines = session.engines.read();
-                ident.span().source_id().map(|p| engines.se().get_path(p)) == *path
-            };
+            if let Some(page_size) = params.page_size {
+                let offset = match params.continuation_token.as_deref().map(str::parse) {
+                    None => 0,
+                    Some(Ok(offset)) => offset,
+                    Some(Err(_)) => return Ok(None),
+                };
+                let ast = capabilities::show_ast::ast_to_string(&session, &path, &params.ast_kind);
+                return Ok(ast.and_then(|ast| {
+                    capabilities::show_ast::ast_page(&ast, offset, page_size).map(
+                        |(text, next_offset)| {
+                            lsp_ext::ShowAstResult::Page(lsp_ext::ShowAstPage {
+                                text: text.to_string(),
+                                continuation_token: next_offset.map(|offset| offset.to_string()),
+                            })
+                        },
+                    )
+                }));
+            }
 
             let ast_path = PathBuf::from(params.save_path.path());
-            {
-                let program = session.compiled_program.read();
-                match params.ast_kind.as_str() {
-                    "lexed" => {
-                        Ok(program.lexed.as_ref().and_then(|lexed_program| {
-                            let mut formatted_ast = format!("{:#?}", program.lexed);
-                            for (ident, submodule) in &lexed_program.root.submodules {
-                                if path_is_submodule(ident, &path) {
-                                    // overwrite the root AST with the submodule AST
-                                    formatted_ast = format!("{:#?}", submodule.module.tree);
-                                }
-                            }
-                            write_ast_to_file(ast_path.join("lexed.rs").as_path(), &formatted_ast)
-                        }))
-                    }
-                    "parsed" => {
-                        Ok(program.parsed.as_ref().and_then(|parsed_program| {
-                            // Initialize the string with the AST from the root
-                            let mut formatted_ast =
-                                format!("{:#?}", parsed_program.root.tree.root_nodes);
-                            for (ident, submodule) in &parsed_program.root.submodules {
-                                if path_is_submodule(ident, &path) {
-                                    // overwrite the root AST with the submodule AST
-                                    formatted_ast =
-                                        format!("{:#?}", submodule.module.tree.root_nodes);
-                                }
-                            }
-                            write_ast_to_file(ast_path.join("parsed.rs").as_path(), &formatted_ast)
-                        }))
-                    }
-                    "typed" => {
-                        Ok(program.typed.as_ref().and_then(|typed_program| {
-                            // Initialize the string with the AST from the root
-                            let mut formatted_ast = debug::print_decl_engine_types(
-                                &typed_program.root.all_nodes,
-                                session.engines.read().de(),
-                            );
-                            for (ident, submodule) in &typed_program.root.submodules {
-                                if path_is_submodule(ident, &path) {
-                                    // overwrite the root AST with the submodule AST
-                                    formatted_ast = debug::print_decl_engine_types(
-                                        &submodule.module.all_nodes,
-                                        session.engines.read().de(),
-                                    );
-                                }
-                            }
-                            write_ast_to_file(ast_path.join("typed.rs").as_path(), &formatted_ast)
-                        }))
-                    }
-                    _ => Ok(None),
-                }
-            }
+            let file_name = match params.ast_kind.as_str() {
+                "lexed" => "lexed.rs",
+                "parsed" => "parsed.rs",
+                "typed" => "typed.rs",
+                _ => return Ok(None),
+            };
+            Ok(
+                capabilities::show_ast::ast_to_string(&session, &path, &params.ast_kind).and_then(
+                    |formatted_ast| {
+                        write_ast_to_file(ast_path.join(file_name).as_path(), &formatted_ast)
+                            .map(lsp_ext::ShowAstResult::Document)
+                    },
+                ),
+            )
         }
         Err(err) => {
             tracing::error!("{}", err.to_string());
@@ -477,3 +623,143 @@ pub(crate) fn metrics(
         }
     }
 }
+
+/// Returns the monomorphized signature of the generic function call at the given position.
+pub fn handle_expand_generic(
+    state: &ServerState,
+    params: lsp_ext::ExpandGenericParams,
+) -> Result<Option<String>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            let _ = session.wait_for_parsing();
+            Ok(capabilities::expand_generic::expand_generic(
+                session,
+                &uri,
+                params.position,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+/// Runs FileCheck directives against the AST of the current file, as it would be displayed
+/// by "sway/show_ast", and returns the match report.
+pub fn handle_filecheck(
+    state: &ServerState,
+    params: lsp_ext::FileCheckParams,
+) -> Result<Option<lsp_ext::FileCheckResult>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document.uri)
+    {
+        Ok((_, session)) => {
+            let _ = session.wait_for_parsing();
+            Ok(capabilities::filecheck::filecheck(
+                session,
+                &params.text_document.uri,
+                &params.directives,
+                &params.stage,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the lossless syntax tree of the current file, including comments and whitespace.
+pub fn handle_syntax_tree(
+    state: &ServerState,
+    params: lsp_ext::SyntaxTreeParams,
+) -> Result<Option<lsp_ext::SyntaxNode>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document.uri)
+    {
+        Ok((uri, session)) => Ok(capabilities::syntax_tree::syntax_tree(session, &uri)),
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the entry points of the contract and their selectors.
+pub fn handle_abi_methods(
+    state: &ServerState,
+    params: lsp_ext::AbiMethodsParams,
+) -> Result<Option<Vec<lsp_ext::AbiMethod>>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document.uri)
+    {
+        Ok((_, session)) => {
+            let _ = session.wait_for_parsing();
+            Ok(capabilities::abi_methods::abi_methods(&session))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+/// Formats a snippet of code that isn't a document of the workspace, e.g. clipboard contents.
+pub fn handle_format_snippet(
+    _state: &ServerState,
+    params: lsp_ext::FormatSnippetParams,
+) -> Result<lsp_ext::FormatSnippetResult> {
+    Ok(
+        match capabilities::formatting::format_snippet(&params.text, &params.options) {
+            Ok(text) => lsp_ext::FormatSnippetResult {
+                text: Some(text),
+                error: None,
+            },
+            Err(err) => lsp_ext::FormatSnippetResult {
+                text: None,
+                error: Some(err.to_string()),
+            },
+        },
+    )
+}
+
+/// Compiles the tests of the project of the current file and runs the one with the given
+/// fully-qualified name.
+pub async fn handle_run_test(
+    state: &ServerState,
+    params: lsp_ext::RunTestParams,
+) -> Result<Option<lsp_ext::RunTestResult>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document.uri)
+    {
+        Ok((uri, session)) => {
+            // Building and running the tests can take a while, so it's done off the async runtime.
+            let result = tokio::task::spawn_blocking(move || {
+                let _ = session.wait_for_parsing();
+                // The project is built from the temporary copy of the workspace, so that unsaved
+                // changes are tested.
+                capabilities::run_test::run_test(Path::new(uri.path()), &params.test_name)
+            })
+            .await;
+            match result {
+                Ok(result) => Ok(Some(result)),
+                Err(err) => {
+                    tracing::error!("{}", err.to_string());
+                    Ok(None)
+                }
+            }
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
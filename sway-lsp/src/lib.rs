@@ -14,56 +14,100 @@ pub mod server;
 mod traverse;
 pub mod utils;
 
+use config::FeaturesConfig;
 use lsp_types::{
-    CodeActionProviderCapability, CodeLensOptions, CompletionOptions, ExecuteCommandOptions,
-    HoverProviderCapability, OneOf, RenameOptions, SemanticTokensFullOptions, SemanticTokensLegend,
-    SemanticTokensOptions, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
-    WorkDoneProgressOptions,
+    CodeActionProviderCapability, CodeLensOptions, ColorProviderCapability, CompletionOptions,
+    DeclarationCapability, DocumentOnTypeFormattingOptions, ExecuteCommandOptions,
+    FileOperationFilter, FileOperationPattern, FileOperationPatternKind,
+    FileOperationRegistrationOptions, HoverProviderCapability, OneOf, RenameOptions,
+    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, WorkDoneProgressOptions,
+    WorkspaceFileOperationsServerCapabilities, WorkspaceServerCapabilities,
 };
 use server_state::ServerState;
+use std::sync::atomic::Ordering;
 use tower_lsp::{LspService, Server};
 
-pub async fn start() {
+/// Runs the language server over stdio until the client sends `exit` or disconnects.
+///
+/// Returns the exit code the process should terminate with, as required by the LSP spec: `0` if
+/// the server was shut down with a `shutdown` request before exiting, and `1` otherwise.
+pub async fn start() -> i32 {
     let (service, socket) = LspService::build(ServerState::new)
         .custom_method("sway/show_ast", ServerState::show_ast)
         .custom_method("sway/visualize", ServerState::visualize)
         .custom_method("sway/on_enter", ServerState::on_enter)
         .custom_method("sway/metrics", ServerState::metrics)
+        .custom_method("sway/expand_generic", ServerState::expand_generic)
+        .custom_method("sway/filecheck", ServerState::filecheck)
+        .custom_method("sway/syntaxTree", ServerState::syntax_tree)
+        .custom_method("sway/formatSnippet", ServerState::format_snippet)
+        .custom_method("sway/runTest", ServerState::run_test)
+        .custom_method("sway/abi_methods", ServerState::abi_methods)
         .finish();
+    let state = service.inner();
+    let cancellation_token = state.cancellation_token.clone();
+    let shutdown_requested = state.shutdown_requested.clone();
     Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
         .serve(service)
         .await;
+
+    if shutdown_requested.load(Ordering::SeqCst) {
+        0
+    } else {
+        tracing::warn!("The client exited or disconnected without shutting down the server");
+        // Stop compiling projects that nobody is waiting on any more.
+        cancellation_token.cancel();
+        1
+    }
 }
 
 /// Returns the capabilities of the server to the client,
 /// indicating its support for various language server protocol features.
-pub fn server_capabilities() -> ServerCapabilities {
+///
+/// Features that are turned off in `features` aren't advertised.
+pub fn server_capabilities(features: &FeaturesConfig) -> ServerCapabilities {
     ServerCapabilities {
         code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
-        code_lens_provider: Some(CodeLensOptions {
+        code_lens_provider: features.code_lens.then_some(CodeLensOptions {
             resolve_provider: Some(false),
         }),
+        color_provider: Some(ColorProviderCapability::Simple(true)),
         completion_provider: Some(CompletionOptions {
             trigger_characters: Some(vec![".".to_string()]),
+            resolve_provider: Some(true),
             ..Default::default()
         }),
+        declaration_provider: Some(DeclarationCapability::Simple(true)),
         definition_provider: Some(OneOf::Left(true)),
         document_formatting_provider: Some(OneOf::Left(true)),
         document_highlight_provider: Some(OneOf::Left(true)),
-        document_symbol_provider: Some(OneOf::Left(true)),
+        document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+            first_trigger_character: capabilities::on_type_formatting::FIRST_TRIGGER_CHARACTER
+                .to_string(),
+            more_trigger_character: Some(
+                capabilities::on_type_formatting::MORE_TRIGGER_CHARACTERS
+                    .iter()
+                    .map(|ch| ch.to_string())
+                    .collect(),
+            ),
+        }),
+        document_symbol_provider: features.document_symbols.then_some(OneOf::Left(true)),
         execute_command_provider: Some(ExecuteCommandOptions {
             commands: vec![],
             ..Default::default()
         }),
         hover_provider: Some(HoverProviderCapability::Simple(true)),
-        inlay_hint_provider: Some(OneOf::Left(true)),
+        inlay_hint_provider: features.inlay_hints.then_some(OneOf::Left(true)),
+        inline_value_provider: Some(OneOf::Left(true)),
+        moniker_provider: Some(OneOf::Left(true)),
         rename_provider: Some(OneOf::Right(RenameOptions {
             prepare_provider: Some(true),
             work_done_progress_options: WorkDoneProgressOptions {
                 work_done_progress: Some(true),
             },
         })),
-        semantic_tokens_provider: Some(
+        semantic_tokens_provider: features.semantic_tokens.then(|| {
             SemanticTokensOptions {
                 legend: SemanticTokensLegend {
                     token_types: capabilities::semantic_tokens::SUPPORTED_TYPES.to_vec(),
@@ -73,11 +117,27 @@ pub fn server_capabilities() -> ServerCapabilities {
                 range: None,
                 ..Default::default()
             }
-            .into(),
-        ),
+            .into()
+        }),
         text_document_sync: Some(TextDocumentSyncCapability::Kind(
             TextDocumentSyncKind::INCREMENTAL,
         )),
+        workspace: Some(WorkspaceServerCapabilities {
+            file_operations: Some(WorkspaceFileOperationsServerCapabilities {
+                will_rename: Some(FileOperationRegistrationOptions {
+                    filters: vec![FileOperationFilter {
+                        scheme: Some("file".to_string()),
+                        pattern: FileOperationPattern {
+                            glob: "**/*.sw".to_string(),
+                            matches: Some(FileOperationPatternKind::File),
+                            options: None,
+                        },
+                    }],
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
         ..ServerCapabilities::default()
     }
 }
@@ -1,6 +1,8 @@
 //! sway-lsp extensions to the LSP.
 
-use lsp_types::{TextDocumentContentChangeEvent, TextDocumentIdentifier, Url};
+use lsp_types::{
+    FormattingOptions, Position, Range, TextDocumentContentChangeEvent, TextDocumentIdentifier, Url,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -9,6 +11,31 @@ pub struct ShowAstParams {
     pub text_document: TextDocumentIdentifier,
     pub ast_kind: String,
     pub save_path: Url,
+    /// If set, the AST is returned in pages of at most this many bytes instead of being written to
+    /// `save_path`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<usize>,
+    /// The continuation token of the previous page, to fetch the page following it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub continuation_token: Option<String>,
+}
+
+/// The response to "sway/show_ast".
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ShowAstResult {
+    /// The file the whole AST has been written to.
+    Document(TextDocumentIdentifier),
+    /// A page of the AST, if a `page_size` was requested.
+    Page(ShowAstPage),
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShowAstPage {
+    pub text: String,
+    /// The token to request the next page with, or `None` if this is the last page.
+    pub continuation_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -31,3 +58,159 @@ pub struct VisualizeParams {
 pub struct MetricsParams {
     pub text_document: TextDocumentIdentifier,
 }
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpandGenericParams {
+    pub text_document: TextDocumentIdentifier,
+    /// The position of the generic function call to expand.
+    pub position: Position,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileCheckParams {
+    pub text_document: TextDocumentIdentifier,
+    /// The FileCheck directives to match, e.g. `# check: fn main`.
+    pub directives: String,
+    /// The AST to match against: "lexed", "parsed" or "typed".
+    pub stage: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileCheckResult {
+    /// Whether all of the directives matched.
+    pub success: bool,
+    /// The FileCheck report explaining how each directive matched, or why the directives are invalid.
+    pub report: String,
+}
+
+impl FileCheckResult {
+    pub(crate) fn error(report: String) -> Self {
+        Self {
+            success: false,
+            report,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyntaxTreeParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+/// A node of the lossless syntax tree returned by "sway/syntaxTree".
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyntaxNode {
+    pub kind: SyntaxNodeKind,
+    /// The byte offset of the start of the node in the document.
+    pub start: usize,
+    /// The byte offset of the end of the node in the document, exclusive.
+    pub end: usize,
+    /// The source text of leaf nodes. Nodes with children have no text of their own.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub children: Vec<SyntaxNode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SyntaxNodeKind {
+    Root,
+    /// A delimited group of tokens, including its delimiters.
+    Group,
+    OpenDelimiter,
+    CloseDelimiter,
+    Ident,
+    Punct,
+    Literal,
+    Comment,
+    DocComment,
+    Whitespace,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatSnippetParams {
+    /// The Sway code to format, which doesn't need to be a document of the workspace.
+    pub text: String,
+    pub options: FormattingOptions,
+}
+
+/// The response to "sway/formatSnippet".
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatSnippetResult {
+    /// The formatted code, or `None` if the snippet couldn't be formatted.
+    pub text: Option<String>,
+    /// Why the snippet couldn't be formatted, e.g. the error it failed to parse with.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunTestParams {
+    pub text_document: TextDocumentIdentifier,
+    /// The fully-qualified name of the `#[test]` function to run, i.e. its package name, module
+    /// path and function name separated by `::`, e.g. `my_pkg::utils::test_add`.
+    pub test_name: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunTestResult {
+    pub status: TestStatus,
+    /// The values logged by the test, in order. Words are decimal integers and data hex strings.
+    pub logs: Vec<String>,
+    /// The gas used by the test, if it was run.
+    pub gas_used: Option<u64>,
+    /// Why the test didn't pass, e.g. the errors the project failed to compile with.
+    pub error: Option<String>,
+}
+
+impl RunTestResult {
+    pub(crate) fn error(status: TestStatus, error: String) -> Self {
+        Self {
+            status,
+            logs: vec![],
+            gas_used: None,
+            error: Some(error),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    /// The project or its tests failed to compile, so no test was run.
+    CompileError,
+    /// The project has no test of the given name.
+    NotFound,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiMethodsParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+/// An entry point of a contract, as returned by "sway/abi_methods".
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiMethod {
+    pub name: String,
+    /// The document the method is implemented in.
+    pub uri: Option<Url>,
+    /// The range of the name of the method in its implementation.
+    pub range: Range,
+    /// The selector calls to the method are dispatched with, as a hex string, e.g. `0x2151bd4b`.
+    pub selector: Option<String>,
+    /// Why the selector couldn't be computed.
+    pub error: Option<String>,
+}
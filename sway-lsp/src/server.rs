@@ -4,18 +4,26 @@
 use crate::{
     core::document,
     handlers::{notification, request},
-    lsp_ext::{MetricsParams, OnEnterParams, ShowAstParams, VisualizeParams},
+    lsp_ext::{
+        AbiMethod, AbiMethodsParams, ExpandGenericParams, FileCheckParams, FileCheckResult,
+        FormatSnippetParams, FormatSnippetResult, MetricsParams, OnEnterParams, RunTestParams,
+        RunTestResult, ShowAstParams, ShowAstResult, SyntaxNode, SyntaxTreeParams, VisualizeParams,
+    },
     server_state::ServerState,
 };
 use lsp_types::{
-    CodeActionParams, CodeActionResponse, CodeLens, CodeLensParams, CompletionParams,
-    CompletionResponse, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
-    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
-    DocumentFormattingParams, DocumentHighlight, DocumentHighlightParams, DocumentSymbolParams,
+    request::{GotoDeclarationParams, GotoDeclarationResponse},
+    CodeActionParams, CodeActionResponse, CodeLens, CodeLensParams, ColorInformation,
+    ColorPresentation, ColorPresentationParams, CompletionItem, CompletionParams,
+    CompletionResponse, DidChangeConfigurationParams, DidChangeTextDocumentParams,
+    DidChangeWatchedFilesParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidSaveTextDocumentParams, DocumentColorParams, DocumentFormattingParams, DocumentHighlight,
+    DocumentHighlightParams, DocumentOnTypeFormattingParams, DocumentSymbolParams,
     DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams,
-    InitializeParams, InitializeResult, InitializedParams, InlayHint, InlayHintParams,
-    PrepareRenameResponse, RenameParams, SemanticTokensParams, SemanticTokensResult,
-    TextDocumentIdentifier, TextDocumentPositionParams, TextEdit, WorkspaceEdit,
+    InitializeParams, InitializeResult, InitializedParams, InlayHint, InlayHintParams, InlineValue,
+    InlineValueParams, Moniker, MonikerParams, PrepareRenameResponse, RenameFilesParams,
+    RenameParams, SemanticTokensParams, SemanticTokensResult, TextDocumentPositionParams, TextEdit,
+    WorkspaceEdit,
 };
 use sway_utils::PerformanceData;
 use tower_lsp::{jsonrpc::Result, LanguageServer};
@@ -31,6 +39,7 @@ impl LanguageServer for ServerState {
     }
 
     async fn shutdown(&self) -> Result<()> {
+        self.cancel_background_tasks().await;
         self.shutdown_server()
     }
 
@@ -58,6 +67,12 @@ impl LanguageServer for ServerState {
         }
     }
 
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        if let Err(err) = notification::handle_did_change_configuration(self, params).await {
+            tracing::error!("{}", err.to_string());
+        }
+    }
+
     async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
         if let Err(err) = notification::handle_did_change_watched_files(self, params) {
             tracing::error!("{}", err.to_string());
@@ -80,6 +95,10 @@ impl LanguageServer for ServerState {
         request::handle_completion(self, params)
     }
 
+    async fn completion_resolve(&self, item: CompletionItem) -> Result<CompletionItem> {
+        request::handle_completion_resolve(self, item)
+    }
+
     async fn document_symbol(
         &self,
         params: DocumentSymbolParams,
@@ -108,10 +127,28 @@ impl LanguageServer for ServerState {
         request::handle_goto_definition(self, params)
     }
 
+    async fn goto_declaration(
+        &self,
+        params: GotoDeclarationParams,
+    ) -> Result<Option<GotoDeclarationResponse>> {
+        request::handle_goto_declaration(self, params)
+    }
+
+    async fn moniker(&self, params: MonikerParams) -> Result<Option<Vec<Moniker>>> {
+        request::handle_moniker(self, params)
+    }
+
     async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
         request::handle_formatting(self, params)
     }
 
+    async fn on_type_formatting(
+        &self,
+        params: DocumentOnTypeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        request::handle_on_type_formatting(self, params)
+    }
+
     async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
         request::handle_rename(self, params)
     }
@@ -126,11 +163,30 @@ impl LanguageServer for ServerState {
     async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
         request::handle_inlay_hints(self, params)
     }
+
+    async fn inline_value(&self, params: InlineValueParams) -> Result<Option<Vec<InlineValue>>> {
+        request::handle_inline_value(self, params)
+    }
+
+    async fn document_color(&self, params: DocumentColorParams) -> Result<Vec<ColorInformation>> {
+        request::handle_document_color(self, params)
+    }
+
+    async fn color_presentation(
+        &self,
+        params: ColorPresentationParams,
+    ) -> Result<Vec<ColorPresentation>> {
+        request::handle_color_presentation(self, params)
+    }
+
+    async fn will_rename_files(&self, params: RenameFilesParams) -> Result<Option<WorkspaceEdit>> {
+        request::handle_will_rename_files(self, params)
+    }
 }
 
 // Custom LSP-Server Methods
 impl ServerState {
-    pub async fn show_ast(&self, params: ShowAstParams) -> Result<Option<TextDocumentIdentifier>> {
+    pub async fn show_ast(&self, params: ShowAstParams) -> Result<Option<ShowAstResult>> {
         request::handle_show_ast(self, params)
     }
 
@@ -148,4 +204,28 @@ impl ServerState {
     ) -> Result<Option<Vec<(String, PerformanceData)>>> {
         request::metrics(self, params)
     }
+
+    pub async fn expand_generic(&self, params: ExpandGenericParams) -> Result<Option<String>> {
+        request::handle_expand_generic(self, params)
+    }
+
+    pub async fn filecheck(&self, params: FileCheckParams) -> Result<Option<FileCheckResult>> {
+        request::handle_filecheck(self, params)
+    }
+
+    pub async fn syntax_tree(&self, params: SyntaxTreeParams) -> Result<Option<SyntaxNode>> {
+        request::handle_syntax_tree(self, params)
+    }
+
+    pub async fn run_test(&self, params: RunTestParams) -> Result<Option<RunTestResult>> {
+        request::handle_run_test(self, params).await
+    }
+
+    pub async fn format_snippet(&self, params: FormatSnippetParams) -> Result<FormatSnippetResult> {
+        request::handle_format_snippet(self, params)
+    }
+
+    pub async fn abi_methods(&self, params: AbiMethodsParams) -> Result<Option<Vec<AbiMethod>>> {
+        request::handle_abi_methods(self, params)
+    }
 }
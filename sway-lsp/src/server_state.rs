@@ -2,7 +2,7 @@
 
 use crate::{
     capabilities::diagnostic::get_diagnostics,
-    config::{Config, Warnings},
+    config::{BuildMode, Config, Warnings},
     core::session::{self, Session},
     error::{DirectoryError, DocumentError, LanguageServerError},
     utils::debug,
@@ -10,17 +10,37 @@ use crate::{
 };
 use dashmap::DashMap;
 use forc_pkg::PackageManifestFile;
-use lsp_types::{Diagnostic, Url};
+use lsp_types::{Diagnostic, MessageType, Url};
 use parking_lot::RwLock;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
 use tower_lsp::{jsonrpc, Client};
 
+/// The maximum time to wait for background tasks to finish when the server shuts down.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// `ServerState` is the primary mutable state of the language server
 pub struct ServerState {
     pub(crate) client: Option<Client>,
     pub(crate) config: Arc<RwLock<Config>>,
     pub(crate) keyword_docs: Arc<KeywordDocs>,
     pub(crate) sessions: Arc<Sessions>,
+    /// Cancelled when the server shuts down, to stop any background work that is still pending.
+    pub(crate) cancellation_token: CancellationToken,
+    /// Tracks the background tasks that compile projects, so they can be joined on shutdown.
+    pub(crate) tasks: TaskTracker,
+    /// The number of changes received for each document, used to debounce recompiles.
+    pending_changes: DashMap<Url, u64>,
+    /// Set once the client has sent a `shutdown` request, which must come before `exit`.
+    pub(crate) shutdown_requested: Arc<AtomicBool>,
 }
 
 impl Default for ServerState {
@@ -30,6 +50,10 @@ impl Default for ServerState {
             config: Arc::new(RwLock::new(Default::default())),
             keyword_docs: Arc::new(KeywordDocs::new()),
             sessions: Arc::new(Sessions(DashMap::new())),
+            cancellation_token: CancellationToken::new(),
+            tasks: TaskTracker::new(),
+            pending_changes: DashMap::new(),
+            shutdown_requested: Arc::new(AtomicBool::new(false)),
         }
     }
 }
@@ -44,13 +68,128 @@ impl ServerState {
 
     pub fn shutdown_server(&self) -> jsonrpc::Result<()> {
         tracing::info!("Shutting Down the Sway Language Server");
-        let _ = self.sessions.iter().map(|item| {
+        self.shutdown_requested.store(true, Ordering::SeqCst);
+        self.cancellation_token.cancel();
+        self.tasks.close();
+        for item in self.sessions.iter() {
             let session = item.value();
             session.shutdown();
-        });
+        }
         Ok(())
     }
 
+    /// Returns `true` if the server has been shut down, i.e. it's safe for the process to exit.
+    pub fn is_shutdown(&self) -> bool {
+        self.shutdown_requested.load(Ordering::SeqCst)
+    }
+
+    /// Cancels all pending background work, such as compiling projects and publishing their
+    /// diagnostics, and waits for the tasks that are already running to finish.
+    ///
+    /// Returns `false` if the tasks didn't finish within [SHUTDOWN_TIMEOUT].
+    pub async fn cancel_background_tasks(&self) -> bool {
+        self.cancellation_token.cancel();
+        self.tasks.close();
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, self.tasks.wait())
+            .await
+            .is_err()
+        {
+            tracing::warn!(
+                "{} background tasks did not finish within {:?}",
+                self.tasks.len(),
+                SHUTDOWN_TIMEOUT
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Waits for the debounce window configured by `diagnostic.debounce_ms` to pass after a change
+    /// to the document at `uri`.
+    ///
+    /// Returns `false` if another change to the document arrived in the meantime, in which case the
+    /// later change supersedes this one and the document shouldn't be recompiled for it.
+    pub async fn debounce_change(&self, uri: &Url) -> bool {
+        let debounce_ms = self.config.read().diagnostic.debounce_ms;
+        if debounce_ms == 0 {
+            return true;
+        }
+        let change = {
+            let mut changes = self.pending_changes.entry(uri.clone()).or_insert(0);
+            *changes += 1;
+            *changes
+        };
+        tokio::time::sleep(Duration::from_millis(debounce_ms)).await;
+        // The latest change removes the entry, so that documents that are no longer edited don't
+        // keep one around.
+        self.pending_changes
+            .remove_if(uri, |_, changes| *changes == change)
+            .is_some()
+    }
+
+    /// Replaces the current [Config] with `config` and applies the settings that changed.
+    ///
+    /// The whole config is swapped at once, so requests never observe a partially updated config.
+    /// Diagnostics of all open documents are republished if the diagnostic settings changed, and the
+    /// client is asked to refresh its inlay hints if the inlay hint settings changed.
+    pub async fn update_config(&self, config: Config) {
+        let old_config = std::mem::replace(&mut *self.config.write(), config.clone());
+        if old_config.logging != config.logging {
+            tracing::warn!("Changes to the logging config take effect after restarting the server");
+        }
+        if old_config.diagnostic != config.diagnostic || old_config.debug != config.debug {
+            self.publish_all_diagnostics().await;
+        }
+        if old_config.build != config.build {
+            tracing::warn!("Changes to the build mode take effect when a document is next changed");
+        }
+        if old_config.features != config.features {
+            tracing::warn!(
+                "Features that were turned off when the server started are only advertised to the client after restarting the server"
+            );
+        }
+        if old_config.inlay_hints != config.inlay_hints
+            || old_config.features.inlay_hints != config.features.inlay_hints
+        {
+            if let Some(client) = self.client.as_ref() {
+                if let Err(err) = client.inlay_hint_refresh().await {
+                    tracing::error!("Unable to refresh inlay hints: {}", err.to_string());
+                }
+            }
+        }
+    }
+
+    /// Republishes the diagnostics of all open documents, without recompiling them.
+    async fn publish_all_diagnostics(&self) {
+        let client = match self.client.as_ref() {
+            Some(client) => client,
+            None => return,
+        };
+        let sessions: Vec<Arc<Session>> = self
+            .sessions
+            .iter()
+            .map(|item| item.value().clone())
+            .collect();
+        for session in sessions {
+            let uris: Vec<Url> = session
+                .documents
+                .iter()
+                .filter_map(|item| Url::from_file_path(item.key()).ok())
+                .collect();
+            for uri in uris {
+                if let Ok(workspace_uri) = session.sync.temp_to_workspace_url(&uri) {
+                    client
+                        .publish_diagnostics(
+                            workspace_uri,
+                            self.diagnostics(&uri, session.clone()),
+                            None,
+                        )
+                        .await;
+                }
+            }
+        }
+    }
+
     pub(crate) fn diagnostics(&self, uri: &Url, session: Arc<Session>) -> Vec<Diagnostic> {
         let mut diagnostics_to_publish = vec![];
         let config = &self.config.read();
@@ -88,7 +227,17 @@ impl ServerState {
         version: Option<i32>,
         session: Arc<Session>,
     ) {
-        match run_blocking_parse_project(uri.clone(), version, session.clone()).await {
+        let build_mode = self.config.read().build.mode;
+        let parse_result = run_blocking_parse_project(
+            uri.clone(),
+            version,
+            build_mode,
+            session.clone(),
+            &self.tasks,
+            self.cancellation_token.clone(),
+        )
+        .await;
+        match parse_result {
             Ok(_) => {
                 // Note: Even if the computed diagnostics vec is empty, we still have to push the empty Vec
                 // in order to clear former diagnostics. Newly pushed diagnostics always replace previously pushed diagnostics.
@@ -102,6 +251,21 @@ impl ServerState {
                         .await;
                 }
             }
+            Err(LanguageServerError::CompilerPanicked(message)) => {
+                tracing::error!("The compiler panicked while compiling {}: {}", uri, message);
+                if let Some(client) = self.client.as_ref() {
+                    client
+                        .show_message(
+                            MessageType::ERROR,
+                            format!(
+                                "The Sway compiler crashed while compiling {}, so diagnostics may be \
+                                out of date until the next edit. See the server log for details.",
+                                workspace_uri.path()
+                            ),
+                        )
+                        .await;
+                }
+            }
             Err(err) => {
                 if matches!(err, LanguageServerError::FailedToParse) {
                     tracing::error!("Error parsing project: {:?}", err);
@@ -112,36 +276,77 @@ impl ServerState {
 }
 
 /// Runs parse_project in a blocking thread, because parsing is not async.
+///
+/// The thread is spawned on `tasks` so that it can be joined on shutdown. Once `cancellation_token`
+/// is cancelled, no new parsing is started and the results of a parse that is in progress are discarded.
 async fn run_blocking_parse_project(
     uri: Url,
     version: Option<i32>,
+    build_mode: BuildMode,
     session: Arc<Session>,
+    tasks: &TaskTracker,
+    cancellation_token: CancellationToken,
 ) -> Result<(), LanguageServerError> {
+    if cancellation_token.is_cancelled() {
+        return Err(LanguageServerError::Cancelled);
+    }
     // Acquire a permit to parse the project. If there are none available, return false. This way,
     // we avoid publishing the same diagnostics multiple times.
     if session.parse_permits.try_acquire().is_err() {
         return Err(LanguageServerError::UnableToAcquirePermit);
     }
-    tokio::task::spawn_blocking(move || {
-        // Lock the diagnostics result to prevent multiple threads from parsing the project at the same time.
-        let mut diagnostics = session.diagnostics.write();
+    tasks
+        .spawn_blocking(move || {
+            // Lock the diagnostics result to prevent multiple threads from parsing the project at the same time.
+            let mut diagnostics = session.diagnostics.write();
 
-        if let Some(version) = version {
-            // Garbage collection is fairly expsensive so we only clear on every 10th keystroke.
-            if version % 10 == 0 {
-                if let Err(err) = session.garbage_collect() {
-                    tracing::error!("Unable to perform garbage collection: {}", err.to_string());
+            if version.is_some() {
+                // Garbage collection is fairly expensive so we only clear after every 10th parse of
+                // a change. Versions can't be used for this, as debounced changes are never parsed.
+                if session.completed_parses.load(Ordering::Relaxed) >= 10 {
+                    session.completed_parses.store(0, Ordering::Relaxed);
+                    if let Err(err) = session.garbage_collect() {
+                        tracing::error!(
+                            "Unable to perform garbage collection: {}",
+                            err.to_string()
+                        );
+                    }
                 }
             }
-        }
-        let parse_result = session::parse_project(&uri, &session.engines.read())?;
-        let (errors, warnings) = parse_result.diagnostics.clone();
-        session.write_parse_result(parse_result);
-        *diagnostics = get_diagnostics(&warnings, &errors, session.engines.read().se());
-        Ok(())
-    })
-    .await
-    .unwrap_or_else(|_| Err(LanguageServerError::FailedToParse))
+            if cancellation_token.is_cancelled() {
+                return Err(LanguageServerError::Cancelled);
+            }
+            // Catch compiler panics so that a single bad edit doesn't take the server down with it.
+            let parse_result = panic::catch_unwind(AssertUnwindSafe(|| match build_mode {
+                BuildMode::Full => session::parse_project(&uri, &session.engines.read()),
+                BuildMode::Off => {
+                    session::parse_project_without_building(&uri, &session.engines.read())
+                }
+            }))
+            .map_err(|payload| LanguageServerError::CompilerPanicked(panic_message(&*payload)))??;
+            if cancellation_token.is_cancelled() {
+                return Err(LanguageServerError::Cancelled);
+            }
+            let (errors, warnings) = parse_result.diagnostics.clone();
+            session.write_parse_result(parse_result);
+            *diagnostics = get_diagnostics(&warnings, &errors, session.engines.read().se());
+            if version.is_some() {
+                session.completed_parses.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(())
+        })
+        .await
+        .unwrap_or_else(|_| Err(LanguageServerError::FailedToParse))
+}
+
+/// Extracts the message of a panic from its payload, which is a `&str` or a `String` when
+/// `panic!` is given a message.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_string())
 }
 
 /// `Sessions` is a collection of [Session]s, each of which represents a project
@@ -0,0 +1,23 @@
+script;
+
+struct MyStruct {
+    /// Whether the struct is set.
+    a: bool,
+}
+
+impl MyStruct {
+    fn new() -> Self {
+        Self { a: true }
+    }
+
+    /// Returns `foo`.
+    fn get(self, foo: Self) -> Self {
+        foo
+    }
+}
+
+fn main() {
+    let foo = MyStruct::new();
+    let bar = MyStruct::new();
+    foo.
+}
@@ -0,0 +1,25 @@
+contract;
+
+abi Counter {
+    #[storage(read)]
+    fn count() -> u64;
+
+    fn reset();
+}
+
+trait Shape {
+    fn area(self) -> u64;
+    fn sides(self) -> u64;
+}
+
+struct Square {
+    side: u64,
+}
+
+impl Shape for Square {
+    fn sides(self) -> u64 {
+        4
+    }
+}
+
+impl Counter for Contract {}
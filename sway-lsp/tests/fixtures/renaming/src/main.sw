@@ -60,3 +60,17 @@ impl Alias11 {
 fn test1() {
     Alias11::foo();
 }
+
+// Fields of different structs with the same name
+struct Rect {
+    x: u32,
+    width: u32,
+}
+
+fn test_fields() {
+    let x = 1;
+    let rect = Rect { x, width: 2 };
+    let point = Point { x: 3, y: 4 };
+    let _ = rect.x + point.x;
+    let _ = Rect { x: rect.x, width: point.y };
+}
@@ -0,0 +1,26 @@
+contract;
+
+trait Shape {
+    fn area(self) -> u64;
+}
+
+struct Square {
+    side: u64,
+}
+
+impl Shape for Square {
+    fn area(self) -> u64 {
+        self.side * self.side
+    }
+}
+
+abi Geometry {
+    fn square_area(side: u64) -> u64;
+}
+
+impl Geometry for Contract {
+    fn square_area(side: u64) -> u64 {
+        let square = Square { side };
+        square.area()
+    }
+}
@@ -7,3 +7,32 @@ trait TestTrait<A, B> {}
 impl<A, B> TestStruct<A, B> {}
 impl<A, B> TestTrait<A, B> for TestStruct<A, B> {}
 impl<A, B> TestStruct<TestStruct<A, B>, B> {}
+
+struct Foo {}
+struct Bar {}
+
+impl Foo {
+    fn new() -> Self {
+        Foo {}
+    }
+}
+
+impl Bar {
+    fn new() -> Self {
+        Bar {}
+    }
+}
+
+trait Named {
+    fn name() -> u64;
+}
+
+impl Named for Foo {
+    fn name() -> u64 {
+        1
+    }
+}
+
+fn new() -> Foo {
+    Foo::new()
+}
@@ -506,6 +506,7 @@ pub(crate) fn code_action_auto_import_struct_request(server: &ServerState, uri:
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("EvmAddress".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -549,6 +550,7 @@ pub(crate) fn code_action_auto_import_struct_request(server: &ServerState, uri:
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("DeepStruct".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -596,6 +598,7 @@ pub(crate) fn code_action_auto_import_enum_request(server: &ServerState, uri: &U
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("AuthError".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -639,6 +642,7 @@ pub(crate) fn code_action_auto_import_enum_request(server: &ServerState, uri: &U
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("DeepEnum".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -687,6 +691,7 @@ pub(crate) fn code_action_auto_import_function_request(server: &ServerState, uri
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("deep_fun".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -735,6 +740,7 @@ pub(crate) fn code_action_auto_import_constant_request(server: &ServerState, uri
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("TEST_CONST".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -780,6 +786,7 @@ pub(crate) fn code_action_auto_import_trait_request(server: &ServerState, uri: &
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("TryFrom".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -823,6 +830,7 @@ pub(crate) fn code_action_auto_import_trait_request(server: &ServerState, uri: &
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("DeepTrait".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -869,6 +877,7 @@ pub(crate) fn code_action_auto_import_alias_request(server: &ServerState, uri: &
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("A".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -908,3 +917,105 @@ pub(crate) fn code_action_auto_import_alias_request(server: &ServerState, uri: &
     let actual = send_request(server, &params);
     assert_eq!(expected, actual);
 }
+
+pub(crate) fn code_action_missing_trait_methods_request(server: &ServerState, uri: &Url) {
+    // Shape: trait with an existing method
+    let range = Range {
+        start: Position {
+            line: 18,
+            character: 0,
+        },
+        end: Position {
+            line: 22,
+            character: 1,
+        },
+    };
+    let params = create_code_action_params(
+        uri.clone(),
+        Range {
+            start: Position {
+                line: 18,
+                character: 5,
+            },
+            end: Position {
+                line: 18,
+                character: 10,
+            },
+        },
+        create_diagnostic_from_data(
+            range,
+            DiagnosticData {
+                missing_trait_methods: Some(vec!["area".to_string()]),
+                ..Default::default()
+            },
+        ),
+    );
+    let insertion = Position {
+        line: 22,
+        character: 0,
+    };
+    let expected = vec![create_code_action(
+        uri.clone(),
+        "Implement missing methods of `Shape`".to_string(),
+        create_changes_map(
+            uri,
+            Range::new(insertion, insertion),
+            "\n    fn area(self) -> u64 {\n        revert(0)\n    }\n",
+        ),
+        None,
+        Some(CodeActionKind::QUICKFIX),
+    )];
+
+    let actual = send_request(server, &params);
+    assert_eq!(expected, actual);
+
+    // Counter: ABI with an empty impl block on a single line
+    let range = Range {
+        start: Position {
+            line: 24,
+            character: 0,
+        },
+        end: Position {
+            line: 24,
+            character: 28,
+        },
+    };
+    let params = create_code_action_params(
+        uri.clone(),
+        Range {
+            start: Position {
+                line: 24,
+                character: 5,
+            },
+            end: Position {
+                line: 24,
+                character: 12,
+            },
+        },
+        create_diagnostic_from_data(
+            range,
+            DiagnosticData {
+                missing_trait_methods: Some(vec!["count".to_string(), "reset".to_string()]),
+                ..Default::default()
+            },
+        ),
+    );
+    let insertion = Position {
+        line: 24,
+        character: 27,
+    };
+    let expected = vec![create_code_action(
+        uri.clone(),
+        "Implement missing methods of `Counter`".to_string(),
+        create_changes_map(
+            uri,
+            Range::new(insertion, insertion),
+            "\n    #[storage(read)]\n    fn count() -> u64 {\n        revert(0)\n    }\n\n    fn reset() {\n        revert(0)\n    }\n",
+        ),
+        None,
+        Some(CodeActionKind::QUICKFIX),
+    )];
+
+    let actual = send_request(server, &params);
+    assert_eq!(expected, actual);
+}
@@ -9,7 +9,10 @@ use serde_json::json;
 use std::{borrow::Cow, path::Path};
 use sway_lsp::{
     handlers::request,
-    lsp_ext::{ShowAstParams, VisualizeParams},
+    lsp_ext::{
+        AbiMethod, AbiMethodsParams, ExpandGenericParams, FileCheckParams, FileCheckResult,
+        RunTestParams, RunTestResult, ShowAstParams, ShowAstResult, VisualizeParams,
+    },
     server_state::ServerState,
 };
 use sway_utils::PerformanceData;
@@ -36,12 +39,12 @@ pub(crate) async fn call_request(
 }
 
 pub(crate) async fn initialize_request(service: &mut LspService<ServerState>) -> Request {
-    let params = json!({ "capabilities": sway_lsp::server_capabilities() });
+    let params = json!({ "capabilities": ClientCapabilities::default() });
     let initialize = build_request_with_id("initialize", params, 1);
     let response = call_request(service, initialize.clone()).await;
     let expected = Response::from_ok(
         1.into(),
-        json!({ "capabilities": sway_lsp::server_capabilities() }),
+        json!({ "capabilities": sway_lsp::server_capabilities(&Default::default()) }),
     );
     assert_json_eq!(expected, response.ok().unwrap());
     initialize
@@ -138,13 +141,47 @@ pub(crate) async fn show_ast_request(
         text_document: TextDocumentIdentifier { uri: uri.clone() },
         ast_kind: ast_kind.to_string(),
         save_path: save_path.clone(),
+        page_size: None,
+        continuation_token: None,
     };
 
     let response = request::handle_show_ast(server, params);
     let expected = TextDocumentIdentifier {
         uri: Url::parse(&format!("{save_path}/{ast_kind}.rs")).unwrap(),
     };
-    assert_eq!(expected, response.unwrap().unwrap());
+    assert_eq!(
+        ShowAstResult::Document(expected),
+        response.unwrap().unwrap()
+    );
+}
+
+/// Fetches the AST page by page and returns the pages.
+pub(crate) async fn show_ast_paged_request(
+    server: &ServerState,
+    uri: &Url,
+    ast_kind: &str,
+    page_size: usize,
+) -> Vec<String> {
+    let mut pages = vec![];
+    let mut continuation_token = None;
+    loop {
+        let params = ShowAstParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            ast_kind: ast_kind.to_string(),
+            save_path: Url::from_file_path(Path::new("/tmp")).unwrap(),
+            page_size: Some(page_size),
+            continuation_token,
+        };
+        let response = request::handle_show_ast(server, params).unwrap().unwrap();
+        let ShowAstResult::Page(page) = response else {
+            panic!("Expected a page of the AST, got {response:?}");
+        };
+        pages.push(page.text);
+        match page.continuation_token {
+            Some(token) => continuation_token = Some(token),
+            None => return pages,
+        }
+    }
 }
 
 pub(crate) async fn visualize_request(server: &ServerState, uri: &Url, graph_kind: &str) {
@@ -163,6 +200,69 @@ pub(crate) async fn visualize_request(server: &ServerState, uri: &Url, graph_kin
     assert!(!re.find(response.as_str()).unwrap().is_empty());
 }
 
+pub(crate) fn expand_generic_request(
+    server: &ServerState,
+    uri: &Url,
+    line: u32,
+    character: u32,
+) -> Option<String> {
+    let params = ExpandGenericParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        position: Position { line, character },
+    };
+    request::handle_expand_generic(server, params).unwrap()
+}
+
+pub(crate) fn abi_methods_request(server: &ServerState, uri: &Url) -> Option<Vec<AbiMethod>> {
+    let params = AbiMethodsParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+    };
+    request::handle_abi_methods(server, params).unwrap()
+}
+
+pub(crate) fn filecheck_request(
+    server: &ServerState,
+    uri: &Url,
+    directives: &str,
+    stage: &str,
+) -> Option<FileCheckResult> {
+    let params = FileCheckParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        directives: directives.to_string(),
+        stage: stage.to_string(),
+    };
+    request::handle_filecheck(server, params).unwrap()
+}
+
+pub(crate) async fn run_test_request(
+    server: &ServerState,
+    uri: &Url,
+    test_name: &str,
+) -> Option<RunTestResult> {
+    let params = RunTestParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        test_name: test_name.to_string(),
+    };
+    request::handle_run_test(server, params).await.unwrap()
+}
+
+pub(crate) fn moniker_request(
+    server: &ServerState,
+    uri: &Url,
+    line: u32,
+    character: u32,
+) -> Option<Vec<Moniker>> {
+    let params = MonikerParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            position: Position { line, character },
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    request::handle_moniker(server, params).unwrap()
+}
+
 pub(crate) async fn metrics_request(
     service: &mut LspService<ServerState>,
     uri: &Url,
@@ -365,7 +465,13 @@ pub(crate) fn completion_request(server: &ServerState, uri: &Url) {
             trigger_character: Some(".".to_string()),
         }),
     };
-    let res = request::handle_completion(server, params).unwrap();
+    let mut res = request::handle_completion(server, params).unwrap();
+    // The data used to resolve the items contains the path of the temporary workspace.
+    if let Some(CompletionResponse::Array(items)) = &mut res {
+        for item in items {
+            assert!(item.data.take().is_some());
+        }
+    }
     let expected = CompletionResponse::Array(vec![
         CompletionItem {
             label: "a".to_string(),
@@ -402,8 +508,63 @@ pub(crate) fn completion_request(server: &ServerState, uri: &Url) {
     assert_eq!(expected, res.unwrap());
 }
 
-pub(crate) fn definition_check<'a>(server: &ServerState, go_to: &'a GotoDefinition<'a>) {
-    let params = GotoDefinitionParams {
+pub(crate) fn completion_resolve_request(server: &ServerState, uri: &Url) {
+    let params = CompletionParams {
+        text_document_position: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            position: Position {
+                line: 21,
+                character: 8,
+            },
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+        context: Some(CompletionContext {
+            trigger_kind: CompletionTriggerKind::TRIGGER_CHARACTER,
+            trigger_character: Some(".".to_string()),
+        }),
+    };
+    let Some(CompletionResponse::Array(items)) =
+        request::handle_completion(server, params).unwrap()
+    else {
+        panic!("expected completion items");
+    };
+    // The documentation and detail are only filled in when resolving an item.
+    assert!(items
+        .iter()
+        .all(|item| item.documentation.is_none() && item.detail.is_none()));
+    let resolved = items
+        .into_iter()
+        .map(|item| {
+            let item = request::handle_completion_resolve(server, item).unwrap();
+            (item.label, item.documentation, item.detail)
+        })
+        .collect::<Vec<_>>();
+    let documentation = |value: &str| {
+        Some(Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: value.to_string(),
+        }))
+    };
+    assert_eq!(
+        resolved,
+        vec![
+            (
+                "a".to_string(),
+                documentation(" Whether the struct is set.\n"),
+                Some("a: bool".to_string()),
+            ),
+            (
+                "get(…)".to_string(),
+                documentation(" Returns `foo`.\n"),
+                Some("fn get(self, foo: Self) -> Self".to_string()),
+            ),
+        ]
+    );
+}
+
+fn goto_params(go_to: &GotoDefinition<'_>) -> GotoDefinitionParams {
+    GotoDefinitionParams {
         text_document_position_params: TextDocumentPositionParams {
             text_document: TextDocumentIdentifier {
                 uri: go_to.req_uri.clone(),
@@ -415,8 +576,14 @@ pub(crate) fn definition_check<'a>(server: &ServerState, go_to: &'a GotoDefiniti
         },
         work_done_progress_params: Default::default(),
         partial_result_params: Default::default(),
-    };
-    let res = request::handle_goto_definition(server, params.clone()).unwrap();
+    }
+}
+
+fn assert_goto_response(
+    go_to: &GotoDefinition<'_>,
+    params: &GotoDefinitionParams,
+    res: Option<GotoDefinitionResponse>,
+) {
     let unwrapped_response = res.as_ref().unwrap_or_else(|| {
         panic!(
             "Failed to deserialize response: {:?} input: {:#?}",
@@ -452,6 +619,18 @@ pub(crate) fn definition_check<'a>(server: &ServerState, go_to: &'a GotoDefiniti
     }
 }
 
+pub(crate) fn definition_check<'a>(server: &ServerState, go_to: &'a GotoDefinition<'a>) {
+    let params = goto_params(go_to);
+    let res = request::handle_goto_definition(server, params.clone()).unwrap();
+    assert_goto_response(go_to, &params, res);
+}
+
+pub(crate) fn declaration_check<'a>(server: &ServerState, go_to: &'a GotoDefinition<'a>) {
+    let params = goto_params(go_to);
+    let res = request::handle_goto_declaration(server, params.clone()).unwrap();
+    assert_goto_response(go_to, &params, res);
+}
+
 pub(crate) fn definition_check_with_req_offset(
     server: &ServerState,
     go_to: &mut GotoDefinition<'_>,
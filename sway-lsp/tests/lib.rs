@@ -4,7 +4,9 @@ use crate::integration::{code_actions, lsp};
 use lsp_types::*;
 use std::{fs, path::PathBuf};
 use sway_lsp::{
+    config::{BuildConfig, BuildMode, Config, FeaturesConfig},
     handlers::{notification, request},
+    lsp_ext::{AbiMethod, TestStatus},
     server_state::ServerState,
 };
 use sway_lsp_test_utils::{
@@ -87,6 +89,50 @@ async fn did_open() {
     let _ = server.shutdown_server();
 }
 
+#[tokio::test]
+async fn shutdown_cancels_background_tasks() {
+    let server = ServerState::default();
+    let (uri, sway_program) = load_sway_example(e2e_test_dir().join("src/main.sw"));
+    let params = DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: uri.clone(),
+            language_id: "sway".to_string(),
+            version: 1,
+            text: sway_program,
+        },
+    };
+
+    // Shut down while the project is being compiled in the background.
+    let (res, finished) = tokio::join!(
+        notification::handle_did_open_text_document(&server, params),
+        server.cancel_background_tasks(),
+    );
+    assert!(res.is_ok());
+    assert!(finished);
+
+    // The results of the cancelled compilation are discarded.
+    let params = DocumentSymbolParams {
+        text_document: TextDocumentIdentifier { uri },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    match request::handle_document_symbol(&server, params).unwrap() {
+        Some(DocumentSymbolResponse::Flat(symbols)) => assert!(symbols.is_empty()),
+        response => panic!("unexpected document symbols: {response:?}"),
+    }
+    let _ = server.shutdown_server();
+}
+
+#[tokio::test]
+async fn shutdown_before_exit() {
+    let (mut service, _) = LspService::new(ServerState::new);
+    let _ = lsp::initialize_request(&mut service).await;
+    lsp::initialized_notification(&mut service).await;
+    assert!(!service.inner().is_shutdown());
+    shutdown_and_exit(&mut service).await;
+    assert!(service.inner().is_shutdown());
+}
+
 #[tokio::test]
 async fn did_change() {
     let (mut service, _) = LspService::new(ServerState::new);
@@ -95,6 +141,78 @@ async fn did_change() {
     shutdown_and_exit(&mut service).await;
 }
 
+#[tokio::test]
+async fn did_change_debounce() {
+    let server = ServerState::default();
+    let params = InitializeParams {
+        initialization_options: Some(serde_json::json!({
+            "diagnostic": {
+                "showWarnings": true,
+                "showErrors": true,
+                "debounceMs": 50,
+            },
+        })),
+        ..Default::default()
+    };
+    let _ = request::handle_initialize(&server, params);
+    let uri = open(&server, doc_comments_dir().join("src/main.sw")).await;
+
+    // Only the last of the changes that arrive within the window triggers a recompile.
+    let (first, second) = tokio::join!(server.debounce_change(&uri), server.debounce_change(&uri));
+    assert!(!first);
+    assert!(second);
+    assert!(server.debounce_change(&uri).await);
+    let _ = server.shutdown_server();
+}
+
+#[tokio::test]
+async fn did_change_configuration() {
+    let server = ServerState::default();
+    let uri = open(&server, doc_comments_dir().join("src/main.sw")).await;
+
+    // Changes are debounced by default.
+    let (first, second) = tokio::join!(server.debounce_change(&uri), server.debounce_change(&uri));
+    assert!(!first);
+    assert!(second);
+
+    let params = DidChangeConfigurationParams {
+        settings: serde_json::json!({
+            "sway-lsp": {
+                "diagnostic": {
+                    "showWarnings": true,
+                    "showErrors": true,
+                    "debounceMs": 0,
+                },
+            },
+        }),
+    };
+    notification::handle_did_change_configuration(&server, params)
+        .await
+        .unwrap();
+    let (first, second) = tokio::join!(server.debounce_change(&uri), server.debounce_change(&uri));
+    assert!(first);
+    assert!(second);
+
+    // Invalid settings are rejected and the previous config stays in place.
+    let params = DidChangeConfigurationParams {
+        settings: serde_json::json!({
+            "diagnostic": {
+                "showWarnings": "yes",
+                "debounceMs": 100,
+            },
+        }),
+    };
+    assert!(
+        notification::handle_did_change_configuration(&server, params)
+            .await
+            .is_err()
+    );
+    let (first, second) = tokio::join!(server.debounce_change(&uri), server.debounce_change(&uri));
+    assert!(first);
+    assert!(second);
+    let _ = server.shutdown_server();
+}
+
 #[tokio::test]
 async fn did_cache_test() {
     let (mut service, _) = LspService::build(ServerState::new)
@@ -152,6 +270,43 @@ async fn lsp_syncs_with_workspace_edits() {
     shutdown_and_exit(&mut service).await;
 }
 
+#[tokio::test]
+async fn inline_value() {
+    let server = ServerState::default();
+    let uri = open(
+        &server,
+        test_fixtures_dir().join("tokens/variables/src/main.sw"),
+    )
+    .await;
+    let params = InlineValueParams {
+        work_done_progress_params: Default::default(),
+        text_document: TextDocumentIdentifier { uri },
+        range: Range::new(Position::new(0, 0), Position::new(63, 0)),
+        // Stopped at `let variable3 = false;` in `main`.
+        context: InlineValueContext {
+            frame_id: 0,
+            stopped_location: Range::new(Position::new(21, 4), Position::new(21, 26)),
+        },
+    };
+    let values = request::handle_inline_value(&server, params)
+        .unwrap()
+        .unwrap();
+    let names: Vec<_> = values
+        .iter()
+        .map(|value| match value {
+            InlineValue::VariableLookup(lookup) => lookup.variable_name.clone().unwrap(),
+            value => panic!("unexpected inline value: {value:?}"),
+        })
+        .collect();
+    assert!(names.contains(&"variable1".to_string()));
+    assert!(names.contains(&"variable2".to_string()));
+    assert!(names.contains(&"variable3".to_string()));
+    // Variables declared after the stopped location and those of other functions are excluded.
+    assert!(!names.contains(&"variable4".to_string()));
+    assert!(!names.contains(&"variable".to_string()));
+    let _ = server.shutdown_server();
+}
+
 #[tokio::test]
 async fn show_ast() {
     let server = ServerState::default();
@@ -160,6 +315,239 @@ async fn show_ast() {
     let _ = server.shutdown_server();
 }
 
+#[tokio::test]
+async fn show_ast_paged() {
+    let server = ServerState::default();
+    let uri = open(&server, e2e_test_dir().join("src/main.sw")).await;
+    let save_path = std::env::temp_dir().join("show_ast_paged");
+    fs::create_dir_all(&save_path).unwrap();
+    lsp::show_ast_request(
+        &server,
+        &uri,
+        "parsed",
+        Some(Url::from_file_path(&save_path).unwrap()),
+    )
+    .await;
+    let ast = fs::read_to_string(save_path.join("parsed.rs")).unwrap();
+
+    let pages = lsp::show_ast_paged_request(&server, &uri, "parsed", 1024).await;
+    assert!(pages.len() > 1);
+    assert!(pages.iter().all(|page| page.len() <= 1024));
+    // The file ends with a newline after the AST.
+    assert_eq!(format!("{}\n", pages.concat()), ast);
+    let _ = server.shutdown_server();
+}
+
+#[tokio::test]
+async fn document_color() {
+    let server = ServerState::default();
+    let uri = open(
+        &server,
+        test_fixtures_dir().join("tokens/consts/src/main.sw"),
+    )
+    .await;
+    let literal = "0x9ae5b658754e096e4d681c548daf46354495a437cc61492599e33fc64dcdc30c";
+    let range = Range {
+        start: Position::new(9, 36),
+        end: Position::new(9, 36 + literal.len() as u32),
+    };
+
+    let colors = request::handle_document_color(
+        &server,
+        DocumentColorParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+    )
+    .unwrap();
+    let color = colors
+        .iter()
+        .find(|color| color.range == range)
+        .expect("a color for the b256 literal");
+    assert_eq!(color.color.red, 154.0 / 255.0);
+    assert_eq!(color.color.green, 229.0 / 255.0);
+    assert_eq!(color.color.blue, 182.0 / 255.0);
+
+    let presentations = request::handle_color_presentation(
+        &server,
+        ColorPresentationParams {
+            text_document: TextDocumentIdentifier { uri },
+            color: color.color,
+            range,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+    )
+    .unwrap();
+    let literals: Vec<_> = presentations
+        .iter()
+        .map(|presentation| presentation.text_edit.as_ref().unwrap().new_text.clone())
+        .collect();
+    assert_eq!(literals[0], literal);
+    assert_eq!(literals[1], format!("0x{}", literal[2..].to_uppercase()));
+    assert!(literals[2].starts_with("0b10011010"));
+    let _ = server.shutdown_server();
+}
+
+#[tokio::test]
+async fn disabled_features() {
+    let features = FeaturesConfig {
+        inlay_hints: false,
+        semantic_tokens: false,
+        code_lens: false,
+        document_symbols: false,
+    };
+    let capabilities = sway_lsp::server_capabilities(&features);
+    assert!(capabilities.inlay_hint_provider.is_none());
+    assert!(capabilities.semantic_tokens_provider.is_none());
+    assert!(capabilities.code_lens_provider.is_none());
+    assert!(capabilities.document_symbol_provider.is_none());
+
+    let server = ServerState::default();
+    let uri = open(&server, runnables_test_dir().join("src/main.sw")).await;
+    let mut config = Config::default();
+    config.features = features;
+    server.update_config(config).await;
+    let text_document = TextDocumentIdentifier { uri };
+    let code_lens = request::handle_code_lens(
+        &server,
+        CodeLensParams {
+            text_document: text_document.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+    );
+    assert!(code_lens.unwrap().is_none());
+    let semantic_tokens = request::handle_semantic_tokens_full(
+        &server,
+        SemanticTokensParams {
+            text_document: text_document.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+    );
+    assert!(semantic_tokens.unwrap().is_none());
+    let document_symbols = request::handle_document_symbol(
+        &server,
+        DocumentSymbolParams {
+            text_document,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+    );
+    assert!(document_symbols.unwrap().is_none());
+    let _ = server.shutdown_server();
+}
+
+#[tokio::test]
+async fn build_mode_off() {
+    let server = ServerState::default();
+    let mut config = Config::default();
+    config.build = BuildConfig {
+        mode: BuildMode::Off,
+    };
+    server.update_config(config).await;
+    let uri = open(&server, runnables_test_dir().join("src/main.sw")).await;
+    let text_document = TextDocumentIdentifier { uri };
+
+    // Syntactic features still work.
+    let document_symbols = request::handle_document_symbol(
+        &server,
+        DocumentSymbolParams {
+            text_document: text_document.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+    );
+    assert!(matches!(
+        document_symbols.unwrap(),
+        Some(DocumentSymbolResponse::Flat(symbols)) if !symbols.is_empty()
+    ));
+    let completion_params = |trigger_character: Option<&str>| CompletionParams {
+        text_document_position: TextDocumentPositionParams {
+            text_document: text_document.clone(),
+            position: Position::new(0, 0),
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+        context: Some(CompletionContext {
+            trigger_kind: CompletionTriggerKind::INVOKED,
+            trigger_character: trigger_character.map(str::to_string),
+        }),
+    };
+    let completion = request::handle_completion(&server, completion_params(None));
+    assert!(matches!(
+        completion.unwrap(),
+        Some(CompletionResponse::Array(items)) if items.iter().any(|item| item.label == "fn")
+    ));
+    let completion = request::handle_completion(&server, completion_params(Some(".")));
+    assert!(completion.unwrap().is_none());
+
+    // Runnables need the typed program, so there are no code lenses.
+    let code_lens = request::handle_code_lens(
+        &server,
+        CodeLensParams {
+            text_document,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        },
+    );
+    assert_eq!(code_lens.unwrap(), Some(vec![]));
+    let _ = server.shutdown_server();
+}
+
+#[tokio::test]
+async fn run_test() {
+    let server = ServerState::default();
+    let uri = open(&server, runnables_test_dir().join("src/main.sw")).await;
+
+    let result = lsp::run_test_request(&server, &uri, "script_multi_test::test_bar")
+        .await
+        .unwrap();
+    assert_eq!(result.status, TestStatus::Passed);
+    assert_eq!(result.logs.len(), 1);
+    assert!(result.gas_used.is_some());
+    assert!(result.error.is_none());
+
+    // Tests are only found by their fully-qualified name.
+    for test_name in [
+        "script_multi_test::test_baz",
+        "test_bar",
+        "script_multi_test::other::test_bar",
+    ] {
+        let result = lsp::run_test_request(&server, &uri, test_name)
+            .await
+            .unwrap();
+        assert_eq!(result.status, TestStatus::NotFound);
+        assert!(result.error.is_some());
+    }
+    let _ = server.shutdown_server();
+}
+
+#[tokio::test]
+async fn filecheck() {
+    let server = ServerState::default();
+    let uri = open(
+        &server,
+        test_fixtures_dir().join("tokens/declarations/src/main.sw"),
+    )
+    .await;
+
+    let result = lsp::filecheck_request(&server, &uri, "# check: Shape\n# check: Square", "typed");
+    assert!(result.unwrap().success);
+
+    let result = lsp::filecheck_request(&server, &uri, "# check: Square\n# check: Circle", "typed");
+    assert!(!result.unwrap().success);
+
+    let result = lsp::filecheck_request(&server, &uri, "Square", "parsed").unwrap();
+    assert!(!result.success);
+    assert_eq!(result.report, "No FileCheck directives found.");
+
+    assert!(lsp::filecheck_request(&server, &uri, "# check: Square", "optimized").is_none());
+    let _ = server.shutdown_server();
+}
+
 #[tokio::test]
 async fn visualize() {
     let server = ServerState::default();
@@ -168,6 +556,122 @@ async fn visualize() {
     let _ = server.shutdown_server();
 }
 
+#[tokio::test]
+async fn expand_generic() {
+    let server = ServerState::default();
+    let uri = open(
+        &server,
+        test_fixtures_dir().join("tokens/turbofish/src/main.sw"),
+    )
+    .await;
+
+    // fun::<Option<u32>>(Option::None);
+    let expanded = lsp::expand_generic_request(&server, &uri, 15, 5).unwrap();
+    assert!(expanded.contains("// T = Option<u32>"));
+    assert!(expanded.contains("fn fun(t: Option<u32>)"));
+
+    // fun::<Option<Result<u32, u32>>>(Option::None);
+    let expanded = lsp::expand_generic_request(&server, &uri, 20, 5).unwrap();
+    assert!(expanded.contains("// T = Option<Result<u32, u32>>"));
+
+    // let s = A{b: 0};
+    assert!(lsp::expand_generic_request(&server, &uri, 14, 9).is_none());
+    let _ = server.shutdown_server();
+}
+
+#[tokio::test]
+async fn abi_methods() {
+    let server = ServerState::default();
+    let uri = open(&server, test_fixtures_dir().join("tokens/abi/src/main.sw")).await;
+    let methods = lsp::abi_methods_request(&server, &uri).unwrap();
+    assert_eq!(
+        methods,
+        vec![AbiMethod {
+            name: "test_function".to_string(),
+            uri: Some(uri.clone()),
+            range: Range {
+                start: Position {
+                    line: 10,
+                    character: 7,
+                },
+                end: Position {
+                    line: 10,
+                    character: 20,
+                },
+            },
+            // The first 4 bytes of the SHA-256 hash of `test_function()`.
+            selector: Some("0x2151bd4b".to_string()),
+            error: None,
+        }]
+    );
+
+    // Scripts have no entry points of their own.
+    let uri = open(&server, test_fixtures_dir().join("completion/src/main.sw")).await;
+    assert!(lsp::abi_methods_request(&server, &uri).is_none());
+    let _ = server.shutdown_server();
+}
+
+#[tokio::test]
+async fn moniker() {
+    let server = ServerState::default();
+    let uri = open(
+        &server,
+        test_fixtures_dir().join("tokens/structs/src/main.sw"),
+    )
+    .await;
+    let check = |line, character, scheme: &str, identifier: &str, kind| {
+        let monikers = lsp::moniker_request(&server, &uri, line, character).unwrap();
+        assert_eq!(monikers.len(), 1);
+        assert_eq!(monikers[0].scheme, scheme);
+        assert_eq!(monikers[0].identifier, identifier);
+        assert_eq!(monikers[0].kind, Some(kind));
+    };
+
+    // let x = Simple {
+    check(24, 12, "sway", "structs::Simple", MonikerKind::Export);
+    check(24, 8, "local", "x:24:8", MonikerKind::Local);
+    // x: u8,
+    check(20, 4, "sway", "structs::Simple::x", MonikerKind::Export);
+    // First: (),
+    check(4, 4, "sway", "structs::Data::First", MonikerKind::Export);
+    // o: Option<Identity>,
+    check(16, 8, "sway", "std::option::Option", MonikerKind::Import);
+
+    // contract;
+    assert!(lsp::moniker_request(&server, &uri, 0, 2).is_none());
+    let _ = server.shutdown_server();
+}
+
+#[tokio::test]
+async fn moniker_for_methods() {
+    let server = ServerState::default();
+    let uri = open(
+        &server,
+        test_fixtures_dir().join("tokens/impls/src/main.sw"),
+    )
+    .await;
+    let check = |line, character, identifier: &str| {
+        let monikers = lsp::moniker_request(&server, &uri, line, character).unwrap();
+        assert_eq!(monikers.len(), 1);
+        assert_eq!(monikers[0].scheme, "sway");
+        assert_eq!(monikers[0].identifier, identifier);
+        assert_eq!(monikers[0].unique, UniquenessLevel::Scheme);
+    };
+
+    // Methods with the same name are qualified with the type they're implemented for.
+    check(14, 7, "impls::Foo::new");
+    check(20, 7, "impls::Bar::new");
+    // fn new() -> Foo {
+    check(35, 3, "impls::new");
+    // Foo::new()
+    check(36, 9, "impls::Foo::new");
+    // fn name() -> u64;
+    check(26, 7, "impls::Named::name");
+    // impl Named for Foo {
+    check(30, 7, "impls::<Foo as Named>::name");
+    let _ = server.shutdown_server();
+}
+
 //------------------- GO TO DEFINITION -------------------//
 
 #[tokio::test]
@@ -187,6 +691,62 @@ async fn go_to_definition() {
     let _ = server.shutdown_server();
 }
 
+#[tokio::test]
+async fn go_to_declaration() {
+    let server = ServerState::default();
+    let uri = open(
+        &server,
+        test_fixtures_dir().join("tokens/declarations/src/main.sw"),
+    )
+    .await;
+
+    // square.area()
+    let mut go_to = GotoDefinition {
+        req_uri: &uri,
+        req_line: 23,
+        req_char: 16,
+        def_line: 3,
+        def_start_char: 7,
+        def_end_char: 11,
+        def_path: "sway-lsp/tests/fixtures/tokens/declarations/src/main.sw",
+    };
+    lsp::declaration_check(&server, &go_to);
+    // fn area(self) -> u64 {
+    go_to.req_line = 11;
+    go_to.req_char = 8;
+    lsp::declaration_check(&server, &go_to);
+
+    // Goto definition still jumps to the implementation.
+    let mut go_to_impl = GotoDefinition {
+        def_line: 11,
+        ..go_to
+    };
+    lsp::definition_check_with_req_offset(&server, &mut go_to_impl, 23, 16);
+
+    // fn square_area(side: u64) -> u64 {
+    let go_to = GotoDefinition {
+        req_line: 21,
+        req_char: 8,
+        def_line: 17,
+        def_start_char: 7,
+        def_end_char: 18,
+        ..go_to
+    };
+    lsp::declaration_check(&server, &go_to);
+
+    // Symbols other than trait and ABI methods are declared where they are defined.
+    let go_to = GotoDefinition {
+        req_line: 23,
+        req_char: 9,
+        def_line: 22,
+        def_start_char: 12,
+        def_end_char: 18,
+        ..go_to
+    };
+    lsp::declaration_check(&server, &go_to);
+    let _ = server.shutdown_server();
+}
+
 #[tokio::test]
 async fn go_to_definition_for_fields() {
     let server = ServerState::default();
@@ -1683,6 +2243,60 @@ async fn rename() {
     let _ = server.shutdown_server();
 }
 
+#[tokio::test]
+async fn rename_struct_fields() {
+    let server = ServerState::default();
+    let uri = open(&server, test_fixtures_dir().join("renaming/src/main.sw")).await;
+    let edits = |line, character, new_name| {
+        let rename = Rename {
+            req_uri: &uri,
+            req_line: line,
+            req_char: character,
+            new_name,
+        };
+        let mut edits: Vec<_> = lsp::rename_request(&server, &rename)
+            .changes
+            .unwrap()
+            .into_values()
+            .flatten()
+            .map(|edit| {
+                (
+                    edit.range.start.line,
+                    edit.range.start.character,
+                    edit.new_text,
+                )
+            })
+            .collect();
+        edits.sort();
+        edits
+    };
+
+    // Renaming `Rect::x` updates its shorthand initializer, but not the fields of `Point`.
+    assert_eq!(
+        edits(65, 4, "left"),
+        vec![
+            (65, 4, "left".to_string()),
+            (71, 22, "left: x".to_string()),
+            (73, 16, "left".to_string()),
+            (74, 19, "left".to_string()),
+            (74, 27, "left".to_string()),
+        ]
+    );
+
+    // Renaming `Point::x` doesn't touch the fields of `Rect`.
+    assert_eq!(
+        edits(12, 4, "px"),
+        vec![
+            (12, 4, "px".to_string()),
+            (22, 24, "px".to_string()),
+            (23, 22, "px".to_string()),
+            (72, 24, "px".to_string()),
+            (73, 25, "px".to_string()),
+        ]
+    );
+    let _ = server.shutdown_server();
+}
+
 #[tokio::test]
 async fn publish_diagnostics_dead_code_warning() {
     let (mut service, socket) = LspService::new(ServerState::new);
@@ -1822,6 +2436,11 @@ lsp_capability_test!(
     code_actions::code_action_auto_import_alias_request,
     test_fixtures_dir().join("auto_import/src/main.sw")
 );
+lsp_capability_test!(
+    code_action_missing_trait_methods,
+    code_actions::code_action_missing_trait_methods_request,
+    test_fixtures_dir().join("missing_trait_methods/src/main.sw")
+);
 lsp_capability_test!(
     code_lens,
     lsp::code_lens_request,
@@ -1837,6 +2456,11 @@ lsp_capability_test!(
     lsp::completion_request,
     test_fixtures_dir().join("completion/src/main.sw")
 );
+lsp_capability_test!(
+    completion_resolve,
+    lsp::completion_resolve_request,
+    test_fixtures_dir().join("completion_resolve/src/main.sw")
+);
 
 // This method iterates over all of the examples in the e2e langauge should_pass dir
 // and saves the lexed, parsed, and typed ASTs to the users home directory.
@@ -1,7 +1,7 @@
 use std::ops::{Not, Shl, Shr};
 
 use num_bigint::{BigUint, ParseBigIntError, TryFromBigIntError};
-use num_traits::Zero;
+use num_traits::{One, Zero};
 use thiserror::Error;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
@@ -47,6 +47,41 @@ impl U256 {
         let r = (&self.0).shl(other);
         (r.bits() <= 256).then_some(Self(r))
     }
+
+    /// The largest value that fits in 256 bits.
+    pub fn max_value() -> U256 {
+        Self((BigUint::one() << 256u32) - 1u32)
+    }
+
+    pub fn wrapping_add(&self, other: &U256) -> U256 {
+        Self::wrap(&self.0 + &other.0)
+    }
+
+    pub fn wrapping_sub(&self, other: &U256) -> U256 {
+        Self::wrap((BigUint::one() << 256u32) + &self.0 - &other.0)
+    }
+
+    pub fn wrapping_mul(&self, other: &U256) -> U256 {
+        Self::wrap(&self.0 * &other.0)
+    }
+
+    pub fn saturating_add(&self, other: &U256) -> U256 {
+        self.checked_add(other).unwrap_or_else(Self::max_value)
+    }
+
+    pub fn saturating_sub(&self, other: &U256) -> U256 {
+        self.checked_sub(other)
+            .unwrap_or_else(|| Self(BigUint::zero()))
+    }
+
+    pub fn saturating_mul(&self, other: &U256) -> U256 {
+        self.checked_mul(other).unwrap_or_else(Self::max_value)
+    }
+
+    /// Truncates `value` to its lowest 256 bits.
+    fn wrap(value: BigUint) -> U256 {
+        Self(value % (BigUint::one() << 256u32))
+    }
 }
 
 impl std::fmt::Display for U256 {
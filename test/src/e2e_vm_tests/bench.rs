@@ -0,0 +1,146 @@
+//! Measures compiler throughput by repeatedly compiling e2e test programs, without running or
+//! validating them.
+//!
+//! Only tests that are expected to compile are benchmarked, and the usual filters select the
+//! subset to compile. The results are printed and emitted as JSON for trend tracking:
+//!
+//! ```json
+//! {
+//!   "target": "fuel",
+//!   "programs": 42,
+//!   "iterations": 3,
+//!   "compilations": 126,
+//!   "total_secs": 61.2,
+//!   "programs_per_sec": 2.06,
+//!   "median_ms": 402.1,
+//!   "p95_ms": 1103.7
+//! }
+//! ```
+
+use super::{discover_test_configs, glob_matches, harness, TestCategory, TestDescription};
+use crate::{FilterConfig, RunConfig};
+use anyhow::{bail, Result};
+use colored::*;
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// Compiles the tests selected by `filter_config` `iterations` times and reports the number of
+/// programs compiled per second and the median and 95th percentile compile latency.
+///
+/// The JSON report is written to `output` if given, otherwise it's printed to stdout.
+pub async fn bench(
+    filter_config: &FilterConfig,
+    run_config: &RunConfig,
+    iterations: usize,
+    output: Option<&Path>,
+) -> Result<()> {
+    if iterations == 0 {
+        bail!("The number of benchmark iterations must be at least 1");
+    }
+    let categories = filter_config
+        .categories
+        .iter()
+        .map(|category| category.parse::<TestCategory>())
+        .collect::<Result<Vec<_>>>()?;
+    let mut tests = discover_test_configs(filter_config.exact.as_deref())?;
+    tests.retain(|t| {
+        matches!(
+            t.category,
+            TestCategory::Compiles
+                | TestCategory::Runs
+                | TestCategory::RunsWithContract
+                | TestCategory::UnitTestsPass
+        ) && t.supported_targets.contains(&run_config.build_target)
+            && (categories.is_empty() || categories.contains(&t.category))
+            && is_selected(filter_config, t)
+    });
+    if filter_config.first_only {
+        tests.truncate(1);
+    }
+    if tests.is_empty() {
+        bail!("No tests were selected for benchmarking");
+    }
+
+    let mut latencies = Vec::with_capacity(tests.len() * iterations);
+    let started = Instant::now();
+    for iteration in 1..=iterations {
+        println!("Iteration {iteration}/{iterations}");
+        for test in &tests {
+            let compile_started = Instant::now();
+            let (result, out) = harness::run_and_capture_output(|| {
+                harness::compile_to_bytes(&test.name, run_config)
+            })
+            .await;
+            let latency = compile_started.elapsed();
+            if let Err(err) = result {
+                println!("{}", textwrap::indent(&out, "     "));
+                bail!("Failed to compile {}: {err}", test.name);
+            }
+            if run_config.verbose {
+                println!("  {} {:?}", test.name.bold(), latency);
+            }
+            latencies.push(latency);
+        }
+    }
+    let total = started.elapsed();
+
+    latencies.sort();
+    let programs_per_sec = latencies.len() as f64 / total.as_secs_f64();
+    let median = percentile(&latencies, 50);
+    let p95 = percentile(&latencies, 95);
+    println!("_________________________________");
+    println!(
+        "Compiled {} programs {} times in {:.2?}: {:.2} programs/sec, median {:.2?}, p95 {:.2?}",
+        tests.len(),
+        iterations,
+        total,
+        programs_per_sec,
+        median,
+        p95
+    );
+
+    let report = serde_json::json!({
+        "target": run_config.build_target,
+        "programs": tests.len(),
+        "iterations": iterations,
+        "compilations": latencies.len(),
+        "total_secs": total.as_secs_f64(),
+        "programs_per_sec": programs_per_sec,
+        "median_ms": median.as_secs_f64() * 1000.0,
+        "p95_ms": p95.as_secs_f64() * 1000.0,
+    });
+    let report = serde_json::to_string_pretty(&report)?;
+    match output {
+        Some(path) => std::fs::write(path, report)?,
+        None => println!("{report}"),
+    }
+    Ok(())
+}
+
+/// Applies the regex and glob filters of `filter_config` to `test`.
+fn is_selected(filter_config: &FilterConfig, test: &TestDescription) -> bool {
+    filter_config
+        .include
+        .as_ref()
+        .map_or(true, |include| include.is_match(&test.name))
+        && filter_config
+            .exclude
+            .as_ref()
+            .map_or(true, |exclude| !exclude.is_match(&test.name))
+        && filter_config
+            .include_glob
+            .as_ref()
+            .map_or(true, |include| glob_matches(include, &test.name))
+        && filter_config
+            .exclude_glob
+            .as_ref()
+            .map_or(true, |exclude| !glob_matches(exclude, &test.name))
+}
+
+/// Returns the `p`th percentile of the sorted `latencies`, using the nearest-rank method.
+fn percentile(latencies: &[Duration], p: usize) -> Duration {
+    let rank = (p * latencies.len() + 99) / 100;
+    latencies[rank.saturating_sub(1)]
+}
@@ -0,0 +1,83 @@
+//! A cache of contract deployments that is persisted to disk, so that repeated `run_on_node`
+//! runs against the same node can reuse contracts deployed by previous runs.
+//!
+//! The cache file is a JSON object mapping each contract path to the hash of the bytecode that
+//! was deployed and the resulting contract ID:
+//!
+//! ```json
+//! {
+//!   "test_contracts/basic_storage": {
+//!     "bytecode_hash": "0x...",
+//!     "contract_id": "0x..."
+//!   }
+//! }
+//! ```
+//!
+//! An entry is only reused when the hash of the freshly compiled bytecode matches, otherwise
+//! the contract is redeployed and the entry is replaced.
+
+use anyhow::{anyhow, Result};
+use fuel_vm::fuel_crypto::Hasher;
+use fuel_vm::prelude::*;
+use serde_json::{json, Map, Value};
+use std::{fs, path::Path, str::FromStr};
+
+pub(crate) struct DeployCache {
+    entries: Map<String, Value>,
+}
+
+impl DeployCache {
+    /// Reads the cache from `path`, or returns an empty cache if the file doesn't exist yet.
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self {
+                entries: Map::new(),
+            });
+        }
+        let contents = fs::read_to_string(path)?;
+        match serde_json::from_str(&contents)? {
+            Value::Object(entries) => Ok(Self { entries }),
+            _ => Err(anyhow!(
+                "Malformed deploy cache '{}', expected a JSON object.",
+                path.display()
+            )),
+        }
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.entries)?)?;
+        Ok(())
+    }
+
+    /// Returns the contract ID deployed for `contract_path`, unless the cached deployment was
+    /// made with different bytecode.
+    pub(crate) fn get(&self, contract_path: &str, bytecode_hash: &str) -> Option<ContractId> {
+        let entry = self.entries.get(contract_path)?;
+        if entry.get("bytecode_hash")?.as_str()? != bytecode_hash {
+            return None;
+        }
+        ContractId::from_str(entry.get("contract_id")?.as_str()?).ok()
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        contract_path: String,
+        bytecode_hash: String,
+        contract_id: ContractId,
+    ) {
+        self.entries.insert(
+            contract_path,
+            json!({
+                "bytecode_hash": bytecode_hash,
+                "contract_id": format!("0x{contract_id:x}"),
+            }),
+        );
+    }
+}
+
+pub(crate) fn bytecode_hash(bytecode: &[u8]) -> String {
+    format!("0x{}", Hasher::hash(bytecode))
+}
@@ -2,44 +2,98 @@ use anyhow::{anyhow, bail, Result};
 use colored::Colorize;
 use forc_client::{
     cmd::{Deploy as DeployCommand, Run as RunCommand},
-    op::{deploy, run},
+    op::{deploy, deploy_pkg, run},
     NodeTarget,
 };
-use forc_pkg::{Built, BuiltPackage};
+use forc_pkg::{Built, BuiltPackage, PackageManifestFile};
 use fuel_tx::TransactionBuilder;
 use fuel_vm::checked_transaction::builder::TransactionBuilderExt;
 use fuel_vm::fuel_tx;
 use fuel_vm::interpreter::Interpreter;
 use fuel_vm::prelude::*;
+use fuels_accounts::{provider::Provider, wallet::WalletUnlocked, Account};
+use fuels_core::types::{
+    bech32::{Bech32Address, Bech32ContractId},
+    transaction::TxParameters,
+};
 use futures::Future;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use regex::{Captures, Regex};
-use std::{fs, io::Read, path::PathBuf, str::FromStr};
-use sway_core::{asm_generation::ProgramABI, BuildTarget};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+use sway_core::{asm_generation::ProgramABI, BuildConfig, BuildTarget, Engines};
+use sway_error::handler::Handler;
+use tokio::sync::Mutex;
 
 use super::RunConfig;
 
 pub const NODE_URL: &str = "http://127.0.0.1:4000";
 pub const SECRET_KEY: &str = "de97d8624a438121b86a1956544bd72ed68cd69f2c99555b08b1e8c51ffd511c";
 
+/// The error of a test reaching a path of the harness which isn't implemented yet, e.g. an EVM exit
+/// reason no test expects. It fails the test, unless `--strict-unimplemented` is passed, in which
+/// case it aborts the whole run.
+#[derive(Debug)]
+pub(crate) struct UnimplementedHarnessPath(pub(crate) String);
+
+impl std::fmt::Display for UnimplementedHarnessPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unimplemented harness path: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnimplementedHarnessPath {}
+
+/// Serializes the redirections of stdout and stderr, which are process wide and can't be nested,
+/// so that tests running concurrently neither fail to redirect them nor capture each other's output.
+static CAPTURE_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Runs `func` and returns its result along with everything it wrote to stdout and stderr,
+/// including the compiler diagnostics and the output of `tracing`.
+///
+/// The output never contains ANSI escape sequences, so that FileCheck directives match it the same
+/// way whether or not the tests run in a terminal.
 pub(crate) async fn run_and_capture_output<F, Fut, T>(func: F) -> (T, String)
 where
     F: FnOnce() -> Fut,
     Fut: Future<Output = T>,
 {
+    let _guard = CAPTURE_LOCK.lock().await;
     let mut output = String::new();
 
     // Capture both stdout and stderr to buffers, run the code and save to a string.
     let mut buf_stdout = gag::BufferRedirect::stdout().unwrap();
     let mut buf_stderr = gag::BufferRedirect::stderr().unwrap();
 
+    // Whether `colored` colors its output depends on the terminal, so it's turned off while
+    // capturing. Output that is always colored, like the compiler diagnostics, is stripped below.
+    colored::control::set_override(false);
     let result = func().await;
+    colored::control::unset_override();
 
+    // Output that is still buffered would otherwise only be written once the redirections are
+    // dropped, and go to the terminal instead.
+    let _ = std::io::stdout().flush();
+    let _ = std::io::stderr().flush();
     buf_stdout.read_to_string(&mut output).unwrap();
-    buf_stderr.read_to_string(&mut output).unwrap();
+    let mut stderr_output = String::new();
+    buf_stderr.read_to_string(&mut stderr_output).unwrap();
     drop(buf_stdout);
     drop(buf_stderr);
+    if !stderr_output.is_empty() {
+        if !output.is_empty() && !output.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(&stderr_output);
+    }
+    output = strip_ansi_escape_sequences(&output);
 
     if cfg!(windows) {
         // In windows output error and warning path files start with \\?\
@@ -55,6 +109,12 @@ where
     (result, output)
 }
 
+/// Removes the ANSI escape sequences, e.g. `\x1b[1;31m`, from `text`.
+fn strip_ansi_escape_sequences(text: &str) -> String {
+    let regex = Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").unwrap();
+    regex.replace_all(text, "").into_owned()
+}
+
 pub(crate) async fn deploy_contract(file_name: &str, run_config: &RunConfig) -> Result<ContractId> {
     // build the contract
     // deploy it
@@ -83,20 +143,142 @@ pub(crate) async fn deploy_contract(file_name: &str, run_config: &RunConfig) ->
     })
 }
 
-/// Run a given project against a node. Assumes the node is running at localhost:4000.
+/// Deploys the contract with `storage_slots` on top of its initial storage, replacing the slots
+/// of the same keys.
+pub(crate) async fn deploy_contract_with_storage(
+    file_name: &str,
+    run_config: &RunConfig,
+    storage_slots: &[fuel_tx::StorageSlot],
+) -> Result<ContractId> {
+    let mut built_pkg = match compile_to_bytes(file_name, run_config).await? {
+        Built::Package(built_pkg) => built_pkg.as_ref().clone(),
+        Built::Workspace(_) => bail!("seeding the storage of workspaces is not supported"),
+    };
+    println!(" Deploying {} with seeded storage ...", file_name.bold());
+    built_pkg.storage_slots.retain(|slot| {
+        !storage_slots
+            .iter()
+            .any(|seeded| seeded.key() == slot.key())
+    });
+    built_pkg.storage_slots.extend_from_slice(storage_slots);
+
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let command = DeployCommand {
+        pkg: forc_client::cmd::deploy::Pkg {
+            path: Some(format!(
+                "{manifest_dir}/src/e2e_vm_tests/test_programs/{file_name}"
+            )),
+            terse: !run_config.verbose,
+            locked: run_config.locked,
+            ..Default::default()
+        },
+        signing_key: Some(SecretKey::from_str(SECRET_KEY).unwrap()),
+        default_salt: true,
+        ..Default::default()
+    };
+    let manifest = built_pkg.descriptor.manifest_file.clone();
+    deploy_pkg(&command, &manifest, &built_pkg, fuel_tx::Salt::default())
+        .await
+        .map(|deployed| deployed.id)
+}
+
+/// The state the node is seeded with before a `run_on_node` test runs, from the `node_setup`
+/// table of its `test.toml`.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct NodeSetup {
+    /// The coins sent from the wallet of the harness before running the script.
+    pub(crate) coins: Vec<SeededCoin>,
+    /// The storage slots each contract of the test, by path, is deployed with on top of its
+    /// initial storage.
+    pub(crate) storage: HashMap<String, Vec<fuel_tx::StorageSlot>>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SeededCoin {
+    pub(crate) recipient: CoinRecipient,
+    pub(crate) amount: u64,
+    pub(crate) asset_id: fuel_tx::AssetId,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum CoinRecipient {
+    Address(fuel_tx::Address),
+    /// One of the contracts of the test, by path.
+    Contract(String),
+}
+
+/// Sends the seeded coins from the wallet of the harness, to the given addresses or to the
+/// contracts of the test, deployed with the IDs in `contracts`.
+async fn seed_coins(
+    coins: &[SeededCoin],
+    contracts: &[(String, fuel_tx::ContractId)],
+) -> Result<()> {
+    if coins.is_empty() {
+        return Ok(());
+    }
+    let provider = Provider::connect(NODE_URL).await?;
+    let wallet = WalletUnlocked::new_from_private_key(
+        SecretKey::from_str(SECRET_KEY).unwrap(),
+        Some(provider),
+    );
+    for coin in coins {
+        match &coin.recipient {
+            CoinRecipient::Address(address) => {
+                println!(" Seeding {} coins to 0x{address} ...", coin.amount);
+                wallet
+                    .transfer(
+                        &Bech32Address::from(*address),
+                        coin.amount,
+                        coin.asset_id,
+                        TxParameters::default(),
+                    )
+                    .await?;
+            }
+            CoinRecipient::Contract(contract_path) => {
+                let contract_id = contracts
+                    .iter()
+                    .find(|(path, _)| path == contract_path)
+                    .map(|(_, contract_id)| *contract_id)
+                    .ok_or_else(|| anyhow!("contract {contract_path} wasn't deployed"))?;
+                println!(
+                    " Seeding {} coins to {} ...",
+                    coin.amount,
+                    contract_path.bold()
+                );
+                wallet
+                    .force_transfer_to_contract(
+                        &Bech32ContractId::from(contract_id),
+                        coin.amount,
+                        coin.asset_id,
+                        TxParameters::default(),
+                    )
+                    .await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run a given project against a node, after seeding the node with the coins of `node_setup`.
+/// Assumes the node is running at localhost:4000.
+///
+/// `contracts` are the paths of the deployed contracts of the test and their IDs.
 pub(crate) async fn runs_on_node(
     file_name: &str,
     run_config: &RunConfig,
-    contract_ids: &[fuel_tx::ContractId],
+    contracts: &[(String, fuel_tx::ContractId)],
+    node_setup: &NodeSetup,
 ) -> (Result<Vec<fuel_tx::Receipt>>, String) {
     run_and_capture_output(|| async {
+        seed_coins(&node_setup.coins, contracts).await?;
+
         println!(" Running on node {} ...", file_name.bold());
         let manifest_dir = env!("CARGO_MANIFEST_DIR");
 
-        let mut contracts = Vec::<String>::with_capacity(contract_ids.len());
-        for contract_id in contract_ids {
+        let mut contract_ids = Vec::<String>::with_capacity(contracts.len());
+        for (_, contract_id) in contracts {
             let contract = format!("0x{contract_id:x}");
-            contracts.push(contract);
+            contract_ids.push(contract);
         }
 
         let command = RunCommand {
@@ -112,7 +294,7 @@ pub(crate) async fn runs_on_node(
                 node_url: Some(NODE_URL.into()),
                 ..Default::default()
             },
-            contract: Some(contracts),
+            contract: Some(contract_ids),
             signing_key: Some(SecretKey::from_str(SECRET_KEY).unwrap()),
             ..Default::default()
         };
@@ -127,6 +309,16 @@ pub(crate) async fn runs_on_node(
     .await
 }
 
+/// The optional features of the VM that tests are run against, which tests can require with the
+/// `vm_features` entry of their `test.toml`. Tests requiring features that aren't listed here are
+/// skipped, so features should be added as the VM gains them.
+pub(crate) const VM_FEATURES: &[&str] = &[
+    // Predicates, which are verified against the inputs of a transaction.
+    "predicates",
+    // The wide integer instructions used for `u256` arithmetic, e.g. `WQOP` and `WQCM`.
+    "wide_integers",
+];
+
 pub(crate) enum VMExecutionResult {
     Fuel(ProgramState, Vec<Receipt>),
     Evm(revm::ExecutionResult),
@@ -134,16 +326,19 @@ pub(crate) enum VMExecutionResult {
 }
 
 /// Very basic check that code does indeed run in the VM.
+///
+/// The randomized inputs of the transaction are generated from `seed`.
 pub(crate) fn runs_in_vm(
     script: BuiltPackage,
     script_data: Option<Vec<u8>>,
     witness_data: Option<Vec<Vec<u8>>>,
+    seed: u64,
 ) -> Result<VMExecutionResult> {
     match script.descriptor.target {
         BuildTarget::Fuel => {
             let storage = MemoryStorage::default();
 
-            let rng = &mut StdRng::seed_from_u64(2322u64);
+            let rng = &mut StdRng::seed_from_u64(seed);
             let maturity = 1.into();
             let script_data = script_data.unwrap_or_default();
             let block_height = (u32::MAX >> 1).into();
@@ -194,7 +389,10 @@ pub(crate) fn runs_in_vm(
 
             match result.out {
                 revm::TransactOut::None => Err(anyhow!("Could not create smart contract")),
-                revm::TransactOut::Call(_) => todo!(),
+                revm::TransactOut::Call(_) => Err(UnimplementedHarnessPath(
+                    "EVM call output when creating a contract".into(),
+                )
+                .into()),
                 revm::TransactOut::Create(ref _bytes, account_opt) => {
                     match account_opt {
                         Some(account) => {
@@ -205,7 +403,10 @@ pub(crate) fn runs_in_vm(
                             let result = evm.transact_commit();
                             Ok(VMExecutionResult::Evm(result))
                         }
-                        None => todo!(),
+                        None => Err(UnimplementedHarnessPath(
+                            "EVM contract creation without an account".into(),
+                        )
+                        .into()),
                     }
                 }
             }
@@ -234,10 +435,9 @@ pub(crate) fn runs_in_vm(
 
 /// Compiles the code and optionally captures the output of forc and the compilation.
 /// Returns a tuple with the result of the compilation, as well as the output.
-pub(crate) async fn compile_to_bytes(file_name: &str, run_config: &RunConfig) -> Result<Built> {
-    println!("Compiling {} ...", file_name.bold());
+fn build_opts(file_name: &str, run_config: &RunConfig) -> forc_pkg::BuildOpts {
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
-    let build_opts = forc_pkg::BuildOpts {
+    forc_pkg::BuildOpts {
         build_target: run_config.build_target,
         pkg: forc_pkg::PkgOpts {
             path: Some(format!(
@@ -249,7 +449,12 @@ pub(crate) async fn compile_to_bytes(file_name: &str, run_config: &RunConfig) ->
             ..Default::default()
         },
         ..Default::default()
-    };
+    }
+}
+
+pub(crate) async fn compile_to_bytes(file_name: &str, run_config: &RunConfig) -> Result<Built> {
+    println!("Compiling {} ...", file_name.bold());
+    let build_opts = build_opts(file_name, run_config);
     match std::panic::catch_unwind(|| forc_pkg::build_with_options(build_opts)) {
         Ok(result) => {
             // Print the result of the compilation (i.e., any errors Forc produces).
@@ -262,14 +467,127 @@ pub(crate) async fn compile_to_bytes(file_name: &str, run_config: &RunConfig) ->
     }
 }
 
+/// Lexes the entry module of the test, without parsing it, and prints the errors and warnings.
+pub(crate) fn lex_only(file_name: &str) -> Result<()> {
+    println!("Lexing {} ...", file_name.bold());
+    let (_, src) = read_entry_module(file_name)?;
+    let handler = Handler::default();
+    let result = sway_core::lex(src, &handler);
+    print_front_end_diagnostics(handler, result.is_ok(), "Lexing");
+    Ok(())
+}
+
+/// Parses the module tree of the test, without compiling it any further, and prints the errors
+/// and warnings.
+pub(crate) fn parse_only(file_name: &str, run_config: &RunConfig) -> Result<()> {
+    println!("Parsing {} ...", file_name.bold());
+    let (manifest, src) = read_entry_module(file_name)?;
+    let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+        manifest.entry_path(),
+        manifest.dir().to_path_buf(),
+        run_config.build_target,
+    );
+    let engines = Engines::default();
+    let handler = Handler::default();
+    let result = sway_core::parse(src, &handler, &engines, Some(&build_config));
+    print_front_end_diagnostics(handler, result.is_ok(), "Parsing");
+    Ok(())
+}
+
+fn read_entry_module(file_name: &str) -> Result<(PackageManifestFile, Arc<str>)> {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let manifest = PackageManifestFile::from_dir(&PathBuf::from(format!(
+        "{manifest_dir}/src/e2e_vm_tests/test_programs/{file_name}"
+    )))?;
+    let src = fs::read_to_string(manifest.entry_path())?;
+    Ok((manifest, src.into()))
+}
+
+fn print_front_end_diagnostics(handler: Handler, succeeded: bool, stage: &str) {
+    let (errors, warnings) = handler.consume();
+    for error in errors {
+        println!("error: {error}");
+    }
+    for warning in warnings {
+        println!("warning: {}", warning.to_friendly_warning_string());
+    }
+    if succeeded {
+        println!("{stage} succeeded.");
+    } else {
+        println!("{stage} failed.");
+    }
+}
+
+/// Writes the artifacts of a failed `run` test to a directory named after the test under
+/// `dump_dir`, and returns that directory.
+///
+/// The artifacts are the error the test failed with, the bytecode, the IR and the receipts of the
+/// run, if it got that far. The IR is printed by compiling the test again, so that it's only
+/// generated for failing tests.
+pub(crate) async fn dump_failure(
+    dump_dir: &Path,
+    file_name: &str,
+    compiled: &BuiltPackage,
+    receipts: Option<&[Receipt]>,
+    err: &anyhow::Error,
+    run_config: &RunConfig,
+) -> Result<PathBuf> {
+    let dir = dump_dir.join(file_name);
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("error.txt"), format!("{err}\n"))?;
+    fs::write(dir.join("bytecode.bin"), &compiled.bytecode.bytes)?;
+    if let Some(receipts) = receipts {
+        fs::write(dir.join("receipts.txt"), format!("{receipts:#?}\n"))?;
+    }
+
+    fs::write(dir.join("ir.txt"), print_ir(file_name, run_config).await?)?;
+    Ok(dir)
+}
+
+/// Writes the bytecode, ABI and IR of a compiled `run` test to a directory named after the test
+/// under `artifacts_dir`, whether the test passed or not, and returns that directory.
+pub(crate) async fn keep_artifacts(
+    artifacts_dir: &Path,
+    file_name: &str,
+    compiled: &BuiltPackage,
+    run_config: &RunConfig,
+) -> Result<PathBuf> {
+    let dir = artifacts_dir.join(file_name);
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("bytecode.bin"), &compiled.bytecode.bytes)?;
+    fs::write(
+        dir.join("abi.json"),
+        serde_json::to_string_pretty(&json_abi(compiled)?)?,
+    )?;
+    fs::write(dir.join("ir.txt"), print_ir(file_name, run_config).await?)?;
+    Ok(dir)
+}
+
+/// Compiles the test again, returning the IR printed by the compiler.
+async fn print_ir(file_name: &str, run_config: &RunConfig) -> Result<String> {
+    let mut build_opts = build_opts(file_name, run_config);
+    build_opts.print.ir = true;
+    let (result, ir) = run_and_capture_output(|| async {
+        std::panic::catch_unwind(|| forc_pkg::build_with_options(build_opts))
+            .map_err(|_| anyhow!("Compiler panic"))
+            .and_then(|result| result)
+    })
+    .await;
+    result?;
+    Ok(ir)
+}
+
 /// Compiles the project's unit tests, then runs all unit tests.
 /// Returns the tested package result.
+///
+/// Only the output of the compilation is captured. When running verbosely, the result of each
+/// unit test is printed as soon as it completes.
 pub(crate) async fn compile_and_run_unit_tests(
     file_name: &str,
     run_config: &RunConfig,
     capture_output: bool,
 ) -> (Result<Vec<forc_test::TestedPackage>>, String) {
-    run_and_capture_output(|| async {
+    let (built_tests, output) = run_and_capture_output(|| async {
         tracing::info!("Compiling {} ...", file_name.bold());
         let manifest_dir = env!("CARGO_MANIFEST_DIR");
         let path: PathBuf = [
@@ -292,19 +610,38 @@ pub(crate) async fn compile_and_run_unit_tests(
                 ..Default::default()
             })
         }) {
-            Ok(Ok(built_tests)) => {
-                let test_filter = None;
-                let tested = built_tests.run(forc_test::TestRunnerCount::Auto, test_filter)?;
-                match tested {
-                    forc_test::Tested::Package(tested_pkg) => Ok(vec![*tested_pkg]),
-                    forc_test::Tested::Workspace(tested_pkgs) => Ok(tested_pkgs),
-                }
-            }
-            Ok(Err(e)) => Err(e),
+            Ok(result) => result,
             Err(_) => Err(anyhow!("Compiler panic")),
         }
     })
-    .await
+    .await;
+
+    let on_test = |test: &forc_test::TestResult| {
+        if run_config.verbose {
+            let status = if test.passed() {
+                "ok".green()
+            } else {
+                "FAILED".red()
+            };
+            println!("      test {} ... {}", test.name, status);
+        }
+    };
+    let tested = built_tests.and_then(|built_tests| {
+        if run_config.verbose {
+            println!();
+        }
+        let test_filter = None;
+        let tested = built_tests.run_with_progress(
+            forc_test::TestRunnerCount::Auto,
+            test_filter,
+            &on_test,
+        )?;
+        Ok(match tested {
+            forc_test::Tested::Package(tested_pkg) => vec![*tested_pkg],
+            forc_test::Tested::Workspace(tested_pkgs) => tested_pkgs,
+        })
+    });
+    (tested, output)
 }
 
 pub(crate) fn test_json_abi(file_name: &str, built_package: &BuiltPackage) -> Result<()> {
@@ -341,11 +678,7 @@ pub(crate) fn test_json_abi(file_name: &str, built_package: &BuiltPackage) -> Re
 
 fn emit_json_abi(file_name: &str, built_package: &BuiltPackage) -> Result<()> {
     tracing::info!("ABI gen {} ...", file_name.bold());
-    let json_abi = match &built_package.program_abi {
-        ProgramABI::Fuel(abi) => serde_json::json!(abi),
-        ProgramABI::Evm(abi) => serde_json::json!(abi),
-        ProgramABI::MidenVM(_) => todo!(),
-    };
+    let json_abi = json_abi(built_package)?;
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
     let file = std::fs::File::create(format!(
         "{}/src/e2e_vm_tests/test_programs/{}/{}",
@@ -356,6 +689,14 @@ fn emit_json_abi(file_name: &str, built_package: &BuiltPackage) -> Result<()> {
     Ok(())
 }
 
+fn json_abi(built_package: &BuiltPackage) -> Result<serde_json::Value> {
+    Ok(match &built_package.program_abi {
+        ProgramABI::Fuel(abi) => serde_json::json!(abi),
+        ProgramABI::Evm(abi) => serde_json::json!(abi),
+        ProgramABI::MidenVM(_) => return Err(UnimplementedHarnessPath("MidenVM ABI".into()).into()),
+    })
+}
+
 pub(crate) fn test_json_storage_slots(file_name: &str, built_package: &BuiltPackage) -> Result<()> {
     emit_json_storage_slots(file_name, built_package)?;
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
@@ -395,3 +736,38 @@ fn emit_json_storage_slots(file_name: &str, built_package: &BuiltPackage) -> Res
     res?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn run_and_capture_output_captures_stderr() {
+        // Write to the handles directly, as the test harness intercepts `println!` and `eprintln!`.
+        let (result, output) = run_and_capture_output(|| async {
+            write!(std::io::stdout(), "compiling").unwrap();
+            writeln!(std::io::stderr(), "error: mismatched types").unwrap();
+            42
+        })
+        .await;
+        assert_eq!(result, 42);
+        assert_eq!(output, "compiling\nerror: mismatched types\n");
+    }
+
+    #[tokio::test]
+    async fn run_and_capture_output_has_no_ansi_escape_sequences() {
+        let (_, output) = run_and_capture_output(|| async {
+            // Colored by `colored`, if it thinks the output is a terminal...
+            writeln!(std::io::stdout(), "{}", "warning".yellow().bold()).unwrap();
+            // ...and colored unconditionally, like the compiler diagnostics.
+            writeln!(
+                std::io::stderr(),
+                "\x1b[1;31merror\x1b[0m: mismatched types"
+            )
+            .unwrap();
+        })
+        .await;
+        assert!(!output.contains('\x1b'), "{output:?}");
+        assert_eq!(output, "warning\nerror: mismatched types\n");
+    }
+}
@@ -0,0 +1,146 @@
+//! A JUnit XML report of an e2e test run, for CI systems that display test results natively.
+//!
+//! All tests are reported in a single `<testsuite>`. Each test is a `<testcase>` named after the
+//! last component of its path, with the rest of the path as its class name:
+//!
+//! ```xml
+//! <testcase classname="should_pass.language" name="basic_func_decl" time="0.412"/>
+//! ```
+//!
+//! Failing tests have a `<failure>` whose message is the error the test failed with, and whose
+//! text is the output captured while running it. Disabled tests, tests that are expected to fail
+//! and tests requiring unsupported VM features are reported as `<skipped>`.
+
+use anyhow::Result;
+use std::{fmt::Write, fs, path::Path, time::Duration};
+
+enum Outcome {
+    Passed,
+    Failed { message: String, output: String },
+    Skipped { message: String },
+}
+
+struct TestCase {
+    name: String,
+    time: Duration,
+    outcome: Outcome,
+}
+
+#[derive(Default)]
+pub(crate) struct JunitReport {
+    test_cases: Vec<TestCase>,
+}
+
+impl JunitReport {
+    pub(crate) fn passed(&mut self, name: &str, time: Duration) {
+        self.push(name, time, Outcome::Passed);
+    }
+
+    pub(crate) fn failed(&mut self, name: &str, time: Duration, message: &str, output: &str) {
+        let outcome = Outcome::Failed {
+            message: message.to_string(),
+            output: output.to_string(),
+        };
+        self.push(name, time, outcome);
+    }
+
+    pub(crate) fn skipped(&mut self, name: &str, time: Duration, message: &str) {
+        let outcome = Outcome::Skipped {
+            message: message.to_string(),
+        };
+        self.push(name, time, outcome);
+    }
+
+    fn push(&mut self, name: &str, time: Duration, outcome: Outcome) {
+        self.test_cases.push(TestCase {
+            name: name.to_string(),
+            time,
+            outcome,
+        });
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_xml())?;
+        Ok(())
+    }
+
+    fn to_xml(&self) -> String {
+        let count = |f: fn(&Outcome) -> bool| {
+            self.test_cases
+                .iter()
+                .filter(|test_case| f(&test_case.outcome))
+                .count()
+        };
+        let time: Duration = self.test_cases.iter().map(|test_case| test_case.time).sum();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"e2e_vm_tests\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\">",
+            self.test_cases.len(),
+            count(|outcome| matches!(outcome, Outcome::Failed { .. })),
+            count(|outcome| matches!(outcome, Outcome::Skipped { .. })),
+            time.as_secs_f64()
+        );
+        for test_case in &self.test_cases {
+            let (class_name, name) = test_case
+                .name
+                .rsplit_once('/')
+                .unwrap_or(("e2e_vm_tests", test_case.name.as_str()));
+            let _ = write!(
+                xml,
+                "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+                escape(&class_name.replace('/', ".")),
+                escape(name),
+                test_case.time.as_secs_f64()
+            );
+            let _ = match &test_case.outcome {
+                Outcome::Passed => writeln!(xml, "/>"),
+                Outcome::Failed { message, output } => writeln!(
+                    xml,
+                    ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                    escape(message),
+                    escape(output)
+                ),
+                Outcome::Skipped { message } => writeln!(
+                    xml,
+                    ">\n      <skipped message=\"{}\"/>\n    </testcase>",
+                    escape(message)
+                ),
+            };
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+/// Escapes `text` for use in XML attributes and text, dropping the ANSI color codes of colored
+/// output and other control characters that XML can't represent.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Skip ANSI escape sequences, e.g. `\x1b[1;31m`, up to and including their final letter.
+            '\x1b' => {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            '\n' | '\r' | '\t' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,9 +1,15 @@
 // Please take a look in test_programs/README.md for details on how these tests work.
 
+mod bench;
+mod deploy_cache;
 mod harness;
+mod junit;
 mod util;
+mod watch;
 
+use crate::e2e_vm_tests::deploy_cache::DeployCache;
 use crate::e2e_vm_tests::harness::run_and_capture_output;
+use crate::e2e_vm_tests::junit::JunitReport;
 use crate::{FilterConfig, RunConfig};
 
 use anyhow::{anyhow, bail, Result};
@@ -12,6 +18,8 @@ use colored::*;
 use core::fmt;
 use fuel_vm::fuel_tx;
 use fuel_vm::prelude::*;
+use futures::future::join_all;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use regex::Regex;
 use std::collections::HashSet;
 use std::io::stdout;
@@ -21,29 +29,61 @@ use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
+    time::{Duration, Instant},
 };
 use sway_core::BuildTarget;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OnceCell};
 use tracing::Instrument;
 
 use self::util::VecExt;
 
+pub use self::bench::bench;
+pub use self::watch::watch;
+
 #[derive(PartialEq, Debug)]
 enum TestCategory {
     Compiles,
     FailsToCompile,
+    /// Only the entry module is lexed, and the diagnostics are checked with FileCheck.
+    Lexes,
+    /// Only the module tree is parsed, and the diagnostics are checked with FileCheck.
+    Parses,
     Runs,
     RunsWithContract,
     UnitTestsPass,
     Disabled,
 }
 
+impl FromStr for TestCategory {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "run" => Ok(TestCategory::Runs),
+            "run_on_node" => Ok(TestCategory::RunsWithContract),
+            "fail" => Ok(TestCategory::FailsToCompile),
+            "compile" => Ok(TestCategory::Compiles),
+            "lex" => Ok(TestCategory::Lexes),
+            "parse" => Ok(TestCategory::Parses),
+            "disabled" => Ok(TestCategory::Disabled),
+            "unit_tests_pass" => Ok(TestCategory::UnitTestsPass),
+            other => Err(anyhow!("Unknown category '{}'.", other)),
+        }
+    }
+}
+
 #[derive(PartialEq)]
 enum TestResult {
     Result(Word),
     Return(u64),
+    /// A returned word interpreted as a two's complement signed integer.
+    SignedReturn(i64),
     ReturnData(Vec<u8>),
+    /// Returned data starting with these bytes, followed by anything.
+    ReturnDataPrefix(Vec<u8>),
     Revert(u64),
+    /// The VM panicked, with the name of the `PanicReason`.
+    Panic(String),
 }
 
 impl fmt::Debug for TestResult {
@@ -51,8 +91,107 @@ impl fmt::Debug for TestResult {
         match self {
             TestResult::Result(result) => write!(f, "Result({result})"),
             TestResult::Return(code) => write!(f, "Return({code})"),
+            TestResult::SignedReturn(code) => write!(f, "SignedReturn({code})"),
             TestResult::ReturnData(data) => write!(f, "ReturnData(0x{})", hex::encode(data)),
+            TestResult::ReturnDataPrefix(prefix) => {
+                write!(f, "ReturnDataPrefix(0x{})", hex::encode(prefix))
+            }
             TestResult::Revert(code) => write!(f, "Revert({code})"),
+            TestResult::Panic(reason) => write!(f, "Panic({reason})"),
+        }
+    }
+}
+
+/// The result expected from running a test, either the same for every build target or different
+/// for each of them.
+enum ExpectedResult {
+    AllTargets(TestResult),
+    PerTarget(HashMap<BuildTarget, TestResult>),
+}
+
+impl ExpectedResult {
+    /// Returns the result expected from the test `name` when it is built for `build_target`.
+    fn for_target(self, name: &str, build_target: BuildTarget) -> Result<TestResult> {
+        match self {
+            ExpectedResult::AllTargets(result) => Ok(result),
+            ExpectedResult::PerTarget(mut results) => {
+                results.remove(&build_target).ok_or_else(|| {
+                    anyhow!("For {name}:\nNo 'expected_result' for the {build_target:?} target.")
+                })
+            }
+        }
+    }
+
+    /// Checks that a result per target has a result for each of the `supported_targets`, and no
+    /// others.
+    fn check_targets(&self, supported_targets: &HashSet<BuildTarget>) -> Result<()> {
+        let ExpectedResult::PerTarget(results) = self else {
+            return Ok(());
+        };
+        if let Some(target) = supported_targets
+            .iter()
+            .find(|target| !results.contains_key(target))
+        {
+            bail!("Missing 'expected_result' for the supported {target:?} target.");
+        }
+        if let Some(target) = results
+            .keys()
+            .find(|target| !supported_targets.contains(target))
+        {
+            bail!(
+                "'expected_result' for the {target:?} target, which isn't in 'supported_targets'."
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether data is expected to be returned when running in the Fuel VM.
+    fn expects_return_data(&self) -> bool {
+        let is_return_data = |result: &TestResult| {
+            matches!(
+                result,
+                TestResult::ReturnData(_) | TestResult::ReturnDataPrefix(_)
+            )
+        };
+        match self {
+            ExpectedResult::AllTargets(result) => is_return_data(result),
+            ExpectedResult::PerTarget(results) => results
+                .get(&BuildTarget::Fuel)
+                .map_or(false, is_return_data),
+        }
+    }
+}
+
+/// One of the `cases` of a `run` test, which runs the compiled script once per case.
+struct RunCase {
+    script_data: Option<Vec<u8>>,
+    expected_result: ExpectedResult,
+}
+
+/// A value logged by a unit test, either with `log` of a word-sized value or as raw data.
+#[derive(PartialEq, Eq)]
+enum UnitTestLog {
+    Value(u64),
+    Data(Vec<u8>),
+}
+
+impl fmt::Debug for UnitTestLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnitTestLog::Value(value) => write!(f, "{value}"),
+            UnitTestLog::Data(data) => write!(f, "0x{}", hex::encode(data)),
+        }
+    }
+}
+
+impl UnitTestLog {
+    fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        match receipt {
+            Receipt::Log { ra, .. } => Some(UnitTestLog::Value(*ra)),
+            Receipt::LogData {
+                data: Some(data), ..
+            } => Some(UnitTestLog::Data(data.clone())),
+            _ => None,
         }
     }
 }
@@ -62,19 +201,115 @@ struct TestDescription {
     category: TestCategory,
     script_data: Option<Vec<u8>>,
     witness_data: Option<Vec<Vec<u8>>>,
-    expected_result: Option<TestResult>,
+    expected_result: Option<ExpectedResult>,
+    /// The script data and results of a `run` test run several times, replacing its
+    /// `script_data` and `expected_result`.
+    run_cases: Vec<RunCase>,
     expected_warnings: u32,
+    max_gas: Option<u64>,
+    /// Fails a `run` test if the script returns data in the Fuel VM, rather than a single word.
+    forbid_return_data: bool,
+    max_bytecode_size: Option<usize>,
+    expected_receipt_count: Option<usize>,
+    /// The values logged by a `run` test, in order.
+    expected_logs: Option<Vec<UnitTestLog>>,
+    package: Option<String>,
+    unit_test_logs: HashMap<String, Vec<UnitTestLog>>,
     contract_paths: Vec<String>,
+    /// The coins and storage the node is seeded with before a `run_on_node` test runs.
+    node_setup: harness::NodeSetup,
     validate_abi: bool,
+    /// The names of the workspace packages whose ABI is validated, or `None` to validate all of them.
+    validate_abi_packages: Option<Vec<String>>,
     validate_storage_slots: bool,
     supported_targets: HashSet<BuildTarget>,
+    vm_features: Vec<String>,
+    expected_to_fail: bool,
+    expected_to_fail_reason: Option<String>,
     checker: filecheck::Checker,
 }
 
+/// The deployment of a single contract, shared by every test that uses the contract.
+type Deployment = Arc<OnceCell<Result<ContractId, String>>>;
+
+/// The deployments of the contracts used so far, keyed by the contract path and seeded storage.
+type DeployedContracts = Arc<Mutex<HashMap<String, Deployment>>>;
+
 #[derive(Clone)]
 struct TestContext {
     run_config: RunConfig,
-    deployed_contracts: Arc<Mutex<HashMap<String, ContractId>>>,
+    /// The contracts deployed so far, or the errors their deployment failed with, so that every
+    /// contract is only deployed once, however many tests use it. Different contracts are deployed
+    /// concurrently, while a contract being deployed is waited for.
+    deployed_contracts: DeployedContracts,
+    /// Serializes the accesses to the `--deploy-cache` file by concurrent deployments.
+    deploy_cache_lock: Arc<Mutex<()>>,
+    /// The `test.toml` files rewritten by `--update-warnings`.
+    updated_test_tomls: Arc<Mutex<Vec<String>>>,
+}
+
+/// Returns the packages of `built` whose ABI is validated, each with its directory relative to
+/// `test_programs`, where its `json_abi_oracle.json` is.
+///
+/// Every package of a workspace is validated, unless `package_names` only allows some of them.
+fn abi_packages(
+    built: &forc_pkg::Built,
+    name: &str,
+    package_names: Option<&[String]>,
+) -> Result<Vec<(String, forc_pkg::BuiltPackage)>> {
+    let built_workspace = match built {
+        forc_pkg::Built::Package(built_pkg) => {
+            return Ok(vec![(name.to_string(), built_pkg.as_ref().clone())]);
+        }
+        forc_pkg::Built::Workspace(built_workspace) => built_workspace,
+    };
+    if let Some(package_names) = package_names {
+        if let Some(unknown) = package_names.iter().find(|package_name| {
+            !built_workspace
+                .iter()
+                .any(|built_pkg| &&built_pkg.descriptor.pinned.name == package_name)
+        }) {
+            bail!("For {name}:\nPackage '{unknown}' of 'validate_abi_packages' not found in workspace.");
+        }
+    }
+    Ok(built_workspace
+        .iter()
+        .filter(|built_pkg| {
+            package_names.map_or(true, |package_names| {
+                package_names.contains(&built_pkg.descriptor.pinned.name)
+            })
+        })
+        .map(|built_pkg| {
+            let path = built_pkg
+                .descriptor
+                .manifest_file
+                .dir()
+                .iter()
+                .skip_while(|part| part.to_string_lossy() != "test_programs")
+                .skip(1)
+                .collect::<PathBuf>();
+            (
+                path.to_string_lossy().into_owned(),
+                built_pkg.as_ref().clone(),
+            )
+        })
+        .collect())
+}
+
+/// Checks that the bytecode of `built_pkg` is no larger than `max_bytecode_size` bytes.
+fn check_bytecode_size(
+    name: &str,
+    built_pkg: &forc_pkg::BuiltPackage,
+    max_bytecode_size: usize,
+) -> Result<()> {
+    let size = built_pkg.bytecode.bytes.len();
+    if size > max_bytecode_size {
+        return Err(anyhow::Error::msg(format!(
+            "Bytecode size limit of {name} exceeded.\nallowed size: {max_bytecode_size} bytes\nactual size: {size} bytes (+{} bytes)",
+            size - max_bytecode_size
+        )));
+    }
+    Ok(())
 }
 
 fn print_receipt(receipt: &Receipt) {
@@ -87,19 +322,115 @@ fn print_receipt(receipt: &Receipt) {
 }
 
 impl TestContext {
-    async fn deploy_contract(&self, contract_path: String) -> Result<ContractId> {
-        let mut deployed_contracts = self.deployed_contracts.lock().await;
-        Ok(
-            if let Some(contract_id) = deployed_contracts.get(&contract_path) {
-                *contract_id
-            } else {
-                let contract_id =
-                    harness::deploy_contract(contract_path.as_str(), &self.run_config).await?;
-                deployed_contracts.insert(contract_path, contract_id);
-                contract_id
-            },
-        )
+    /// Deploys the contract, with `storage_slots` on top of its initial storage, unless it has
+    /// already been deployed with the same storage in this run.
+    ///
+    /// Only contracts without seeded storage are stored in the deploy cache.
+    async fn deploy_contract(
+        &self,
+        contract_path: String,
+        storage_slots: &[fuel_tx::StorageSlot],
+    ) -> Result<ContractId> {
+        // A contract deployed with seeded storage has a different ID than the plain one.
+        let deployment_key = if storage_slots.is_empty() {
+            contract_path.clone()
+        } else {
+            format!("{contract_path} with storage {storage_slots:?}")
+        };
+        // The map is only locked to find the deployment, so that other contracts can be deployed
+        // in the meantime.
+        let deployment = self
+            .deployed_contracts
+            .lock()
+            .await
+            .entry(deployment_key)
+            .or_default()
+            .clone();
+        if let Some(deployment) = deployment.get() {
+            return deployment.clone().map_err(|err| {
+                anyhow!("Deploying contract {contract_path} failed earlier in this run: {err}")
+            });
+        }
+
+        deployment
+            .get_or_init(|| async {
+                match &self.run_config.deploy_cache {
+                    _ if !storage_slots.is_empty() => {
+                        harness::deploy_contract_with_storage(
+                            contract_path.as_str(),
+                            &self.run_config,
+                            storage_slots,
+                        )
+                        .await
+                    }
+                    Some(cache_path) => {
+                        self.deploy_contract_cached(contract_path.as_str(), cache_path)
+                            .await
+                    }
+                    None => {
+                        harness::deploy_contract(contract_path.as_str(), &self.run_config).await
+                    }
+                }
+                .map_err(|err| format!("{err:#}"))
+            })
+            .await
+            .clone()
+            .map_err(|err| anyhow!("Deploying contract {contract_path} failed: {err}"))
+    }
+
+    /// Deploys the contract unless the deploy cache holds a deployment of the same bytecode.
+    async fn deploy_contract_cached(
+        &self,
+        contract_path: &str,
+        cache_path: &Path,
+    ) -> Result<ContractId> {
+        let bytecode_hash = match harness::compile_to_bytes(contract_path, &self.run_config).await?
+        {
+            forc_pkg::Built::Package(built_pkg) => {
+                deploy_cache::bytecode_hash(&built_pkg.bytecode.bytes)
+            }
+            forc_pkg::Built::Workspace(_) => {
+                bail!("workspaces are not supported by the deploy cache")
+            }
+        };
+        {
+            let _guard = self.deploy_cache_lock.lock().await;
+            let cache = DeployCache::load(cache_path)?;
+            if let Some(contract_id) = cache.get(contract_path, &bytecode_hash) {
+                println!(" Reusing deployment of {} ...", contract_path.bold());
+                return Ok(contract_id);
+            }
+        }
+        let contract_id = harness::deploy_contract(contract_path, &self.run_config).await?;
+        // The cache is loaded again to keep the contracts deployed concurrently in the meantime.
+        let _guard = self.deploy_cache_lock.lock().await;
+        let mut cache = DeployCache::load(cache_path)?;
+        cache.insert(contract_path.to_string(), bytecode_hash, contract_id);
+        cache.save(cache_path)?;
+        Ok(contract_id)
+    }
+    /// Checks that there are no more than `expected_warnings` warnings.
+    ///
+    /// With `--update-warnings`, a differing number of warnings doesn't fail the test. Instead it's
+    /// stored in `warnings_update`, to be written to the `test.toml` if the rest of the test passes.
+    fn check_warnings(
+        &self,
+        expected_warnings: u32,
+        warnings: usize,
+        warnings_update: &mut Option<usize>,
+    ) -> Result<()> {
+        if self.run_config.update_warnings {
+            if warnings != expected_warnings as usize {
+                *warnings_update = Some(warnings);
+            }
+        } else if warnings > expected_warnings as usize {
+            return Err(anyhow::Error::msg(format!(
+                "Expected warnings: {expected_warnings}\nActual number of warnings: {warnings}",
+            )));
+        }
+        Ok(())
     }
+
     async fn run(&self, test: TestDescription, output: &mut String, verbose: bool) -> Result<()> {
         let context = self;
         let TestDescription {
@@ -108,26 +439,67 @@ impl TestContext {
             script_data,
             witness_data,
             expected_result,
+            run_cases,
             expected_warnings,
+            max_gas,
+            forbid_return_data,
+            max_bytecode_size,
+            expected_receipt_count,
+            expected_logs,
+            package,
+            unit_test_logs,
             contract_paths,
+            node_setup,
             validate_abi,
+            validate_abi_packages,
             validate_storage_slots,
             checker,
             ..
         } = test;
 
-        match category {
+        // Tests with a result per target only expect that of the target they are built for.
+        let expected_result = expected_result
+            .map(|expected_result| {
+                expected_result.for_target(&name, context.run_config.build_target)
+            })
+            .transpose()?;
+
+        let mut warnings_update = None;
+        let result = match category {
             TestCategory::Runs => {
-                let res = match expected_result {
-                    Some(TestResult::Return(_))
-                    | Some(TestResult::ReturnData(_))
-                    | Some(TestResult::Revert(_)) => expected_result.unwrap(),
-
-                    _ => panic!(
-                        "For {name}:\n\
-                        Invalid expected result for a 'runs' test: {expected_result:?}."
-                    ),
+                // A test without `cases` is a single case, with the script data of the test.
+                let cases = if run_cases.is_empty() {
+                    vec![(script_data, expected_result)]
+                } else {
+                    run_cases
+                        .into_iter()
+                        .map(|case| {
+                            let expected_result = case
+                                .expected_result
+                                .for_target(&name, context.run_config.build_target)?;
+                            Ok((case.script_data, Some(expected_result)))
+                        })
+                        .collect::<Result<Vec<_>>>()?
                 };
+                let cases = cases
+                    .into_iter()
+                    .map(|(script_data, expected_result)| {
+                        let res = match expected_result {
+                            Some(TestResult::Return(_))
+                            | Some(TestResult::SignedReturn(_))
+                            | Some(TestResult::ReturnData(_))
+                            | Some(TestResult::ReturnDataPrefix(_))
+                            | Some(TestResult::Revert(_))
+                            | Some(TestResult::Panic(_)) => expected_result.unwrap(),
+
+                            _ => panic!(
+                                "For {name}:\n\
+                                Invalid expected result for a 'runs' test: {expected_result:?}."
+                            ),
+                        };
+                        (script_data, res)
+                    })
+                    .collect::<Vec<_>>();
 
                 let (result, out) = run_and_capture_output(|| {
                     harness::compile_to_bytes(&name, &context.run_config)
@@ -135,80 +507,263 @@ impl TestContext {
                 .await;
                 *output = out;
 
-                let compiled = result?;
+                let built = result?;
 
-                let compiled = match compiled {
+                let compiled = match &built {
                     forc_pkg::Built::Package(built_pkg) => built_pkg.as_ref().clone(),
-                    forc_pkg::Built::Workspace(_) => {
-                        panic!("workspaces are not supported in the test suite yet")
+                    forc_pkg::Built::Workspace(built_workspace) => {
+                        let package = package.as_ref().ok_or_else(|| {
+                            anyhow!(
+                                "For {name}:\n\
+                                A 'package' entry is required to run a workspace."
+                            )
+                        })?;
+                        built_workspace
+                            .iter()
+                            .find(|built_pkg| &built_pkg.descriptor.pinned.name == package)
+                            .ok_or_else(|| {
+                                anyhow!("For {name}:\nPackage '{package}' not found in workspace.")
+                            })?
+                            .as_ref()
+                            .clone()
                     }
                 };
 
-                if compiled.warnings.len() > expected_warnings as usize {
-                    return Err(anyhow::Error::msg(format!(
-                        "Expected warnings: {expected_warnings}\nActual number of warnings: {}",
-                        compiled.warnings.len()
-                    )));
-                }
+                context.check_warnings(
+                    expected_warnings,
+                    compiled.warnings.len(),
+                    &mut warnings_update,
+                )?;
+
+                // The receipts of the run, to dump them if the test fails.
+                let mut receipts_dump = None;
+                let result = async {
+                    // The most gas used by any case, which the budget must cover.
+                    let mut max_gas_used = None;
+                    let case_count = cases.len();
+                    for (i, (script_data, res)) in cases.into_iter().enumerate() {
+                        let gas_used = async {
+                            let result = harness::runs_in_vm(
+                                compiled.clone(),
+                                script_data,
+                                witness_data.clone(),
+                                test_seed(context.run_config.seed, &name),
+                            )?;
+                            let mut gas_used = None;
+                            let mut receipt_count = None;
+                            let mut logs = None;
+                            let result = match result {
+                                harness::VMExecutionResult::Fuel(state, receipts) => {
+                                    receipts_dump = Some(receipts.clone());
+                                    if verbose {
+                                        for receipt in receipts.iter() {
+                                            print_receipt(receipt);
+                                        }
+                                    }
+                                    receipt_count = Some(receipts.len());
+                                    logs = Some(
+                                        receipts
+                                            .iter()
+                                            .filter_map(UnitTestLog::from_receipt)
+                                            .collect::<Vec<_>>(),
+                                    );
+                                    gas_used = Some(
+                                        receipts
+                                            .iter()
+                                            .filter_map(|receipt| match receipt {
+                                                Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+                                                _ => None,
+                                            })
+                                            .sum::<u64>(),
+                                    );
+
+                                    match state {
+                                        ProgramState::Return(v) => TestResult::Return(v),
+                                        ProgramState::ReturnData(digest) => {
+                                            // Find the ReturnData receipt matching the digest
+                                            let receipt = receipts
+                                                .iter()
+                                                .find(|r| r.digest() == Some(&digest))
+                                                .unwrap();
+                                            // Get the data from the receipt
+                                            let data = receipt.data().unwrap().to_vec();
+                                            if forbid_return_data {
+                                                bail!(
+                                                    "The script returned data, which 'forbid_return_data' \
+                                                    forbids.\nexpected: {res:?}\nactual: {:?}",
+                                                    TestResult::ReturnData(data)
+                                                );
+                                            }
+                                            TestResult::ReturnData(data)
+                                        }
+                                        ProgramState::Revert(v) => {
+                                            let panic_reason = receipts.iter().find_map(|r| match r {
+                                                Receipt::Panic { reason, .. } => {
+                                                    Some(format!("{:?}", reason.reason()))
+                                                }
+                                                _ => None,
+                                            });
+                                            match panic_reason {
+                                                // A panic also reverts, so it is only reported as a panic
+                                                // when one is expected, keeping `revert` expectations working.
+                                                Some(reason) if matches!(res, TestResult::Panic(_)) => {
+                                                    TestResult::Panic(reason)
+                                                }
+                                                _ => TestResult::Revert(v),
+                                            }
+                                        }
+                                    }
+                                }
+                                harness::VMExecutionResult::Evm(state) => match state.exit_reason {
+                                    revm::Return::Continue => {
+                                        return Err(
+                                            harness::UnimplementedHarnessPath("EVM Continue".into()).into()
+                                        );
+                                    }
+                                    revm::Return::Stop => TestResult::Result(0),
+                                    revm::Return::Return => match state.out {
+                                        // The return buffer is passed through as-is, so unlike the Fuel VM
+                                        // each value returned is ABI encoded and padded to 32 bytes.
+                                        revm::TransactOut::Call(data) => TestResult::ReturnData(data.to_vec()),
+                                        out => {
+                                            return Err(harness::UnimplementedHarnessPath(
+                                                format!("EVM Return with output {out:?}"),
+                                            )
+                                            .into());
+                                        }
+                                    },
+                                    revm::Return::SelfDestruct => {
+                                        return Err(
+                                            harness::UnimplementedHarnessPath("EVM SelfDestruct".into()).into()
+                                        );
+                                    }
+                                    revm::Return::Revert => TestResult::Revert(0),
+                                    reason => {
+                                        return Err(harness::UnimplementedHarnessPath(
+                                            format!("EVM {reason:?}"),
+                                        )
+                                        .into());
+                                    }
+                                },
+                                harness::VMExecutionResult::MidenVM(trace) => {
+                                    let outputs = trace.program_outputs();
+                                    let stack = outputs.stack();
+                                    // for now, just test primitive u64s.
+                                    // Later on, we can test stacks that have more elements in them.
+                                    TestResult::Return(stack[0])
+                                }
+                            };
 
-                let result = harness::runs_in_vm(compiled.clone(), script_data, witness_data)?;
-                let result = match result {
-                    harness::VMExecutionResult::Fuel(state, receipts) => {
-                        if verbose {
-                            for receipt in receipts.iter() {
-                                print_receipt(receipt);
+                            // Words are compared as signed integers if the expected value is signed, and
+                            // only the leading bytes of returned data if a prefix is expected.
+                            let result = match (&res, result) {
+                                (TestResult::SignedReturn(_), TestResult::Return(v)) => {
+                                    TestResult::SignedReturn(v as i64)
+                                }
+                                (TestResult::ReturnDataPrefix(prefix), TestResult::ReturnData(data))
+                                    if data.starts_with(prefix) =>
+                                {
+                                    TestResult::ReturnDataPrefix(prefix.clone())
+                                }
+                                (_, result) => result,
+                            };
+
+                            if result != res {
+                                Err(anyhow::Error::msg(format!(
+                                    "expected: {res:?}\nactual: {result:?}"
+                                )))
+                            } else {
+                                if let (Some(expected), Some(actual)) = (expected_receipt_count, receipt_count)
+                                {
+                                    check_receipt_count(expected, actual)?;
+                                }
+                                if let (Some(expected), Some(actual)) = (&expected_logs, &logs) {
+                                    if expected != actual {
+                                        bail!("Unexpected logs.\nexpected logs: {expected:?}\nactual logs: {actual:?}");
+                                    }
+                                }
+                                if let (Some(max_gas), Some(gas_used)) = (max_gas, gas_used) {
+                                    if !context.run_config.update_gas && gas_used > max_gas {
+                                        return Err(anyhow::Error::msg(format!(
+                                            "Gas budget exceeded.\nbudgeted gas: {max_gas}\nactual gas: {gas_used}"
+                                        )));
+                                    }
+                                }
+                                Ok(gas_used)
                             }
                         }
-
-                        match state {
-                            ProgramState::Return(v) => TestResult::Return(v),
-                            ProgramState::ReturnData(digest) => {
-                                // Find the ReturnData receipt matching the digest
-                                let receipt = receipts
-                                    .iter()
-                                    .find(|r| r.digest() == Some(&digest))
-                                    .unwrap();
-                                // Get the data from the receipt
-                                let data = receipt.data().unwrap().to_vec();
-                                TestResult::ReturnData(data)
+                        .await
+                        // Name the failing case, unless the harness rather than the test failed.
+                        .map_err(|err| {
+                            if case_count == 1 || err.is::<harness::UnimplementedHarnessPath>() {
+                                err
+                            } else {
+                                anyhow!("Case {i} of {case_count} failed.\n{err}")
                             }
-                            ProgramState::Revert(v) => TestResult::Revert(v),
-                        }
+                        })?;
+                        max_gas_used = max_gas_used.max(gas_used);
                     }
-                    harness::VMExecutionResult::Evm(state) => match state.exit_reason {
-                        revm::Return::Continue => todo!(),
-                        revm::Return::Stop => TestResult::Result(0),
-                        revm::Return::Return => todo!(),
-                        revm::Return::SelfDestruct => todo!(),
-                        revm::Return::Revert => TestResult::Revert(0),
-                        _ => {
-                            panic!("EVM exited with unhandled reason: {:?}", state.exit_reason);
+                    if let (true, Some(max_gas), Some(gas_used)) =
+                        (context.run_config.update_gas, max_gas, max_gas_used)
+                    {
+                        if gas_used != max_gas {
+                            update_max_gas(&name, gas_used)?;
                         }
-                    },
-                    harness::VMExecutionResult::MidenVM(trace) => {
-                        let outputs = trace.program_outputs();
-                        let stack = outputs.stack();
-                        // for now, just test primitive u64s.
-                        // Later on, we can test stacks that have more elements in them.
-                        TestResult::Return(stack[0])
                     }
-                };
-
-                if result != res {
-                    Err(anyhow::Error::msg(format!(
-                        "expected: {res:?}\nactual: {result:?}"
-                    )))
-                } else {
                     if validate_abi {
-                        let (result, out) = run_and_capture_output(|| async {
-                            harness::test_json_abi(&name, &compiled)
-                        })
-                        .await;
-                        output.push_str(&out);
-                        result?;
+                        for (path, built_pkg) in
+                            abi_packages(&built, &name, validate_abi_packages.as_deref())?
+                        {
+                            let (result, out) = run_and_capture_output(|| async {
+                                harness::test_json_abi(&path, &built_pkg)
+                            })
+                            .await;
+                            output.push_str(&out);
+                            result?;
+                        }
                     }
                     Ok(())
                 }
+                .await;
+                if let Some(artifacts_dir) = &context.run_config.keep_artifacts {
+                    match harness::keep_artifacts(
+                        artifacts_dir,
+                        &name,
+                        &compiled,
+                        &context.run_config,
+                    )
+                    .await
+                    {
+                        Ok(path) => output.push_str(&format!(
+                            "Kept the compiled artifacts in {}\n",
+                            path.display()
+                        )),
+                        Err(keep_err) => output.push_str(&format!(
+                            "Unable to keep the compiled artifacts: {keep_err}\n"
+                        )),
+                    }
+                }
+                if let (Err(err), Some(dump_dir)) = (&result, &context.run_config.dump_on_fail) {
+                    match harness::dump_failure(
+                        dump_dir,
+                        &name,
+                        &compiled,
+                        receipts_dump.as_deref(),
+                        err,
+                        &context.run_config,
+                    )
+                    .await
+                    {
+                        Ok(path) => output.push_str(&format!(
+                            "Dumped the artifacts of the failure to {}\n",
+                            path.display()
+                        )),
+                        Err(dump_err) => output.push_str(&format!(
+                            "Unable to dump the artifacts of the failure: {dump_err}\n"
+                        )),
+                    }
+                }
+                result
             }
 
             TestCategory::Compiles => {
@@ -218,14 +773,14 @@ impl TestContext {
                 .await;
                 *output = out;
 
-                let compiled_pkgs = match result? {
+                let built = result?;
+                let compiled_pkgs = match &built {
                     forc_pkg::Built::Package(built_pkg) => {
-                        if built_pkg.warnings.len() > expected_warnings as usize {
-                            return Err(anyhow::Error::msg(format!(
-                                "Expected warnings: {expected_warnings}\nActual number of warnings: {}",
-                                built_pkg.warnings.len()
-                            )));
-                        }
+                        context.check_warnings(
+                            expected_warnings,
+                            built_pkg.warnings.len(),
+                            &mut warnings_update,
+                        )?;
                         vec![(name.clone(), built_pkg.as_ref().clone())]
                     }
                     forc_pkg::Built::Workspace(built_workspace) => built_workspace
@@ -241,10 +796,18 @@ impl TestContext {
 
                 check_file_checker(checker, &name, output)?;
 
-                if validate_abi {
+                if let Some(max_bytecode_size) = max_bytecode_size {
                     for (name, built_pkg) in &compiled_pkgs {
+                        check_bytecode_size(name, built_pkg, max_bytecode_size)?;
+                    }
+                }
+
+                if validate_abi {
+                    for (path, built_pkg) in
+                        abi_packages(&built, &name, validate_abi_packages.as_deref())?
+                    {
                         let (result, out) = run_and_capture_output(|| async {
-                            harness::test_json_abi(name, built_pkg)
+                            harness::test_json_abi(&path, &built_pkg)
                         })
                         .await;
                         result?;
@@ -280,6 +843,23 @@ impl TestContext {
                 }
             }
 
+            TestCategory::Lexes | TestCategory::Parses => {
+                let (result, out) = run_and_capture_output(|| async {
+                    if category == TestCategory::Lexes {
+                        harness::lex_only(&name)
+                    } else {
+                        harness::parse_only(&name, &context.run_config)
+                    }
+                })
+                .await;
+                *output = out;
+
+                // Whether the stage succeeds or not is up to the FileCheck directives.
+                result?;
+                check_file_checker(checker, &name, output)?;
+                Ok(())
+            }
+
             TestCategory::RunsWithContract => {
                 let val = if let Some(TestResult::Result(val)) = expected_result {
                     val
@@ -297,18 +877,28 @@ impl TestContext {
                     );
                 }
 
-                let mut contract_ids = Vec::new();
-                for contract_path in contract_paths.clone() {
-                    let (result, out) = run_and_capture_output(|| async {
-                        context.deploy_contract(contract_path).await
-                    })
-                    .await;
-                    output.push_str(&out);
-                    contract_ids.push(result);
-                }
+                // The contracts are deployed concurrently, and `join_all` keeps their order.
+                let (contract_ids, out) = run_and_capture_output(|| {
+                    join_all(contract_paths.iter().map(|contract_path| {
+                        let storage_slots = node_setup
+                            .storage
+                            .get(contract_path)
+                            .map(Vec::as_slice)
+                            .unwrap_or_default();
+                        context.deploy_contract(contract_path.clone(), storage_slots)
+                    }))
+                })
+                .await;
+                output.push_str(&out);
                 let contract_ids = contract_ids.into_iter().collect::<Result<Vec<_>, _>>()?;
+                let contracts = contract_paths
+                    .iter()
+                    .cloned()
+                    .zip(contract_ids.iter().copied())
+                    .collect::<Vec<_>>();
                 let (result, out) =
-                    harness::runs_on_node(&name, &context.run_config, &contract_ids).await;
+                    harness::runs_on_node(&name, &context.run_config, &contracts, &node_setup)
+                        .await;
                 output.push_str(&out);
 
                 let receipt = result?;
@@ -327,6 +917,9 @@ impl TestContext {
                 assert!(receipt.len() >= 2);
                 assert_matches!(receipt[receipt.len() - 2], fuel_tx::Receipt::Return { .. });
                 assert_eq!(receipt[receipt.len() - 2].val().unwrap(), val);
+                if let Some(expected) = expected_receipt_count {
+                    check_receipt_count(expected, receipt.len())?;
+                }
 
                 Ok(())
             }
@@ -338,8 +931,10 @@ impl TestContext {
 
                 result.map(|tested_pkgs| {
                     let mut failed = vec![];
+                    let mut unchecked_logs = unit_test_logs.keys().collect::<HashSet<_>>();
                     for pkg in tested_pkgs {
                         for test in pkg.tests.into_iter() {
+                            unchecked_logs.remove(&test.name);
                             if verbose {
                                 println!("Test: {} {}", test.name, test.passed());
                                 for log in test.logs.iter() {
@@ -355,10 +950,29 @@ impl TestContext {
                                     test.state,
                                     test.condition,
                                 ));
+                            } else if let Some(expected_logs) = unit_test_logs.get(&test.name) {
+                                let logs = test
+                                    .logs
+                                    .iter()
+                                    .filter_map(UnitTestLog::from_receipt)
+                                    .collect::<Vec<_>>();
+                                if &logs != expected_logs {
+                                    failed.push(format!(
+                                        "{}: Test '{}' logged {:?}, expected: {:?}",
+                                        pkg.built.descriptor.name, test.name, logs, expected_logs,
+                                    ));
+                                }
                             }
                         }
                     }
 
+                    // Catch misspelled test names, whose logs would otherwise never be checked.
+                    for test_name in unchecked_logs {
+                        failed.push(format!(
+                            "'unit_test_logs' has logs for test '{test_name}', which doesn't exist"
+                        ));
+                    }
+
                     if !failed.is_empty() {
                         println!("FAILED!! output:\n{}", output);
                         panic!(
@@ -373,14 +987,32 @@ impl TestContext {
             category => Err(anyhow::Error::msg(format!(
                 "Unexpected test category: {category:?}",
             ))),
+        };
+
+        if let (Ok(()), Some(warnings)) = (&result, warnings_update) {
+            let path = update_expected_warnings(&name, warnings)?;
+            context.updated_test_tomls.lock().await.push(path);
         }
+        result
     }
 }
 
 pub async fn run(filter_config: &FilterConfig, run_config: &RunConfig) -> Result<()> {
+    if run_config.validate_only {
+        return validate_test_configs();
+    }
+
     // Discover tests
-    let mut tests = discover_test_configs()?;
+    let mut tests = discover_test_configs(filter_config.exact.as_deref())?;
     let total_number_of_tests = tests.len();
+    if run_config.shuffle {
+        tests.shuffle(&mut StdRng::seed_from_u64(run_config.seed));
+        tracing::info!(
+            "Shuffled the tests with seed {}, pass `--shuffle --seed {}` to run them in the same order",
+            run_config.seed,
+            run_config.seed
+        );
+    }
 
     // Filter tests
     let skipped_tests = filter_config
@@ -410,6 +1042,42 @@ pub async fn run(filter_config: &FilterConfig, run_config: &RunConfig) -> Result
         .as_ref()
         .map(|exclude| tests.retained(|t| !exclude.is_match(&t.name)))
         .unwrap_or_default();
+    let included_glob_tests = filter_config
+        .include_glob
+        .as_ref()
+        .map(|include| tests.retained(|t| glob_matches(include, &t.name)))
+        .unwrap_or_default();
+    let excluded_glob_tests = filter_config
+        .exclude_glob
+        .as_ref()
+        .map(|exclude| tests.retained(|t| !glob_matches(exclude, &t.name)))
+        .unwrap_or_default();
+    let changed_since_tests = filter_config
+        .changed_since
+        .as_ref()
+        .map(|rev| match changed_test_files(rev) {
+            Ok(files) => tests.retained(|t| files.iter().any(|file| file.starts_with(&t.name))),
+            Err(err) => {
+                tracing::warn!("Unable to find the tests changed since `{rev}`: {err}");
+                tests.retained(|_| false)
+            }
+        })
+        .unwrap_or_default();
+    let changed_files_tests = filter_config
+        .changed_files
+        .as_ref()
+        .map(|files| tests.retained(|t| files.iter().any(|file| file.starts_with(&t.name))))
+        .unwrap_or_default();
+    let categories = filter_config
+        .categories
+        .iter()
+        .map(|category| category.parse::<TestCategory>())
+        .collect::<Result<Vec<_>>>()?;
+    let category_filtered_tests = if categories.is_empty() {
+        vec![]
+    } else {
+        tests.retained(|t| categories.contains(&t.category))
+    };
     if filter_config.abi_only {
         tests.retain(|t| t.validate_abi);
     }
@@ -424,10 +1092,20 @@ pub async fn run(filter_config: &FilterConfig, run_config: &RunConfig) -> Result
     let context = TestContext {
         run_config: run_config.clone(),
         deployed_contracts: Default::default(),
+        deploy_cache_lock: Default::default(),
+        updated_test_tomls: Default::default(),
     };
     let mut number_of_tests_executed = 0;
     let mut number_of_tests_failed = 0;
     let mut failed_tests = vec![];
+    let mut xfailed_tests = vec![];
+    let mut xpassed_tests = vec![];
+    let mut flaky_tests = vec![];
+    let mut unsupported_vm_feature_tests = vec![];
+    let mut junit_report = JunitReport::default();
+    for test in &disabled_tests {
+        junit_report.skipped(&test.name, Duration::ZERO, "disabled");
+    }
 
     for (i, test) in tests.into_iter().enumerate() {
         let name = test.name.clone();
@@ -441,23 +1119,157 @@ pub async fn run(filter_config: &FilterConfig, run_config: &RunConfig) -> Result
             continue;
         }
 
-        let result = if !filter_config.first_only {
-            context
-                .run(test, &mut output, run_config.verbose)
-                .instrument(tracing::trace_span!("E2E", i))
-                .await
-        } else {
-            context.run(test, &mut output, run_config.verbose).await
+        // Skip the test if it needs features that the VM it would run in doesn't have.
+        let missing_vm_features = test
+            .vm_features
+            .iter()
+            .filter(|feature| !harness::VM_FEATURES.contains(&feature.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !missing_vm_features.is_empty() {
+            println!(
+                " {} (unsupported VM features: {})",
+                "skipped".yellow().bold(),
+                missing_vm_features.join(", ")
+            );
+            junit_report.skipped(
+                &name,
+                Duration::ZERO,
+                &format!(
+                    "unsupported VM features: {}",
+                    missing_vm_features.join(", ")
+                ),
+            );
+            unsupported_vm_feature_tests.push(name);
+            continue;
+        }
+
+        let expected_to_fail = test.expected_to_fail;
+        let expected_to_fail_reason = test.expected_to_fail_reason.clone().unwrap_or_default();
+        let start = Instant::now();
+
+        // With `--repeat`, the test is run again from a fresh description each time. The result
+        // and output are those of the first failing run, or of the last run if none failed.
+        let mut test = Some(test);
+        let mut number_of_passes = 0;
+        let mut result = Ok(());
+        for _ in 0..run_config.repeat {
+            let test = match test.take() {
+                Some(test) => test,
+                None => {
+                    parse_test_toml(&test_toml_path(&name)).map_err(|e| anyhow!("{name}: {e}"))?
+                }
+            };
+            let mut run_output = String::new();
+            let run_result = if !filter_config.first_only {
+                context
+                    .run(test, &mut run_output, run_config.verbose)
+                    .instrument(tracing::trace_span!("E2E", i))
+                    .await
+            } else {
+                context.run(test, &mut run_output, run_config.verbose).await
+            };
+
+            // In strict mode, reaching an unimplemented harness path aborts the run, even if the
+            // test is expected to fail, so that it can't go unnoticed in CI.
+            match run_result {
+                Err(err)
+                    if run_config.strict_unimplemented
+                        && err.is::<harness::UnimplementedHarnessPath>() =>
+                {
+                    println!(" {}", "failed".red().bold());
+                    println!("{}", textwrap::indent(err.to_string().as_str(), "     "));
+                    if let Some(path) = &run_config.junit {
+                        junit_report.failed(&name, start.elapsed(), &err.to_string(), &run_output);
+                        junit_report.save(path)?;
+                    }
+                    return Err(
+                        err.context(format!("Test {name} reached an unimplemented harness path"))
+                    );
+                }
+                Ok(()) => {
+                    number_of_passes += 1;
+                    if result.is_ok() {
+                        output = run_output;
+                    }
+                }
+                Err(err) => {
+                    if result.is_ok() {
+                        result = Err(err);
+                        output = run_output;
+                    }
+                }
+            }
+        }
+        let time = start.elapsed();
+
+        // A test that passes only some of the times is flaky, whether it's expected to fail or
+        // not, and is reported apart from the tests that fail every time.
+        if number_of_passes != 0 && number_of_passes != run_config.repeat {
+            let err = result.unwrap_err();
+            let ratio = format!("{number_of_passes}/{} runs passed", run_config.repeat);
+            println!(" {} ({ratio})", "flaky".red().bold());
+            println!("{}", textwrap::indent(err.to_string().as_str(), "     "));
+            println!("{}", textwrap::indent(&output, "          "));
+            junit_report.failed(&name, time, &format!("flaky ({ratio}): {err}"), &output);
+            if run_config.fail_fast {
+                if let Some(path) = &run_config.junit {
+                    junit_report.save(path)?;
+                }
+                return Err(err.context(format!("Test {name} is flaky, {ratio}")));
+            }
+            flaky_tests.push((name, number_of_passes));
+            number_of_tests_executed += 1;
+            continue;
+        }
+
+        // Tests that are expected to fail must fail, and an unexpected pass is reported as a
+        // failure so that the test gets unmarked.
+        let result = match (result, expected_to_fail) {
+            (Err(err), true) => {
+                println!(" {}", "xfail".yellow().bold());
+                if run_config.verbose {
+                    println!("{}", textwrap::indent(err.to_string().as_str(), "     "));
+                }
+                junit_report.skipped(
+                    &name,
+                    time,
+                    &format!("expected to fail: {expected_to_fail_reason}"),
+                );
+                xfailed_tests.push((name, expected_to_fail_reason));
+                number_of_tests_executed += 1;
+                continue;
+            }
+            (Ok(()), true) => {
+                xpassed_tests.push(name.clone());
+                Err(anyhow!(
+                    "Test passed but is marked `expected_to_fail`, remove the marker from its test.toml"
+                ))
+            }
+            (result, false) => result,
         };
 
         if let Err(err) = result {
-            println!(" {}", "failed".red().bold());
+            println!(
+                " {}",
+                if expected_to_fail { "xpass" } else { "failed" }
+                    .red()
+                    .bold()
+            );
             println!("{}", textwrap::indent(err.to_string().as_str(), "     "));
             println!("{}", textwrap::indent(&output, "          "));
+            junit_report.failed(&name, time, &err.to_string(), &output);
+            if run_config.fail_fast {
+                if let Some(path) = &run_config.junit {
+                    junit_report.save(path)?;
+                }
+                return Err(err.context(format!("Test {name} failed")));
+            }
             number_of_tests_failed += 1;
             failed_tests.push(name);
         } else {
             println!(" {}", "ok".green().bold());
+            junit_report.passed(&name, time);
 
             // If verbosity is requested then print it out.
             if run_config.verbose {
@@ -490,47 +1302,160 @@ pub async fn run(filter_config: &FilterConfig, run_config: &RunConfig) -> Result
                 exclude.to_string()
             );
         }
+        if let Some(include_glob) = &filter_config.include_glob {
+            tracing::info!(
+                "Filtered {} tests with `include-glob` glob: {}",
+                included_glob_tests.len(),
+                include_glob
+            );
+        }
+        if let Some(exclude_glob) = &filter_config.exclude_glob {
+            tracing::info!(
+                "Filtered {} tests with `exclude-glob` glob: {}",
+                excluded_glob_tests.len(),
+                exclude_glob
+            );
+        }
+        if let Some(rev) = &filter_config.changed_since {
+            tracing::info!(
+                "Filtered {} tests with `changed-since` revision: {}",
+                changed_since_tests.len(),
+                rev
+            );
+        }
+        if filter_config.changed_files.is_some() {
+            tracing::info!(
+                "Filtered {} tests that don't contain a changed file",
+                changed_files_tests.len()
+            );
+        }
+        if !filter_config.categories.is_empty() {
+            tracing::info!(
+                "Filtered {} tests with `category` filter: {}",
+                category_filtered_tests.len(),
+                filter_config.categories.join(", ")
+            );
+        }
         if !disabled_tests.is_empty() {
             tracing::info!("{} tests were disabled.", disabled_tests.len());
         }
+        if !unsupported_vm_feature_tests.is_empty() {
+            tracing::info!(
+                "{} tests were skipped because they require unsupported VM features.",
+                unsupported_vm_feature_tests.len()
+            );
+        }
         tracing::warn!(
-            "No tests were run. Regex filters filtered out all {} tests.",
+            "No tests were run. Filters filtered out all {} tests.",
             total_number_of_tests
         );
     } else {
         tracing::info!("_________________________________");
         tracing::info!(
-            "Sway tests result: {}. {} total, {} passed; {} failed; {} disabled",
-            if number_of_tests_failed == 0 {
+            "Sway tests result: {}. {} total, {} passed; {} failed; {} flaky; {} xfailed; {} xpassed; {} disabled",
+            if number_of_tests_failed == 0 && flaky_tests.is_empty() {
                 "ok".green().bold()
             } else {
                 "failed".red().bold()
             },
             total_number_of_tests,
-            number_of_tests_executed - number_of_tests_failed,
-            number_of_tests_failed,
+            number_of_tests_executed
+                - number_of_tests_failed
+                - flaky_tests.len()
+                - xfailed_tests.len(),
+            number_of_tests_failed - xpassed_tests.len(),
+            flaky_tests.len(),
+            xfailed_tests.len(),
+            xpassed_tests.len(),
             disabled_tests.len()
         );
+        if !unsupported_vm_feature_tests.is_empty() {
+            tracing::info!(
+                "{} tests were skipped because they require unsupported VM features: {}",
+                unsupported_vm_feature_tests.len(),
+                unsupported_vm_feature_tests.join(", ")
+            );
+        }
+        if !xfailed_tests.is_empty() {
+            tracing::info!("{}", "Known failing tests:".yellow().bold());
+            tracing::info!(
+                "    {}",
+                xfailed_tests
+                    .into_iter()
+                    .map(|(test_name, reason)| if reason.is_empty() {
+                        format!("{} ... {}", test_name.bold(), "xfail".yellow().bold())
+                    } else {
+                        format!(
+                            "{} ... {} ({reason})",
+                            test_name.bold(),
+                            "xfail".yellow().bold()
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n    ")
+            );
+        }
+        if !flaky_tests.is_empty() {
+            tracing::info!("{}", "Flaky tests:".red().bold());
+            tracing::info!(
+                "    {}",
+                flaky_tests
+                    .iter()
+                    .map(|(test_name, number_of_passes)| format!(
+                        "{} ... {} ({number_of_passes}/{} runs passed)",
+                        test_name.bold(),
+                        "flaky".red().bold(),
+                        run_config.repeat
+                    ))
+                    .collect::<Vec<_>>()
+                    .join("\n    ")
+            );
+        }
         if number_of_tests_failed > 0 {
             tracing::info!("{}", "Failing tests:".red().bold());
             tracing::info!(
                 "    {}",
                 failed_tests
                     .into_iter()
-                    .map(|test_name| format!("{} ... {}", test_name.bold(), "failed".red().bold()))
+                    .map(|test_name| {
+                        let status = if xpassed_tests.contains(&test_name) {
+                            "xpass"
+                        } else {
+                            "failed"
+                        };
+                        format!("{} ... {}", test_name.bold(), status.red().bold())
+                    })
                     .collect::<Vec<_>>()
                     .join("\n    ")
             );
         }
     }
-    if number_of_tests_failed != 0 {
+    if let Some(path) = &run_config.junit {
+        junit_report.save(path)?;
+        tracing::info!("Wrote JUnit report to {}", path.display());
+    }
+    let updated_test_tomls = context.updated_test_tomls.lock().await;
+    if !updated_test_tomls.is_empty() {
+        tracing::info!(
+            "Updated `expected_warnings` in {} files:",
+            updated_test_tomls.len()
+        );
+        for path in updated_test_tomls.iter() {
+            tracing::info!("    {path}");
+        }
+    }
+    if number_of_tests_failed != 0 || !flaky_tests.is_empty() {
         Err(anyhow::Error::msg("Failed tests"))
     } else {
         Ok(())
     }
 }
 
-fn discover_test_configs() -> Result<Vec<TestDescription>> {
+/// Parses the `test.toml` of every test, or only that of the test named `exact` if given, which
+/// avoids walking the whole `test_programs` tree.
+///
+/// The tests are sorted by name, so that they run in the same order on every platform.
+fn discover_test_configs(exact: Option<&str>) -> Result<Vec<TestDescription>> {
     fn recursive_search(path: &Path, configs: &mut Vec<TestDescription>) -> Result<()> {
         let wrap_err = |e| {
             let relative_path = path
@@ -553,11 +1478,184 @@ fn discover_test_configs() -> Result<Vec<TestDescription>> {
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
     let tests_root_dir = format!("{manifest_dir}/src/e2e_vm_tests/test_programs");
 
+    if let Some(name) = exact {
+        let path = PathBuf::from(&tests_root_dir).join(name).join("test.toml");
+        if !path.is_file() {
+            bail!(
+                "No test named `{name}` was found, expected a test description at {}",
+                path.display()
+            );
+        }
+        let config = parse_test_toml(&path).map_err(|e| anyhow!("{name}: {e}"))?;
+        return Ok(vec![config]);
+    }
+
     let mut configs = Vec::new();
     recursive_search(&PathBuf::from(tests_root_dir), &mut configs)?;
+    configs.sort_by(|a, b| a.name.cmp(&b.name));
     Ok(configs)
 }
 
+/// Parses the `test.toml` of every test and checks that its entries are consistent with its
+/// category, without running any tests. All problems are reported at once, rather than only the
+/// first one as when discovering the tests to run.
+fn validate_test_configs() -> Result<()> {
+    fn find_test_tomls(path: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+        if path.is_dir() {
+            for entry in std::fs::read_dir(path)? {
+                find_test_tomls(&entry?.path(), paths)?;
+            }
+        } else if path.file_name().map(|f| f == "test.toml").unwrap_or(false) {
+            paths.push(path.to_path_buf());
+        }
+        Ok(())
+    }
+
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let tests_root_dir = PathBuf::from(format!("{manifest_dir}/src/e2e_vm_tests/test_programs"));
+    let mut paths = vec![];
+    find_test_tomls(&tests_root_dir, &mut paths)?;
+    paths.sort();
+
+    let mut number_of_problems = 0;
+    let mut number_of_invalid_tests = 0;
+    for path in &paths {
+        let mut problems = match parse_test_toml(path) {
+            Ok(_) => vec![],
+            Err(err) => vec![err.to_string()],
+        };
+        if let Ok(toml_content) = std::fs::read_to_string(path)?.parse::<toml::Value>() {
+            problems.extend(test_toml_inconsistencies(&toml_content));
+        }
+        if problems.is_empty() {
+            continue;
+        }
+
+        let name = path
+            .parent()
+            .and_then(|dir| dir.strip_prefix(&tests_root_dir).ok())
+            .unwrap_or(path);
+        for problem in &problems {
+            println!("{}: {problem}", name.display().to_string().bold());
+        }
+        number_of_problems += problems.len();
+        number_of_invalid_tests += 1;
+    }
+
+    if number_of_problems > 0 {
+        bail!(
+            "Found {number_of_problems} problems in {number_of_invalid_tests} of {} test descriptions.",
+            paths.len()
+        );
+    }
+    println!("All {} test descriptions are valid.", paths.len());
+    Ok(())
+}
+
+/// Returns the entries of a `test.toml` that are unknown or have no effect for its category, which
+/// [parse_test_toml] silently ignores.
+fn test_toml_inconsistencies(toml_content: &toml::Value) -> Vec<String> {
+    const RUN_CATEGORIES: &[&str] = &["run", "run_on_node"];
+    const KEYS: &[(&str, &[&str])] = &[
+        ("category", &[]),
+        ("expected_warnings", &[]),
+        ("expected_to_fail", &[]),
+        ("expected_to_fail_reason", &[]),
+        ("supported_targets", &[]),
+        ("vm_features", &[]),
+        ("expected_result", RUN_CATEGORIES),
+        ("script_data", RUN_CATEGORIES),
+        ("cases", &["run"]),
+        ("witness_data", RUN_CATEGORIES),
+        ("max_gas", RUN_CATEGORIES),
+        ("forbid_return_data", &["run"]),
+        ("expected_receipt_count", RUN_CATEGORIES),
+        ("expected_logs", &["run"]),
+        ("package", RUN_CATEGORIES),
+        ("contracts", &["run_on_node"]),
+        ("node_setup", &["run_on_node"]),
+        ("validate_abi", &["compile", "run", "run_on_node"]),
+        ("validate_abi_packages", &["compile", "run", "run_on_node"]),
+        ("validate_storage_slots", &["compile"]),
+        ("max_bytecode_size", &["compile"]),
+        ("unit_test_logs", &["unit_tests_pass"]),
+    ];
+
+    let Some(table) = toml_content.as_table() else {
+        return vec![];
+    };
+    let category = table.get("category").and_then(|v| v.as_str());
+    let mut problems = vec![];
+    for key in table.keys() {
+        match KEYS.iter().find(|(known_key, _)| known_key == key) {
+            None => problems.push(format!("Unknown entry '{key}'.")),
+            Some((_, categories)) => match category {
+                // Disabled tests keep the entries of the category they are re-enabled with.
+                Some(category) if category != "disabled" => {
+                    if !categories.is_empty() && !categories.contains(&category) {
+                        problems.push(format!(
+                            "'{key}' has no effect on '{category}' tests, it's only supported by {}.",
+                            categories
+                                .iter()
+                                .map(|category| format!("'{category}'"))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ));
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+    if table.contains_key("expected_to_fail_reason")
+        && table.get("expected_to_fail").and_then(|v| v.as_bool()) != Some(true)
+    {
+        problems.push(
+            "'expected_to_fail_reason' is given but 'expected_to_fail' isn't set to true."
+                .to_string(),
+        );
+    }
+    problems
+}
+
+/// Returns the files under `test_programs` that differ from `rev`, relative to `test_programs`,
+/// including uncommitted changes and new files that aren't ignored.
+fn changed_test_files(rev: &str) -> Result<Vec<PathBuf>> {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let tests_root_dir = format!("{manifest_dir}/src/e2e_vm_tests/test_programs");
+    let git = |args: &[&str]| -> Result<String> {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&tests_root_dir)
+            .args(args)
+            .output()
+            .map_err(|e| anyhow!("failed to run git: {e}"))?;
+        if !output.status.success() {
+            bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let changed = git(&["diff", "--name-only", "--relative", rev, "--", "."])?;
+    let untracked = git(&["ls-files", "--others", "--exclude-standard", "--", "."])?;
+    Ok(changed
+        .lines()
+        .chain(untracked.lines())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Matches a test name against a glob pattern as a path, so that `*` doesn't match across
+/// directories while `**` does.
+fn glob_matches(pattern: &glob::Pattern, name: &str) -> bool {
+    pattern.matches_with(
+        name,
+        glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        },
+    )
+}
+
 const DIRECTIVE_RX: &str = r"(?m)^\s*#\s*(\w+):\s+(.*)$";
 
 fn build_file_checker(content: &str) -> Result<filecheck::Checker> {
@@ -595,7 +1693,7 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
 
     let checker = build_file_checker(&toml_content_str)?;
 
-    let toml_content = toml_content_str.parse::<toml::Value>()?;
+    let mut toml_content = toml_content_str.parse::<toml::Value>()?;
 
     if !toml_content.is_table() {
         bail!("Malformed test description.");
@@ -605,39 +1703,69 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
         .get("category")
         .ok_or_else(|| anyhow!("Missing mandatory 'category' entry."))
         .and_then(|category_val| match category_val.as_str() {
-            Some("run") => Ok(TestCategory::Runs),
-            Some("run_on_node") => Ok(TestCategory::RunsWithContract),
-            Some("fail") => Ok(TestCategory::FailsToCompile),
-            Some("compile") => Ok(TestCategory::Compiles),
-            Some("disabled") => Ok(TestCategory::Disabled),
-            Some("unit_tests_pass") => Ok(TestCategory::UnitTestsPass),
+            Some(category) => category.parse::<TestCategory>(),
             None => Err(anyhow!(
                 "Malformed category '{category_val}', should be a string."
             )),
-            Some(other) => Err(anyhow!("Unknown category '{}'.", other)),
         })?;
 
+    // The expectations of an `expected.json` next to the `test.toml` supersede those given in it.
+    if let Some(expected) = read_expected_json(path)? {
+        if !matches!(
+            category,
+            TestCategory::Runs | TestCategory::RunsWithContract
+        ) {
+            bail!("'expected.json' is only supported by 'run' and 'run_on_node' tests.");
+        }
+        if let Some(table) = toml_content.as_table_mut() {
+            for (key, value) in expected {
+                table.insert(key, value);
+            }
+        }
+    }
+
     // Abort early if we find a FailsToCompile test without any Checker directives.
     if category == TestCategory::FailsToCompile && checker.is_empty() {
         bail!("'fail' tests must contain some FileCheck verification directives.");
     }
+    if matches!(category, TestCategory::Lexes | TestCategory::Parses) && checker.is_empty() {
+        bail!("'lex' and 'parse' tests must contain some FileCheck verification directives.");
+    }
 
-    let script_data = match &category {
-        TestCategory::Runs | TestCategory::RunsWithContract => {
-            match toml_content.get("script_data") {
-                Some(toml::Value::String(v)) => {
-                    let decoded = hex::decode(v)
-                        .map_err(|e| anyhow!("Invalid hex value for 'script_data': {}", e))?;
-                    Some(decoded)
-                }
-                Some(_) => {
-                    bail!("Expected 'script_data' to be a hex string.");
+    let run_cases = match toml_content.get("cases") {
+        None => Vec::new(),
+        Some(_) if category != TestCategory::Runs => {
+            bail!("Only 'run' tests may have 'cases'.");
+        }
+        Some(_) if toml_content.get("script_data").is_some() => {
+            bail!("'script_data' must be given for each of the 'cases' instead.");
+        }
+        Some(_) if toml_content.get("expected_result").is_some() => {
+            bail!("'expected_result' must be given for each of the 'cases' instead.");
+        }
+        Some(toml::Value::Array(cases)) if !cases.is_empty() => cases
+            .iter()
+            .map(|case| {
+                if !case.is_table() {
+                    bail!("Expected each of the 'cases' to be a table.");
                 }
-                _ => None,
-            }
+                Ok(RunCase {
+                    script_data: get_script_data(case)?,
+                    expected_result: get_expected_result(case)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?,
+        Some(_) => {
+            bail!("Expected 'cases' to be a non-empty array of tables.");
         }
+    };
+
+    let script_data = match &category {
+        TestCategory::Runs | TestCategory::RunsWithContract => get_script_data(&toml_content)?,
         TestCategory::Compiles
         | TestCategory::FailsToCompile
+        | TestCategory::Lexes
+        | TestCategory::Parses
         | TestCategory::UnitTestsPass
         | TestCategory::Disabled => None,
     };
@@ -670,16 +1798,21 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
         }
         TestCategory::Compiles
         | TestCategory::FailsToCompile
+        | TestCategory::Lexes
+        | TestCategory::Parses
         | TestCategory::UnitTestsPass
         | TestCategory::Disabled => None,
     };
 
     let expected_result = match &category {
+        TestCategory::Runs if !run_cases.is_empty() => None,
         TestCategory::Runs | TestCategory::RunsWithContract => {
             Some(get_expected_result(&toml_content)?)
         }
         TestCategory::Compiles
         | TestCategory::FailsToCompile
+        | TestCategory::Lexes
+        | TestCategory::Parses
         | TestCategory::UnitTestsPass
         | TestCategory::Disabled => None,
     };
@@ -700,11 +1833,44 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
             })?,
     };
 
+    let expected_to_fail = toml_content
+        .get("expected_to_fail")
+        .map(|v| v.as_bool().unwrap_or(false))
+        .unwrap_or(false);
+
+    let expected_to_fail_reason = toml_content
+        .get("expected_to_fail_reason")
+        .map(|v| {
+            v.as_str().map(str::to_string).ok_or_else(|| {
+                anyhow!("Malformed 'expected_to_fail_reason' entry, should be a string.")
+            })
+        })
+        .transpose()?;
+
     let validate_abi = toml_content
         .get("validate_abi")
         .map(|v| v.as_bool().unwrap_or(false))
         .unwrap_or(false);
 
+    let validate_abi_packages = toml_content
+        .get("validate_abi_packages")
+        .map(|v| {
+            v.as_array()
+                .and_then(|packages| {
+                    packages
+                        .iter()
+                        .map(|package| package.as_str().map(str::to_string))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| {
+                    anyhow!("Expected 'validate_abi_packages' to be an array of strings.")
+                })
+        })
+        .transpose()?;
+    if validate_abi_packages.is_some() && !validate_abi {
+        bail!("'validate_abi_packages' requires 'validate_abi = true'.");
+    }
+
     let expected_warnings = u32::try_from(
         toml_content
             .get("expected_warnings")
@@ -713,6 +1879,92 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
     )
     .unwrap_or(0u32);
 
+    let max_gas = toml_content
+        .get("max_gas")
+        .map(|v| {
+            v.as_integer()
+                .and_then(|v| u64::try_from(v).ok())
+                .ok_or_else(|| anyhow!("Expected 'max_gas' to be a non-negative integer."))
+        })
+        .transpose()?;
+
+    let max_bytecode_size = toml_content
+        .get("max_bytecode_size")
+        .map(|v| {
+            v.as_integer()
+                .and_then(|v| usize::try_from(v).ok())
+                .ok_or_else(|| {
+                    anyhow!("Expected 'max_bytecode_size' to be a non-negative integer.")
+                })
+        })
+        .transpose()?;
+    if max_bytecode_size.is_some() && category != TestCategory::Compiles {
+        bail!("'max_bytecode_size' is only supported by 'compile' tests.");
+    }
+
+    let expected_receipt_count = toml_content
+        .get("expected_receipt_count")
+        .map(|v| {
+            v.as_integer()
+                .and_then(|v| usize::try_from(v).ok())
+                .ok_or_else(|| {
+                    anyhow!("Expected 'expected_receipt_count' to be a non-negative integer.")
+                })
+        })
+        .transpose()?;
+
+    let expected_logs = toml_content
+        .get("expected_logs")
+        .map(|v| {
+            v.as_array()
+                .ok_or_else(|| anyhow!("Expected 'expected_logs' to be an array."))?
+                .iter()
+                .map(get_unit_test_log)
+                .collect::<Result<Vec<_>>>()
+        })
+        .transpose()?;
+    if expected_logs.is_some() && category != TestCategory::Runs {
+        bail!("'expected_logs' is only supported by 'run' tests.");
+    }
+
+    let forbid_return_data = toml_content
+        .get("forbid_return_data")
+        .map(|v| {
+            v.as_bool()
+                .ok_or_else(|| anyhow!("Expected 'forbid_return_data' to be a boolean."))
+        })
+        .transpose()?
+        .unwrap_or(false);
+    if forbid_return_data && category != TestCategory::Runs {
+        bail!("'forbid_return_data' is only supported by 'run' tests.");
+    }
+
+    let package = toml_content
+        .get("package")
+        .map(|v| {
+            v.as_str()
+                .map(|package| package.to_owned())
+                .ok_or_else(|| anyhow!("Expected 'package' to be a string."))
+        })
+        .transpose()?;
+
+    let unit_test_logs = match toml_content.get("unit_test_logs") {
+        None => HashMap::new(),
+        Some(toml::Value::Table(tests)) => tests
+            .iter()
+            .map(|(test_name, logs)| {
+                let logs = logs
+                    .as_array()
+                    .ok_or_else(|| anyhow!("Expected the logs of '{test_name}' to be an array."))?
+                    .iter()
+                    .map(get_unit_test_log)
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((test_name.clone(), logs))
+            })
+            .collect::<Result<HashMap<_, _>>>()?,
+        Some(_) => bail!("Expected 'unit_test_logs' to be a table."),
+    };
+
     let validate_storage_slots = toml_content
         .get("validate_storage_slots")
         .map(|v| v.as_bool().unwrap_or(false))
@@ -733,6 +1985,19 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
         .map(|s| s.to_owned())
         .unwrap();
 
+    let contract_paths = contract_paths
+        .iter()
+        .map(|contract_path| expand_contract_path(contract_path, &name))
+        .collect::<Result<Vec<_>>>()?;
+
+    let node_setup = match toml_content.get("node_setup") {
+        None => harness::NodeSetup::default(),
+        Some(_) if category != TestCategory::RunsWithContract => {
+            bail!("'node_setup' is only supported by 'run_on_node' tests.");
+        }
+        Some(node_setup) => get_node_setup(node_setup, &name, &contract_paths)?,
+    };
+
     // Check for supported build target for each test. For now we assume that the
     // the default is that only Fuel VM target is supported. Once the other targets
     // get to a fully usable state, we should update this.
@@ -750,21 +2015,252 @@ fn parse_test_toml(path: &Path) -> Result<TestDescription> {
         supported_targets
     });
 
+    for expected_result in expected_result
+        .iter()
+        .chain(run_cases.iter().map(|case| &case.expected_result))
+    {
+        expected_result.check_targets(&supported_targets)?;
+        if forbid_return_data && expected_result.expects_return_data() {
+            bail!("'forbid_return_data' is set, but an 'expected_result' returns data.");
+        }
+    }
+
+    let vm_features = toml_content
+        .get("vm_features")
+        .map(|v| v.as_array().cloned().unwrap_or_default())
+        .unwrap_or_default()
+        .iter()
+        .map(|feature| {
+            feature.as_str().map(str::to_string).ok_or_else(|| {
+                anyhow!("Malformed 'vm_features' entry '{feature}', should be a string.")
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
     Ok(TestDescription {
         name,
         category,
         script_data,
         witness_data,
         expected_result,
+        run_cases,
         expected_warnings,
+        max_gas,
+        forbid_return_data,
+        max_bytecode_size,
+        expected_receipt_count,
+        expected_logs,
+        package,
+        unit_test_logs,
         contract_paths,
+        node_setup,
         validate_abi,
+        validate_abi_packages,
         validate_storage_slots,
         supported_targets,
+        vm_features,
+        expected_to_fail,
+        expected_to_fail_reason,
         checker,
     })
 }
 
+/// Expands the `${VAR}` placeholders of a `contracts` entry of the test `name`, and resolves the
+/// path relative to `test_programs`.
+///
+/// `${TEST_DIR}` is the directory of the test, and any other placeholder is the value of the
+/// environment variable of that name. Paths that resolve to outside of `test_programs` are an
+/// error, as are unknown variables.
+fn expand_contract_path(contract_path: &str, name: &str) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = contract_path;
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let Some(len) = rest[start + 2..].find('}') else {
+            bail!("Unterminated '${{' in contract path '{contract_path}'.");
+        };
+        let var = &rest[start + 2..start + 2 + len];
+        match var {
+            "TEST_DIR" => expanded.push_str(name),
+            _ => match std::env::var(var) {
+                Ok(value) => expanded.push_str(&value),
+                Err(_) => bail!(
+                    "Unknown variable '{var}' in contract path '{contract_path}', it's neither \
+                    `TEST_DIR` nor set in the environment."
+                ),
+            },
+        }
+        rest = &rest[start + 2 + len + 1..];
+    }
+    expanded.push_str(rest);
+
+    // Normalize the path, so that the same contract is only deployed once however it's referred to.
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let tests_root_dir = PathBuf::from(format!("{manifest_dir}/src/e2e_vm_tests/test_programs"));
+    let mut resolved = PathBuf::new();
+    for component in tests_root_dir.join(&expanded).components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
+        }
+    }
+    resolved
+        .strip_prefix(&tests_root_dir)
+        .ok()
+        .and_then(|path| path.to_str())
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            anyhow!(
+                "Contract path '{contract_path}' resolves to {}, which isn't a directory under \
+                `test_programs`.",
+                resolved.display()
+            )
+        })
+}
+
+/// The entries an `expected.json` may contain, which supersede those of the `test.toml`.
+const EXPECTED_JSON_KEYS: &[&str] = &[
+    "expected_result",
+    "expected_logs",
+    "max_gas",
+    "expected_receipt_count",
+];
+
+/// Reads the `expected.json` next to the `test.toml` at `test_toml_path`, if there's one, as the
+/// equivalent TOML entries.
+fn read_expected_json(test_toml_path: &Path) -> Result<Option<toml::Table>> {
+    let path = test_toml_path.with_file_name("expected.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)
+        .map_err(|e| anyhow!("Malformed 'expected.json': {e}"))?;
+    let toml::Value::Table(table) = json_to_toml(&json)? else {
+        bail!("Malformed 'expected.json', should be an object.");
+    };
+    if let Some(key) = table
+        .keys()
+        .find(|key| !EXPECTED_JSON_KEYS.contains(&key.as_str()))
+    {
+        bail!(
+            "Unknown entry '{key}' in 'expected.json', it only supports {}.",
+            EXPECTED_JSON_KEYS
+                .iter()
+                .map(|key| format!("'{key}'"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    Ok(Some(table))
+}
+
+fn json_to_toml(json: &serde_json::Value) -> Result<toml::Value> {
+    Ok(match json {
+        serde_json::Value::Null => bail!("'null' isn't supported in 'expected.json'."),
+        serde_json::Value::Bool(b) => toml::Value::Boolean(*b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(n) => toml::Value::Integer(n),
+            None => bail!("Unsupported number '{n}' in 'expected.json', only integers up to `i64::MAX` are supported."),
+        },
+        serde_json::Value::String(s) => toml::Value::String(s.clone()),
+        serde_json::Value::Array(items) => {
+            toml::Value::Array(items.iter().map(json_to_toml).collect::<Result<_>>()?)
+        }
+        serde_json::Value::Object(entries) => toml::Value::Table(
+            entries
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), json_to_toml(value)?)))
+                .collect::<Result<_>>()?,
+        ),
+    })
+}
+
+fn get_unit_test_log(value: &toml::Value) -> Result<UnitTestLog> {
+    match value {
+        toml::Value::Integer(v) => u64::try_from(*v)
+            .map(UnitTestLog::Value)
+            .map_err(|_| anyhow!("Logged values must be non-negative integers.")),
+        toml::Value::String(v) => hex::decode(v.trim_start_matches("0x"))
+            .map(UnitTestLog::Data)
+            .map_err(|e| anyhow!("Invalid hex value for logged data: {}", e)),
+        _ => Err(anyhow!(
+            "Logged values must be integers or hex strings, found '{value}'."
+        )),
+    }
+}
+
+/// Derives the seed of a single test from the seed of the whole run, so that the randomized data
+/// of a test doesn't depend on which other tests are run.
+fn test_seed(seed: u64, name: &str) -> u64 {
+    // FNV-1a, which unlike `DefaultHasher` is stable across Rust versions.
+    name.bytes()
+        .fold(0xcbf2_9ce4_8422_2325 ^ seed, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// Checks the number of receipts a test produced against its `expected_receipt_count`.
+fn check_receipt_count(expected: usize, actual: usize) -> Result<()> {
+    if expected != actual {
+        bail!("Unexpected number of receipts.\nexpected receipts: {expected}\nactual receipts: {actual}");
+    }
+    Ok(())
+}
+
+/// Returns the path of the `test.toml` of the test `name`.
+fn test_toml_path(name: &str) -> PathBuf {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(format!(
+        "{manifest_dir}/src/e2e_vm_tests/test_programs/{name}/test.toml"
+    ))
+}
+
+/// Rewrites the `max_gas` entry of the test's `test.toml` to the given value, or of its
+/// `expected.json` if the budget is given there.
+fn update_max_gas(name: &str, gas_used: u64) -> Result<()> {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let json_path = format!("{manifest_dir}/src/e2e_vm_tests/test_programs/{name}/expected.json");
+    if let Ok(content) = std::fs::read_to_string(&json_path) {
+        let mut json: serde_json::Value = serde_json::from_str(&content)?;
+        if let Some(max_gas) = json.get_mut("max_gas") {
+            *max_gas = gas_used.into();
+            let content = serde_json::to_string_pretty(&json)? + "\n";
+            std::fs::write(&json_path, &content)?;
+            watch::record_written_file(json_path, content);
+            return Ok(());
+        }
+    }
+    let path = format!("{manifest_dir}/src/e2e_vm_tests/test_programs/{name}/test.toml");
+    let content = std::fs::read_to_string(&path)?;
+    let re = Regex::new(r"(?m)^max_gas\s*=.*$").unwrap();
+    let content = re.replace(&content, format!("max_gas = {gas_used}"));
+    std::fs::write(&path, content.as_ref())?;
+    watch::record_written_file(path, content.as_bytes());
+    Ok(())
+}
+
+/// Rewrites the `expected_warnings` entry of the test's `test.toml` to the given value, adding it
+/// after the `category` entry if it's missing. Returns the path of the rewritten file.
+fn update_expected_warnings(name: &str, warnings: usize) -> Result<String> {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let path = format!("{manifest_dir}/src/e2e_vm_tests/test_programs/{name}/test.toml");
+    let content = std::fs::read_to_string(&path)?;
+    let re = Regex::new(r"(?m)^expected_warnings\s*=.*$").unwrap();
+    let content = if re.is_match(&content) {
+        re.replace(&content, format!("expected_warnings = {warnings}"))
+    } else {
+        let category = Regex::new(r"(?m)^category\s*=.*$").unwrap();
+        category.replace(&content, format!("$0\nexpected_warnings = {warnings}"))
+    };
+    std::fs::write(&path, content.as_ref())?;
+    watch::record_written_file(&path, content.as_bytes());
+    Ok(path)
+}
+
 fn get_test_abi_from_value(value: &toml::Value) -> Result<BuildTarget> {
     match value.as_str() {
         Some(target) => match BuildTarget::from_str(target) {
@@ -775,43 +2271,226 @@ fn get_test_abi_from_value(value: &toml::Value) -> Result<BuildTarget> {
     }
 }
 
-fn get_expected_result(toml_content: &toml::Value) -> Result<TestResult> {
-    fn get_action_value(action: &toml::Value, expected_value: &toml::Value) -> Result<TestResult> {
+/// Parses the `node_setup` table of the `run_on_node` test `name`, with the `coins` and `storage`
+/// the node is seeded with. Contracts are given by path, like the `contracts` of the test, and
+/// must be among them.
+fn get_node_setup(
+    node_setup: &toml::Value,
+    name: &str,
+    contract_paths: &[String],
+) -> Result<harness::NodeSetup> {
+    let table = node_setup
+        .as_table()
+        .ok_or_else(|| anyhow!("Expected 'node_setup' to be a table."))?;
+    if let Some(key) = table
+        .keys()
+        .find(|key| !["coins", "storage"].contains(&key.as_str()))
+    {
+        bail!("Unknown entry '{key}' in 'node_setup'.");
+    }
+    let contract_path = |entry: &toml::Value, table_name: &str| -> Result<String> {
+        let contract_path = entry
+            .get("contract")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow!("Expected 'contract' of '{table_name}' to be a path string."))?;
+        let contract_path = expand_contract_path(contract_path, name)?;
+        if !contract_paths.contains(&contract_path) {
+            bail!("Contract '{contract_path}' of '{table_name}' isn't in 'contracts'.");
+        }
+        Ok(contract_path)
+    };
+    let entries = |table_name: &str| -> Result<&[toml::Value]> {
+        match table.get(table_name) {
+            None => Ok(&[]),
+            Some(toml::Value::Array(entries)) => Ok(entries.as_slice()),
+            Some(_) => bail!("Expected '{table_name}' of 'node_setup' to be an array of tables."),
+        }
+    };
+
+    let coins = entries("coins")?
+        .iter()
+        .map(|coin| {
+            let recipient = match (coin.get("address"), coin.get("contract")) {
+                (Some(address), None) => harness::CoinRecipient::Address(fuel_tx::Address::new(
+                    get_bytes32(address, "address")?,
+                )),
+                (None, Some(_)) => harness::CoinRecipient::Contract(contract_path(coin, "coins")?),
+                _ => bail!("Expected exactly one of 'address' or 'contract' in 'coins'."),
+            };
+            let amount = coin
+                .get("amount")
+                .and_then(|v| v.as_integer())
+                .and_then(|v| u64::try_from(v).ok())
+                .ok_or_else(|| {
+                    anyhow!("Expected 'amount' of 'coins' to be a non-negative integer.")
+                })?;
+            let asset_id = coin
+                .get("asset_id")
+                .map(|asset_id| get_bytes32(asset_id, "asset_id").map(fuel_tx::AssetId::new))
+                .transpose()?
+                .unwrap_or(fuel_tx::AssetId::BASE);
+            Ok(harness::SeededCoin {
+                recipient,
+                amount,
+                asset_id,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut storage = HashMap::<String, Vec<fuel_tx::StorageSlot>>::new();
+    for slot in entries("storage")? {
+        let contract_path = contract_path(slot, "storage")?;
+        let key = slot
+            .get("key")
+            .ok_or_else(|| anyhow!("Expected a 'key' in 'storage'."))?;
+        let value = slot
+            .get("value")
+            .ok_or_else(|| anyhow!("Expected a 'value' in 'storage'."))?;
+        storage
+            .entry(contract_path)
+            .or_default()
+            .push(fuel_tx::StorageSlot::new(
+                get_bytes32(key, "key")?.into(),
+                get_bytes32(value, "value")?.into(),
+            ));
+    }
+
+    Ok(harness::NodeSetup { coins, storage })
+}
+
+/// Parses a hex string of 32 bytes, with an optional `0x` prefix, from the entry `entry_name`.
+fn get_bytes32(value: &toml::Value, entry_name: &str) -> Result<[u8; 32]> {
+    let hex_str = value
+        .as_str()
+        .ok_or_else(|| anyhow!("Expected '{entry_name}' to be a hex string."))?;
+    let bytes = hex::decode(hex_str.trim_start_matches("0x"))
+        .map_err(|e| anyhow!("Invalid hex value for '{entry_name}': {e}"))?;
+    <[u8; 32]>::try_from(bytes).map_err(|bytes| {
+        anyhow!(
+            "Expected '{entry_name}' to be 32 bytes, found {}.",
+            bytes.len()
+        )
+    })
+}
+
+/// Parses the optional `script_data` entry, a hex string, of a test or of one of its `cases`.
+fn get_script_data(toml_content: &toml::Value) -> Result<Option<Vec<u8>>> {
+    match toml_content.get("script_data") {
+        Some(toml::Value::String(v)) => {
+            let decoded = hex::decode(v)
+                .map_err(|e| anyhow!("Invalid hex value for 'script_data': {}", e))?;
+            Ok(Some(decoded))
+        }
+        Some(_) => {
+            bail!("Expected 'script_data' to be a hex string.");
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Parses the `expected_result` entry, which is either a single result or a table of results keyed
+/// by build target, e.g. `[expected_result.fuel]` and `[expected_result.evm]`.
+fn get_expected_result(toml_content: &toml::Value) -> Result<ExpectedResult> {
+    let expected_result = toml_content
+        .get("expected_result")
+        .ok_or_else(|| anyhow!("Could not find mandatory 'expected_result' entry."))?;
+    // A single result always has an action, which isn't a build target.
+    let Some(results) = expected_result
+        .as_table()
+        .filter(|table| !table.contains_key("action"))
+    else {
+        return get_target_expected_result(expected_result).map(ExpectedResult::AllTargets);
+    };
+    results
+        .iter()
+        .map(|(target, expected_result)| {
+            let target = BuildTarget::from_str(target).map_err(|_| {
+                anyhow!("Unknown build target '{target}' in 'expected_result' entry.")
+            })?;
+            Ok((target, get_target_expected_result(expected_result)?))
+        })
+        .collect::<Result<HashMap<_, _>>>()
+        .map(ExpectedResult::PerTarget)
+}
+
+/// Parses a single result of the `expected_result` entry, with an `action` and a `value`.
+fn get_target_expected_result(expected_result_table: &toml::Value) -> Result<TestResult> {
+    fn get_action_value(
+        action: &toml::Value,
+        expected_value: &toml::Value,
+        signed: bool,
+        prefix: bool,
+    ) -> Result<TestResult> {
+        // Negative values are only allowed for signed results, rather than silently wrapping.
+        let word = |v: i64| {
+            if v < 0 && !signed {
+                Err(anyhow!(
+                    "Negative value {v} in 'expected_result', add `signed = true` to expect a \
+                    two's complement signed integer."
+                ))
+            } else {
+                Ok(v as u64)
+            }
+        };
         match (action.as_str(), expected_value) {
             // A simple integer value.
-            (Some("return"), toml::Value::Integer(v)) => Ok(TestResult::Return(*v as u64)),
+            (Some("return"), toml::Value::Integer(v)) if signed => Ok(TestResult::SignedReturn(*v)),
+            (Some("return"), toml::Value::Integer(v)) => Ok(TestResult::Return(word(*v)?)),
 
             // Also a simple integer value, but is a result from a contract call.
-            (Some("result"), toml::Value::Integer(v)) => Ok(TestResult::Result(*v as Word)),
+            (Some("result"), toml::Value::Integer(v)) => Ok(TestResult::Result(word(*v)?)),
 
             // A bytes32 value.
             (Some("return_data"), toml::Value::String(v)) => hex::decode(v)
-                .map(TestResult::ReturnData)
+                .map(if prefix {
+                    TestResult::ReturnDataPrefix
+                } else {
+                    TestResult::ReturnData
+                })
                 .map_err(|e| anyhow!("Invalid hex value for 'return_data': {}", e)),
 
-            // Revert with a specific code.
+            // Revert with a specific code. Codes above `i64::MAX`, e.g. those of the standard library,
+            // can only be written as negative integers in TOML, so they always wrap.
             (Some("revert"), toml::Value::Integer(v)) => Ok(TestResult::Revert(*v as u64)),
 
+            // A VM panic with a specific reason.
+            (Some("panic"), toml::Value::String(v)) => Ok(TestResult::Panic(v.clone())),
+
             _otherwise => Err(anyhow!("Malformed action value: {action} {expected_value}")),
         }
     }
 
-    toml_content
-        .get("expected_result")
-        .ok_or_else(|| anyhow!( "Could not find mandatory 'expected_result' entry."))
-        .and_then(|expected_result_table| {
+    expected_result_table
+        .get("action")
+        .ok_or_else(|| {
+            anyhow!("Could not find mandatory 'action' field in 'expected_result' entry.")
+        })
+        .and_then(|action| {
+            let signed = match expected_result_table.get("signed") {
+                None => false,
+                Some(signed) => signed.as_bool().ok_or_else(|| {
+                    anyhow!("Malformed 'signed' field in 'expected_result' entry, should be a boolean.")
+                })?,
+            };
+            let prefix = match expected_result_table.get("match").map(toml::Value::as_str) {
+                None | Some(Some("exact")) => false,
+                Some(Some("prefix")) if action.as_str() == Some("return_data") => true,
+                Some(Some("prefix")) => bail!(
+                    "'match = \"prefix\"' in 'expected_result' entry is only supported for 'return_data' actions."
+                ),
+                Some(_) => bail!(
+                    "Malformed 'match' field in 'expected_result' entry, should be \"exact\" or \"prefix\"."
+                ),
+            };
+            // Panics are described by their reason rather than a value.
+            let field = if action.as_str() == Some("panic") { "reason" } else { "value" };
             expected_result_table
-                .get("action")
+                .get(field)
                 .ok_or_else(|| {
-                    anyhow!("Could not find mandatory 'action' field in 'expected_result' entry.")
+                    anyhow!("Could not find mandatory '{field}' field in 'expected_result' entry.")
                 })
-                .and_then(|action| {
-                    expected_result_table
-                        .get("value")
-                        .ok_or_else(|| {
-                            anyhow!("Could not find mandatory 'value' field in 'expected_result' entry.")
-                        })
-                        .and_then(|expected_value| get_action_value(action, expected_value))
+                .and_then(|expected_value| {
+                    get_action_value(action, expected_value, signed, prefix)
                 })
         })
 }
@@ -0,0 +1,11 @@
+script;
+
+fn main() -> bool {
+    let a: u64 = u64::max();
+    let b: u64 = 1;
+
+    let result: u64 = a + b;
+    log(result);
+
+    true
+}
@@ -0,0 +1,7 @@
+script;
+
+fn main() -> u64 {
+    0
+}
+
+let x = 1;
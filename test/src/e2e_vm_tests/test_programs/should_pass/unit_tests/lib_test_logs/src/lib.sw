@@ -0,0 +1,25 @@
+library;
+
+use std::logging::log;
+
+#[test]
+fn test_log_sum() {
+    let mut sum = 0;
+    let mut i = 1;
+    while i <= 2 {
+        sum += i;
+        log(sum);
+        i += 1;
+    }
+    log(sum);
+}
+
+#[test]
+fn test_log_b256() {
+    log(0x0000000000000000000000000000000000000000000000000000000000000001);
+}
+
+#[test]
+fn test_without_expected_logs() {
+    log(42);
+}
@@ -0,0 +1,194 @@
+//! Reruns the e2e tests whenever a test program or a watched compiler source changes, for a fast
+//! edit-test loop.
+//!
+//! Changes are debounced, so that saving several files at once triggers a single run. If all the
+//! changed files belong to tests, only those tests are rerun. Any other change, e.g. to the
+//! compiler, reruns all the tests selected by the filters.
+//!
+//! Files written by the run itself, like build artifacts or the `test.toml` rewritten by
+//! `--update-gas`, don't trigger another run.
+
+use super::{discover_test_configs, run};
+use crate::{FilterConfig, RunConfig};
+use anyhow::Result;
+use colored::*;
+use notify::RecursiveMode;
+use notify_debouncer_mini::new_debouncer;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// How long to wait for further changes before rerunning the tests.
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The files the tests wrote into the test programs, with the contents they wrote.
+static WRITTEN_FILES: Mutex<BTreeMap<PathBuf, Vec<u8>>> = Mutex::new(BTreeMap::new());
+
+/// Records that running the tests wrote `contents` to `path`, so that the change doesn't trigger
+/// another run.
+pub(crate) fn record_written_file(path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) {
+    WRITTEN_FILES
+        .lock()
+        .unwrap()
+        .insert(path.into(), contents.into());
+}
+
+/// Returns whether `path` still holds the contents the tests last wrote to it. A file edited since
+/// then is considered changed.
+fn is_written_by_run(path: &Path) -> bool {
+    WRITTEN_FILES
+        .lock()
+        .unwrap()
+        .get(path)
+        .map_or(false, |written| {
+            std::fs::read(path).map_or(false, |contents| contents == *written)
+        })
+}
+
+/// Runs the tests selected by `filter_config`, then keeps rerunning the affected tests whenever a
+/// file under `test_programs` or `watch_paths` changes, until the process is interrupted.
+pub async fn watch(
+    filter_config: &FilterConfig,
+    run_config: &RunConfig,
+    watch_paths: &[PathBuf],
+) -> Result<()> {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let tests_root_dir = PathBuf::from(format!("{manifest_dir}/src/e2e_vm_tests/test_programs"));
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, None, move |events| match events {
+        Ok(events) => {
+            let _ = tx.send(events);
+        }
+        Err(errs) => tracing::warn!("Unable to watch for changes: {errs:?}"),
+    })?;
+    for path in std::iter::once(&tests_root_dir).chain(watch_paths) {
+        debouncer.watcher().watch(path, RecursiveMode::Recursive)?;
+    }
+
+    run_and_summarize(filter_config, run_config).await;
+    loop {
+        tracing::info!(
+            "Watching {} for changes...",
+            std::iter::once(&tests_root_dir)
+                .chain(watch_paths)
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let Some(events) = rx.recv().await else {
+            return Ok(());
+        };
+        let changed_files: Vec<PathBuf> = events
+            .into_iter()
+            .map(|event| event.path)
+            .filter(|path| !is_build_artifact(path) && !is_written_by_run(path))
+            .collect();
+        if changed_files.is_empty() {
+            continue;
+        }
+
+        let filter_config = match changed_test_files(&tests_root_dir, &changed_files) {
+            Some(files) => FilterConfig {
+                changed_files: Some(files),
+                ..filter_config.clone()
+            },
+            None => filter_config.clone(),
+        };
+        run_and_summarize(&filter_config, run_config).await;
+    }
+}
+
+/// Runs the selected tests and prints a one line summary of the outcome, without stopping the
+/// watch if they fail.
+async fn run_and_summarize(filter_config: &FilterConfig, run_config: &RunConfig) {
+    let start = Instant::now();
+    let result = run(filter_config, run_config).await;
+    let elapsed = start.elapsed().as_secs_f64();
+    match result {
+        Ok(()) => tracing::info!("{} in {elapsed:.1}s", "Tests passed".green().bold()),
+        Err(err) => tracing::info!("{} in {elapsed:.1}s: {err}", "Tests failed".red().bold()),
+    }
+}
+
+/// Returns whether `path` was written by building or running a test rather than edited, so that
+/// running the tests doesn't trigger another run.
+fn is_build_artifact(path: &Path) -> bool {
+    path.file_name().map_or(false, |name| {
+        name == "Forc.lock"
+            || name == "json_abi_output.json"
+            || name == "json_storage_slots_output.json"
+    }) || path
+        .components()
+        .any(|component| component.as_os_str() == "out" || component.as_os_str() == "target")
+}
+
+/// Returns the changed files relative to `test_programs` if all of them belong to a test, or `None`
+/// if any other file changed and all the tests have to be rerun.
+fn changed_test_files(tests_root_dir: &Path, changed_files: &[PathBuf]) -> Option<Vec<PathBuf>> {
+    let files = changed_files
+        .iter()
+        .map(|path| {
+            path.strip_prefix(tests_root_dir)
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let tests = discover_test_configs(None).ok()?;
+    files
+        .iter()
+        .all(|file| tests.iter().any(|t| file.starts_with(&t.name)))
+        .then_some(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_artifacts_are_ignored() {
+        for path in [
+            "should_pass/language/basic_func_decl/Forc.lock",
+            "should_pass/language/basic_func_decl/out/debug/basic_func_decl.bin",
+            "should_pass/language/basic_func_decl/target/debug/build",
+            "should_pass/language/basic_func_decl/json_abi_output.json",
+            "should_pass/language/basic_func_decl/json_storage_slots_output.json",
+        ] {
+            assert!(is_build_artifact(Path::new(path)), "{path}");
+        }
+        for path in [
+            "should_pass/language/basic_func_decl/src/main.sw",
+            "should_pass/language/basic_func_decl/Forc.toml",
+            "should_pass/language/basic_func_decl/test.toml",
+            "should_pass/language/basic_func_decl/expected.json",
+            "should_pass/language/basic_func_decl/json_abi_oracle.json",
+            "should_pass/language/basic_func_decl/json_storage_slots_oracle.json",
+        ] {
+            assert!(!is_build_artifact(Path::new(path)), "{path}");
+        }
+    }
+
+    #[test]
+    fn files_written_by_the_run_are_ignored_until_edited() {
+        let dir = std::env::temp_dir().join(format!("e2e_watch_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let test_toml = dir.join("test.toml");
+        let expected_json = dir.join("expected.json");
+        std::fs::write(&test_toml, "max_gas = 1\n").unwrap();
+        std::fs::write(&expected_json, "{}\n").unwrap();
+        assert!(!is_written_by_run(&test_toml));
+
+        std::fs::write(&test_toml, "max_gas = 2\n").unwrap();
+        record_written_file(&test_toml, "max_gas = 2\n");
+        assert!(is_written_by_run(&test_toml));
+        assert!(!is_written_by_run(&expected_json));
+
+        std::fs::write(&test_toml, "max_gas = 3\n").unwrap();
+        assert!(!is_written_by_run(&test_toml));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,7 +4,7 @@ mod ir_generation;
 use anyhow::Result;
 use clap::Parser;
 use forc_tracing::init_tracing_subscriber;
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 use sway_core::BuildTarget;
 use tracing::Instrument;
 
@@ -18,10 +18,31 @@ struct Cli {
     #[arg(long, short, value_name = "REGEX")]
     exclude: Option<regex::Regex>,
 
+    /// Only run the test with exactly this name, e.g. `should_pass/language/basic_func_decl`,
+    /// without discovering all the other tests
+    #[arg(long, value_name = "NAME")]
+    exact: Option<String>,
+
+    /// Only run tests whose path matches this glob, e.g. `should_pass/language/**`
+    #[arg(long, value_name = "GLOB")]
+    include_glob: Option<glob::Pattern>,
+
+    /// Exclude tests whose path matches this glob
+    #[arg(long, value_name = "GLOB")]
+    exclude_glob: Option<glob::Pattern>,
+
+    /// Only run tests whose files changed since this git revision, including uncommitted changes
+    #[arg(long, value_name = "REV")]
+    changed_since: Option<String>,
+
     /// Skip all tests until a test matches this regex
     #[arg(long, short, value_name = "REGEX")]
     skip_until: Option<regex::Regex>,
 
+    /// Only run tests of this category, e.g. `run`, `compile` or `fail`. Can be repeated
+    #[arg(long, value_name = "CATEGORY")]
+    category: Vec<String>,
+
     /// Only run tests with ABI JSON output validation
     #[arg(long, visible_alias = "abi")]
     abi_only: bool,
@@ -34,10 +55,44 @@ struct Cli {
     #[arg(long, visible_alias = "first")]
     first_only: bool,
 
+    /// Keep running, and rerun the affected tests whenever a test program or a watched path changes
+    #[arg(long)]
+    watch: bool,
+
+    /// Also rerun the tests when files under these paths change, e.g. compiler sources
+    #[arg(long, value_name = "PATH", num_args = 1.., requires = "watch")]
+    watch_paths: Vec<PathBuf>,
+
+    /// Stop running tests after the first failure
+    #[arg(long)]
+    fail_fast: bool,
+
+    /// Run each test this many times, reporting tests that only fail some of the times as flaky
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    repeat: u32,
+
+    /// Abort the run when a test reaches a path of the harness which isn't implemented yet,
+    /// instead of only failing that test
+    #[arg(long)]
+    strict_unimplemented: bool,
+
     /// Print out warnings and errors
     #[arg(long, env = "SWAY_TEST_VERBOSE")]
     verbose: bool,
 
+    /// Rewrite the `max_gas` budget of `run` tests to the gas they actually used
+    #[arg(long)]
+    update_gas: bool,
+
+    /// Rewrite the `expected_warnings` of tests that only fail because of their number of warnings
+    #[arg(long)]
+    update_warnings: bool,
+
     /// Intended for use in `CI` to ensure test lock files are up to date
     #[arg(long)]
     locked: bool,
@@ -45,16 +100,63 @@ struct Cli {
     /// Build target.
     #[arg(long, visible_alias = "target")]
     build_target: Option<String>,
+
+    /// Seed for randomized test data. A random seed is chosen if not given
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Run the tests in a random order derived from the seed, e.g. to find tests that depend on
+    /// each other. By default the tests run in the order of their names
+    #[arg(long)]
+    shuffle: bool,
+
+    /// Persist deployed contract IDs to this file and reuse them in later runs against the same node
+    #[arg(long, value_name = "PATH")]
+    deploy_cache: Option<PathBuf>,
+
+    /// Write the bytecode, IR and receipts of each failing `run` test to a directory named after
+    /// the test under this directory
+    #[arg(long, value_name = "DIR")]
+    dump_on_fail: Option<PathBuf>,
+
+    /// Write the bytecode, ABI and IR of each `run` test to a directory named after the test under
+    /// this directory, whether the test passes or not
+    #[arg(long, value_name = "DIR")]
+    keep_artifacts: Option<PathBuf>,
+
+    /// Only check the `test.toml` of every test, reporting all problems at once, without running
+    /// any tests
+    #[arg(long)]
+    validate_only: bool,
+
+    /// Write a JUnit XML report of the e2e test results to this file
+    #[arg(long, value_name = "PATH")]
+    junit: Option<PathBuf>,
+
+    /// Instead of running the tests, compile them this many times and report compile throughput
+    #[arg(long, value_name = "ITERATIONS")]
+    bench: Option<usize>,
+
+    /// Write the benchmark results as JSON to this file instead of stdout
+    #[arg(long, value_name = "PATH", requires = "bench")]
+    bench_output: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
 pub struct FilterConfig {
     pub include: Option<regex::Regex>,
     pub exclude: Option<regex::Regex>,
+    pub exact: Option<String>,
+    pub include_glob: Option<glob::Pattern>,
+    pub exclude_glob: Option<glob::Pattern>,
+    pub changed_since: Option<String>,
     pub skip_until: Option<regex::Regex>,
+    pub categories: Vec<String>,
     pub abi_only: bool,
     pub contract_only: bool,
     pub first_only: bool,
+    /// Only run the tests containing one of these files, relative to `test_programs`.
+    pub changed_files: Option<Vec<PathBuf>>,
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +164,19 @@ pub struct RunConfig {
     pub build_target: BuildTarget,
     pub locked: bool,
     pub verbose: bool,
+    pub fail_fast: bool,
+    /// The number of times each test is run.
+    pub repeat: u32,
+    pub strict_unimplemented: bool,
+    pub update_gas: bool,
+    pub update_warnings: bool,
+    pub seed: u64,
+    pub shuffle: bool,
+    pub deploy_cache: Option<PathBuf>,
+    pub junit: Option<PathBuf>,
+    pub dump_on_fail: Option<PathBuf>,
+    pub keep_artifacts: Option<PathBuf>,
+    pub validate_only: bool,
 }
 
 #[tokio::main]
@@ -73,10 +188,16 @@ async fn main() -> Result<()> {
     let filter_config = FilterConfig {
         include: cli.include,
         exclude: cli.exclude,
+        exact: cli.exact,
+        include_glob: cli.include_glob,
+        exclude_glob: cli.exclude_glob,
+        changed_since: cli.changed_since,
         skip_until: cli.skip_until,
+        categories: cli.category,
         abi_only: cli.abi_only,
         contract_only: cli.contract_only,
         first_only: cli.first_only,
+        changed_files: None,
     };
     let build_target = match cli.build_target {
         Some(target) => match BuildTarget::from_str(target.as_str()) {
@@ -88,16 +209,57 @@ async fn main() -> Result<()> {
     let run_config = RunConfig {
         locked: cli.locked,
         verbose: cli.verbose,
+        fail_fast: cli.fail_fast,
+        repeat: cli.repeat,
+        strict_unimplemented: cli.strict_unimplemented,
+        update_gas: cli.update_gas,
+        update_warnings: cli.update_warnings,
+        seed: cli.seed.unwrap_or_else(rand::random),
+        shuffle: cli.shuffle,
         build_target,
+        deploy_cache: cli.deploy_cache,
+        junit: cli.junit,
+        dump_on_fail: cli.dump_on_fail,
+        keep_artifacts: cli.keep_artifacts,
+        validate_only: cli.validate_only,
     };
 
+    tracing::info!(
+        "Using seed {}, pass `--seed {}` to reproduce this run",
+        run_config.seed,
+        run_config.seed
+    );
+
+    // Benchmark compile throughput instead of running the tests
+    if let Some(iterations) = cli.bench {
+        return e2e_vm_tests::bench(
+            &filter_config,
+            &run_config,
+            iterations,
+            cli.bench_output.as_deref(),
+        )
+        .instrument(tracing::trace_span!("Bench"))
+        .await;
+    }
+
+    // Rerun E2E tests on changes until interrupted
+    if cli.watch {
+        return e2e_vm_tests::watch(&filter_config, &run_config, &cli.watch_paths)
+            .instrument(tracing::trace_span!("Watch"))
+            .await;
+    }
+
     // Run E2E tests
     e2e_vm_tests::run(&filter_config, &run_config)
         .instrument(tracing::trace_span!("E2E"))
         .await?;
 
     // Run IR tests
-    if !filter_config.first_only {
+    if !run_config.validate_only
+        && !filter_config.first_only
+        && filter_config.exact.is_none()
+        && filter_config.changed_since.is_none()
+    {
         println!("\n");
         ir_generation::run(filter_config.include.as_ref(), cli.verbose)
             .instrument(tracing::trace_span!("IR"))